@@ -0,0 +1,346 @@
+// Copyright 2020 Zachary Stewart
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Line-based console that the game is played through.
+use std::io::{self, BufRead, Write};
+
+/// Line-based text console. The game flow only talks to the player through this trait,
+/// so it can be driven by something other than the real terminal.
+pub trait Console {
+    /// Show a prompt for input. The prompt is followed by a space and no newline.
+    fn prompt(&mut self, prompt: &str) -> io::Result<()>;
+
+    /// Read a single line of input. Returns `None` at the end of input.
+    fn read_command(&mut self) -> io::Result<Option<String>>;
+
+    /// Write a single line of output.
+    fn write_line(&mut self, line: &str) -> io::Result<()>;
+
+    /// Clear the screen.
+    #[allow(unused)]
+    fn clear_screen(&mut self) -> io::Result<()>;
+
+    /// Get the history of inputs accepted during this session.
+    fn history(&mut self) -> &mut History;
+}
+
+/// Inputs accepted during this session, which can be repeated with `!` references.
+#[derive(Default)]
+pub struct History {
+    entries: Vec<String>,
+}
+
+impl History {
+    /// Number of entries listed by the `history` command.
+    const SHOWN: usize = 20;
+
+    /// Record an accepted input.
+    pub fn push(&mut self, input: &str) {
+        self.entries.push(input.to_owned());
+    }
+
+    /// Iterate the most recent entries along with the index used to refer to them as
+    /// `!<index>`. Indexes start at 1 and count from the start of the session.
+    pub fn recent(&self) -> impl Iterator<Item = (usize, &str)> {
+        let skip = self.entries.len().saturating_sub(Self::SHOWN);
+        self.entries
+            .iter()
+            .enumerate()
+            .skip(skip)
+            .map(|(i, entry)| (i + 1, entry.as_str()))
+    }
+
+    /// Expand a history reference. `!!` is the previous input, `!<index>` is the input
+    /// with that index, and `!<text>` is the most recent input starting with `text`.
+    /// Input which doesn't start with `!` is returned unchanged. If the reference doesn't
+    /// match anything, returns the message to show the player.
+    pub fn expand<'a>(&'a self, input: &'a str) -> Result<&'a str, String> {
+        let reference = match input.strip_prefix('!') {
+            Some(reference) => reference,
+            None => return Ok(input),
+        };
+        let found = if reference == "!" {
+            self.entries.last()
+        } else if let Ok(index) = reference.parse::<usize>() {
+            index.checked_sub(1).and_then(|i| self.entries.get(i))
+        } else if reference.is_empty() {
+            None
+        } else {
+            self.entries
+                .iter()
+                .rev()
+                .find(|entry| entry.starts_with(reference))
+        };
+        found
+            .map(String::as_str)
+            .ok_or_else(|| format!("No history entry matches \"{}\".", input))
+    }
+}
+
+/// [`Console`] backed by stdout and the given input.
+pub struct Terminal<B> {
+    read: B,
+    history: History,
+}
+
+impl<B> Terminal<B> {
+    pub fn new(read: B) -> Self {
+        Self {
+            read,
+            history: History::default(),
+        }
+    }
+}
+
+impl<B: BufRead> Console for Terminal<B> {
+    fn prompt(&mut self, prompt: &str) -> io::Result<()> {
+        let mut stdout = io::stdout();
+        write!(stdout, "{} ", prompt)?;
+        stdout.flush()
+    }
+
+    fn read_command(&mut self) -> io::Result<Option<String>> {
+        let mut line = String::new();
+        if self.read.read_line(&mut line)? == 0 {
+            Ok(None)
+        } else {
+            Ok(Some(line))
+        }
+    }
+
+    fn write_line(&mut self, line: &str) -> io::Result<()> {
+        writeln!(io::stdout(), "{}", line)
+    }
+
+    fn clear_screen(&mut self) -> io::Result<()> {
+        let mut stdout = io::stdout();
+        write!(stdout, "\x1b[2J\x1b[H")?;
+        stdout.flush()
+    }
+
+    fn history(&mut self) -> &mut History {
+        &mut self.history
+    }
+}
+
+/// In-memory [`Console`] which reads from a fixed script of inputs and records everything
+/// written, for driving the game flow in tests.
+#[cfg(test)]
+pub struct Script {
+    inputs: std::collections::VecDeque<String>,
+    prompts: usize,
+    output: Vec<String>,
+    history: History,
+}
+
+#[cfg(test)]
+impl Script {
+    pub fn new<'a>(inputs: impl IntoIterator<Item = &'a str>) -> Self {
+        Self {
+            inputs: inputs.into_iter().map(str::to_owned).collect(),
+            prompts: 0,
+            output: Vec::new(),
+            history: History::default(),
+        }
+    }
+
+    /// Number of times a prompt was shown.
+    pub fn prompts(&self) -> usize {
+        self.prompts
+    }
+
+    /// Number of scripted inputs not yet read.
+    pub fn remaining(&self) -> usize {
+        self.inputs.len()
+    }
+
+    /// Lines written so far.
+    pub fn output(&self) -> &[String] {
+        &self.output
+    }
+
+    /// Whether any written line contains the given text.
+    pub fn wrote(&self, text: &str) -> bool {
+        self.output.iter().any(|line| line.contains(text))
+    }
+}
+
+#[cfg(test)]
+impl Console for Script {
+    fn prompt(&mut self, _prompt: &str) -> io::Result<()> {
+        self.prompts += 1;
+        Ok(())
+    }
+
+    fn read_command(&mut self) -> io::Result<Option<String>> {
+        Ok(self.inputs.pop_front())
+    }
+
+    fn write_line(&mut self, line: &str) -> io::Result<()> {
+        self.output.push(line.to_owned());
+        Ok(())
+    }
+
+    fn clear_screen(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn history(&mut self) -> &mut History {
+        &mut self.history
+    }
+}
+
+/// Repeatedly tries to read input until the input checker returns `Ok`. Converts to ascii
+/// lower before running the checker. If the checker returns `Err`, the message is written
+/// to the console before prompting again. History references are expanded before the
+/// checker runs, and accepted input is added to the history. At the end of input, returns
+/// an error with kind [`io::ErrorKind::UnexpectedEof`].
+pub fn read_input_lower<F, T>(
+    console: &mut dyn Console,
+    prompt: &str,
+    mut checker: F,
+) -> io::Result<T>
+where
+    F: FnMut(&str) -> Result<T, String>,
+{
+    loop {
+        let mut input = read_expanded(console, prompt)?;
+        input.make_ascii_lowercase();
+        match checker(&input) {
+            Ok(val) => {
+                console.history().push(&input);
+                return Ok(val);
+            }
+            Err(msg) => console.write_line(&msg)?,
+        }
+    }
+}
+
+/// Repeatedly tries to read input until the input checker returns `Ok`. If the checker
+/// returns `Err`, the message is written to the console before prompting again. History
+/// references are expanded before the checker runs, and accepted input is added to the
+/// history.
+pub fn read_input<F, T>(console: &mut dyn Console, prompt: &str, mut checker: F) -> io::Result<T>
+where
+    F: FnMut(&str) -> Result<T, String>,
+{
+    loop {
+        let input = read_expanded(console, prompt)?;
+        match checker(&input) {
+            Ok(val) => {
+                console.history().push(&input);
+                return Ok(val);
+            }
+            Err(msg) => console.write_line(&msg)?,
+        }
+    }
+}
+
+/// Read trimmed input, handling the `history` command and expanding history references.
+/// The expansion of a reference is echoed so the player can see what is being run.
+fn read_expanded(console: &mut dyn Console, prompt: &str) -> io::Result<String> {
+    loop {
+        let input = read_input_inner(console, prompt)?;
+        let input = input.trim();
+        if input.eq_ignore_ascii_case("history") {
+            let lines: Vec<_> = console
+                .history()
+                .recent()
+                .map(|(i, entry)| format!("{:>4}  {}", i, entry))
+                .collect();
+            for line in lines {
+                console.write_line(&line)?;
+            }
+            continue;
+        }
+        let expanded = console.history().expand(input).map(str::to_owned);
+        match expanded {
+            Ok(expanded) => {
+                if expanded != input {
+                    console.write_line(&expanded)?;
+                }
+                return Ok(expanded);
+            }
+            Err(msg) => console.write_line(&msg)?,
+        }
+    }
+}
+
+/// Helper to print the prompt and read a line.
+fn read_input_inner(console: &mut dyn Console, prompt: &str) -> io::Result<String> {
+    console.prompt(prompt)?;
+    match console.read_command()? {
+        Some(input) => Ok(input),
+        None => {
+            console.write_line("")?;
+            Err(io::ErrorKind::UnexpectedEof.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checker accepting only numbers.
+    fn number(input: &str) -> Result<u32, String> {
+        input
+            .parse()
+            .map_err(|_| format!("not a number: {}", input))
+    }
+
+    #[test]
+    fn retries_until_valid() {
+        let mut console = Script::new(vec!["abc", "", " 42 \n", "7"]);
+        assert_eq!(read_input(&mut console, ">", number).unwrap(), 42);
+        assert_eq!(console.prompts(), 3);
+        assert_eq!(console.remaining(), 1);
+        assert_eq!(console.output(), ["not a number: abc", "not a number: "]);
+    }
+
+    #[test]
+    fn lowercases_before_checking() {
+        let mut console = Script::new(vec!["YES"]);
+        let res = read_input_lower(&mut console, ">", |input| match input {
+            "yes" => Ok(true),
+            _ => Err("no".to_owned()),
+        });
+        assert!(res.unwrap());
+    }
+
+    #[test]
+    fn expands_history_references() {
+        let mut console = Script::new(vec!["12", "!!", "!5", "history", "!1"]);
+        assert_eq!(read_input(&mut console, ">", number).unwrap(), 12);
+        assert_eq!(read_input(&mut console, ">", number).unwrap(), 12);
+        assert_eq!(read_input(&mut console, ">", number).unwrap(), 12);
+        assert_eq!(
+            console.output(),
+            [
+                "12",
+                "No history entry matches \"!5\".",
+                "   1  12",
+                "   2  12",
+                "12",
+            ]
+        );
+    }
+
+    #[test]
+    fn end_of_input() {
+        let mut console = Script::new(vec!["x"]);
+        let err = read_input(&mut console, ">", number).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
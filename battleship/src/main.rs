@@ -12,26 +12,29 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{
-    fmt,
-    io::{self, BufRead, Write},
-    str, thread,
-    time::Duration,
-};
+use std::{fmt, io, str, thread, time::Duration};
 
 use clap::{App, Arg, ArgMatches};
 use once_cell::sync::Lazy;
-use rand::{distributions::Uniform, Rng};
+use rand::Rng;
 use regex::Regex;
 
-use spacebattleship::game::simple::{
-    CannotPlaceReason, CannotShootReason, Coordinate, Game, GameSetup, Orientation, Player, Ship,
-    ShotOutcome,
+use spacebattleship::{
+    board::rectangular::RectDimensions,
+    game::{
+        annotations::Note,
+        simple::{
+            ai::{Bot, Difficulty},
+            CannotPlaceReason, CannotShootReason, Coordinate, English, Game, GameSetup,
+            Orientation, Player, Ship, ShotOutcome, Spanish,
+        },
+    },
+    lexicon::Lexicon,
 };
 
-/// Range of valid coordinates for the standard 10x10 game.
-static COORD_RANGE: Lazy<Uniform<Coordinate>> =
-    Lazy::new(|| Uniform::new(Coordinate::new(0, 0), Coordinate::new(10, 10)));
+use crate::console::{read_input, read_input_lower, Console, Terminal};
+
+mod console;
 
 fn main() -> io::Result<()> {
     let matches = App::new("Battleship")
@@ -48,38 +51,58 @@ fn main() -> io::Result<()> {
                 .possible_values(&["human", "me", "computer", "bot", "random", "rand"])
                 .case_insensitive(true),
         )
+        .arg(
+            Arg::with_name("lang")
+                .short("l")
+                .long("lang")
+                .value_name("LANG")
+                .help("language for ship names and shot outcomes")
+                .takes_value(true)
+                .possible_values(&["en", "es"])
+                .default_value("en")
+                .case_insensitive(true),
+        )
         .get_matches();
 
     let stdin = std::io::stdin();
-    let mut input = InputReader::new(stdin.lock());
+    let mut console = Terminal::new(stdin.lock());
+    match play(&matches, &mut console) {
+        // Reaching the end of input quits the game.
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => Ok(()),
+        res => res,
+    }
+}
+
+/// Play a full game against the bot through the given console.
+fn play(matches: &ArgMatches, console: &mut dyn Console) -> io::Result<()> {
     let mut rng = rand::thread_rng();
+    let lexicon: &dyn Lexicon<Ship> = match matches.value_of("lang") {
+        Some(lang) if lang.eq_ignore_ascii_case("es") => &Spanish,
+        _ => &English,
+    };
 
-    let player = choose_player(&matches, &mut input)?;
+    let player = choose_player(matches, console)?;
     let bot = player.opponent();
+    let bot_player = Bot::new(Difficulty::Random);
 
     let mut setup = GameSetup::new();
-    choose_placements(&mut rng, &mut setup, player, &mut input)?;
-    choose_random_placements(&mut rng, &mut setup, bot);
+    choose_placements(&mut rng, &mut setup, player, lexicon, console)?;
+    bot_player.place_ships(&mut setup, bot, &mut rng);
     let mut game = setup.start().map_err(|_| ()).unwrap();
 
     while game.winner().is_none() {
         if game.current() == player {
-            player_turn(&mut input, &mut game, player)?;
+            player_turn(console, &mut game, player, lexicon)?;
         } else {
-            bot_turn(&mut rng, &mut game, bot);
+            bot_turn(console, &bot_player, &mut rng, &mut game, bot, lexicon)?;
         }
     }
 
-    show_status(&game, player);
-
-    Ok(())
+    show_status(console, &game, player)
 }
 
 /// Choose which [`Player`] is the human player based on either args or cli input.
-fn choose_player<B: BufRead>(
-    matches: &ArgMatches,
-    input: &mut InputReader<B>,
-) -> io::Result<Player> {
+fn choose_player(matches: &ArgMatches, console: &mut dyn Console) -> io::Result<Player> {
     Ok(if let Some(clichoice) = matches.value_of("first_player") {
         match clichoice {
             "human" | "me" => Player::P1,
@@ -88,14 +111,15 @@ fn choose_player<B: BufRead>(
             _ => unreachable!(),
         }
     } else {
-        input.read_input_lower("Do you want to go first? (Y/n)", |input| match input {
-            "yes" | "y" | "first" | "1" | "1st" | "" => Some(Player::P1),
-            "no" | "n" | "second" | "2" | "2nd" => Some(Player::P2),
-            _ => {
-                println!("Invalid selection.");
-                None
-            }
-        })?
+        read_input_lower(
+            console,
+            "Do you want to go first? (Y/n)",
+            |input| match input {
+                "yes" | "y" | "first" | "1" | "1st" | "" => Ok(Player::P1),
+                "no" | "n" | "second" | "2" | "2nd" => Ok(Player::P2),
+                _ => Err("Invalid selection.".to_owned()),
+            },
+        )?
     })
 }
 
@@ -104,7 +128,8 @@ fn choose_placements(
     rng: &mut impl Rng,
     setup: &mut GameSetup,
     player: Player,
-    input: &mut InputReader<impl BufRead>,
+    lexicon: &dyn Lexicon<Ship>,
+    console: &mut dyn Console,
 ) -> io::Result<()> {
     enum Command {
         Done,
@@ -114,17 +139,17 @@ fn choose_placements(
         RandomizeRest,
         Help,
     }
-    println!();
-    println!("Place ships. Type help or ? for commands.");
+    console.write_line("")?;
+    console.write_line("Place ships. Type help or ? for commands.")?;
     loop {
-        println!();
+        console.write_line("")?;
         /// Matcher for commands with args.
         static PLACE: Lazy<Regex> = Lazy::new(|| {
             Regex::new(
                 r"^(?x)(?:place|put)\s+
         (?P<ship>\w+)\s+
         (?:(?:at|on|to|->|=>)\s+)?
-        (?P<x>[0-9]+)(?:\s*,\s*|\s+)(?P<y>[0-9]+)\s+
+        (?P<coord>[0-9]+(?:\s*,\s*|\s+)[0-9]+)\s+
         (?P<dir>\w+)$",
             )
             .unwrap()
@@ -138,74 +163,75 @@ fn choose_placements(
         });
 
         if setup.is_player_ready(player) {
-            println!("All ships placed, type done to start the game");
+            console.write_line("All ships placed, type done to start the game")?;
         } else {
-            let mut ships = setup.get_pending_ships(player);
-            print!(
-                "Remaining ships to place: {}",
-                ShipFullName(ships.next().unwrap())
-            );
-            for ship in ships {
-                print!(", {}", ShipFullName(ship));
-            }
-            println!();
+            let names: Vec<_> = setup
+                .get_pending_ships(player)
+                .map(|ship| lexicon.ship_name(&ship))
+                .collect();
+            console.write_line(&format!("Remaining ships to place: {}", names.join(", ")))?;
         }
-        println!("Your current board setup:");
-        show_setup_board(setup, player);
-        println!();
-
-        let cmd = input.read_input_lower("> ", |input| match input {
-            "?" | "help" | "h" => Some(Command::Help),
-            "randomize" | "rand" | "random" => Some(Command::RandomizeRest),
-            "done" | "start" => Some(Command::Done),
-            "clear" => Some(Command::Clear),
-            other => if let Some(captures) = PLACE.captures(other) {
-                let ship = match captures.name("ship").unwrap().as_str() {
-                    "cv" | "carrier" => Ship::Carrier,
-                    "bb" | "battleship" => Ship::Battleship,
-                    "ca" | "cl" | "cruiser" => Ship::Cruiser,
-                    "ss" | "sub" | "submarine" => Ship::Submarine,
-                    "dd" | "destroyer" => Ship::Destroyer,
-                    other => {
-                        println!("invalid ship: {}, choose \"carrier\", \"battleship\", \"cruiser\", \"submarine\", or \"destroyer\"", other);
-                        return None;
-                    }
-                };
-                let x = read_coord(captures.name("x").unwrap().as_str(), "x")?;
-                let y = read_coord(captures.name("y").unwrap().as_str(), "y")?;
-                let dir = match captures.name("dir").unwrap().as_str() {
-                    "up" | "north" | "u" | "n" => Orientation::Up,
-                    "down" | "south" | "d" | "s" => Orientation::Down,
-                    "left" | "west" | "l" | "w" => Orientation::Left,
-                    "right" | "east" | "r" | "e" => Orientation::Right,
-                    other => {
-                        println!("invalid direction {}, choose \"up\", \"down\", \"left\", or \"right\"", other);
-                        return None;
-                    }
-                };
-                Some(Command::Place(ship, Coordinate::new(x, y), dir))
-            } else if let Some(captures) = UNPLACE.captures(other) {
-                Some(Command::Unplace(match captures.name("ship").unwrap().as_str() {
-                    "cv" | "carrier" => Ship::Carrier,
-                    "bb" | "battleship" => Ship::Battleship,
-                    "ca" | "cl" | "cruiser" => Ship::Cruiser,
-                    "ss" | "sub" | "submarine" => Ship::Submarine,
-                    "dd" | "destroyer" => Ship::Destroyer,
-                    "all" => return Some(Command::Clear),
-                    other => {
-                        println!("invalid ship: {}, choose \"carrier\", \"battleship\", \"cruiser\", \"submarine\", \"destroyer\", or \"all\"", other);
-                        return None;
-                    }
-                }))
-            } else {
-                println!("Invalid ship-placement command \"{}\". Use '?' for help", other);
-                None
+        console.write_line("Your current board setup:")?;
+        show_setup_board(console, setup, player)?;
+        show_placement_summary(console, setup, player, lexicon)?;
+        console.write_line("")?;
+
+        let cmd = read_input_lower(console, "> ", |input| match input {
+            "?" | "help" | "h" => Ok(Command::Help),
+            "randomize" | "rand" | "random" => Ok(Command::RandomizeRest),
+            "done" | "start" => Ok(Command::Done),
+            "clear" => Ok(Command::Clear),
+            other => {
+                if let Some(captures) = PLACE.captures(other) {
+                    let ship = match parse_ship(lexicon, captures.name("ship").unwrap().as_str()) {
+                        Some(ship) => ship,
+                        None => {
+                            return Err(format!(
+                                "invalid ship: {}, choose {}",
+                                captures.name("ship").unwrap().as_str(),
+                                ship_choices(lexicon)
+                            ));
+                        }
+                    };
+                    let coord = read_coord(captures.name("coord").unwrap().as_str())?;
+                    let dir = match captures.name("dir").unwrap().as_str() {
+                        "up" | "north" | "u" | "n" => Orientation::Up,
+                        "down" | "south" | "d" | "s" => Orientation::Down,
+                        "left" | "west" | "l" | "w" => Orientation::Left,
+                        "right" | "east" | "r" | "e" => Orientation::Right,
+                        other => {
+                            return Err(format!("invalid direction {}, choose \"up\", \"down\", \"left\", or \"right\"", other));
+                        }
+                    };
+                    Ok(Command::Place(ship, coord, dir))
+                } else if let Some(captures) = UNPLACE.captures(other) {
+                    Ok(Command::Unplace(
+                        match captures.name("ship").unwrap().as_str() {
+                            "all" => return Ok(Command::Clear),
+                            other => match parse_ship(lexicon, other) {
+                                Some(ship) => ship,
+                                None => {
+                                    return Err(format!(
+                                        "invalid ship: {}, choose {}, or \"all\"",
+                                        other,
+                                        ship_choices(lexicon)
+                                    ));
+                                }
+                            },
+                        },
+                    ))
+                } else {
+                    Err(format!(
+                        "Invalid ship-placement command \"{}\". Use '?' for help",
+                        other
+                    ))
+                }
             }
         })?;
 
         match cmd {
             Command::Done if setup.is_player_ready(player) => break,
-            Command::Done => println!("You must place all your ships first!"),
+            Command::Done => console.write_line("You must place all your ships first!")?,
             Command::Place(ship, start, dir) => {
                 if setup.get_placement(player, ship).is_some() {
                     setup.unplace_ship(player, ship);
@@ -213,11 +239,16 @@ fn choose_placements(
                 match setup.place_ship(player, ship, start, dir) {
                     Ok(()) => {}
                     Err(CannotPlaceReason::AlreadyOccupied) => {
-                        println!("Invalid placement: overlaps existing ship.");
+                        console.write_line("Invalid placement: overlaps existing ship.")?;
+                        show_suggestions(console, setup, player, ship, start)?;
                     }
                     Err(CannotPlaceReason::AlreadyPlaced) => unreachable!(),
                     Err(CannotPlaceReason::InsufficientSpace) => {
-                        println!("Invalid placement: not enough space on the board.");
+                        console.write_line("Invalid placement: not enough space on the board.")?;
+                        show_suggestions(console, setup, player, ship, start)?;
+                    }
+                    Err(reason) => {
+                        console.write_line(&format!("Invalid placement: {}.", reason))?
                     }
                 }
             }
@@ -229,104 +260,141 @@ fn choose_placements(
                     setup.unplace_ship(player, *ship);
                 }
             }
-            Command::RandomizeRest => choose_random_placements(rng, setup, player),
+            Command::RandomizeRest => setup.randomize_placements(player, rng),
             Command::Help => {
-                println!(
+                console.write_line(
                     "Available Commands:
     done                        if all ships are placed, start the game.
     place <ship> <x>,<y> <dir>  place the ship at the given coordinate in the given direction.
-        Possible directions are \"up\", \"down\", \"left\", and \"right\". See below for ships.
+        Possible directions are \"up\", \"down\", \"left\", and \"right\", where up is
+        toward row 0 at the top of the board. See below for ships.
     unplace <ship>              clear the placement of the specified ship.
         See below for possible ship. Additionally \"all\" may be specified to clear all placements.
     clear                       clears all ship placements.
     randomize                   randomize the placements of the remaining ships.
+    history                     list recent commands, with indexes to repeat them by.
+    !!                          repeat the previous command.
+    !<n>                        repeat the command with index n from history.
+    !<text>                     repeat the most recent command starting with text.
 
 Available Ships:
     \"carrier\" (\"cv\")
     \"battleship\" (\"bb\")
     \"cruiser\" (\"cl\")
     \"submarine\" (\"ss\")
-    \"destroyer\" (\"dd\")",
-                );
+    \"destroyer\" (\"dd\")
+Ship names in the language selected with --lang are also accepted.",
+                )?;
             }
         }
     }
     Ok(())
 }
 
-/// Read a single coordinate from a string. `name` is either 'x' or 'y' for the error
-/// message if the coordinate is invalid.
-fn read_coord(src: &str, name: &str) -> Option<usize> {
-    match src.parse() {
-        Err(_) => {
-            println!("invalid {}: {}, must be a number in range [0,9]", name, src);
-            None
-        }
-        Ok(c) if c >= 10 => {
-            println!("{} must be in range [0,9], got {}", name, c);
-            None
-        }
-        Ok(c) => Some(c),
-    }
+/// Parse a ship name, accepting the built-in English names and abbreviations as well
+/// as any provided by the selected lexicon.
+fn parse_ship(lexicon: &dyn Lexicon<Ship>, name: &str) -> Option<Ship> {
+    English
+        .parse_ship(name)
+        .or_else(|| lexicon.parse_ship(name))
 }
 
-/// Choose all ship placements for all un-placed ships owned by the given player.
-fn choose_random_placements(rng: &mut impl Rng, setup: &mut GameSetup, player: Player) {
-    for &ship in Ship::ALL {
-        loop {
-            let start = rng.sample(&*COORD_RANGE);
-            let dir = rng.gen();
-            match setup.place_ship(player, ship, start, dir) {
-                Ok(()) | Err(CannotPlaceReason::AlreadyPlaced) => break,
-                _ => {}
-            }
-        }
-    }
+/// List the ship names of the selected lexicon for error messages.
+fn ship_choices(lexicon: &dyn Lexicon<Ship>) -> String {
+    let names: Vec<_> = Ship::ALL
+        .iter()
+        .map(|ship| format!("\"{}\"", lexicon.ship_name(ship)))
+        .collect();
+    names.join(", ")
+}
+
+/// Read an x,y coordinate pair from a string. If it is invalid, returns the message to
+/// show the player.
+fn read_coord(src: &str) -> Result<Coordinate, String> {
+    RectDimensions::default()
+        .parse_coordinate(src)
+        .map_err(|err| format!("Invalid coordinates: {}.", err))
 }
 
 /// Handles the input for a player's turn.
 fn player_turn(
-    input: &mut InputReader<impl BufRead>,
+    console: &mut dyn Console,
     game: &mut Game,
     player: Player,
+    lexicon: &dyn Lexicon<Ship>,
 ) -> io::Result<()> {
-    println!();
-    println!("Your Turn!");
-    show_status(game, player);
-    println!();
-    println!("Choose coordinates to attack.");
+    enum Command {
+        Shoot(Coordinate),
+        Mark(Coordinate, Note),
+        Unmark(Coordinate),
+    }
+    /// Matcher for commands with args. Input isn't lowercased, so that notes keep the case
+    /// they were typed in, and the commands are matched case-insensitively instead.
+    static MARK: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(
+            r"^(?xi)mark\s+
+        (?P<coord>[0-9]+(?:\s*,\s*|\s+)[0-9]+)\s+
+        (?P<note>.+)$",
+        )
+        .unwrap()
+    });
+    static UNMARK: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(r"^(?xi)(?:un-?mark)\s+(?P<coord>[0-9]+(?:\s*,\s*|\s+)[0-9]+)$").unwrap()
+    });
+
+    console.write_line("")?;
+    console.write_line("Your Turn!")?;
+    show_status(console, game, player)?;
+    show_notes(console, game, player)?;
+    console.write_line("")?;
+    console.write_line("Choose coordinates to attack.")?;
     loop {
-        static COORD: Lazy<Regex> =
-            Lazy::new(|| Regex::new(r"^(?P<x>[0-9]+)(?:\s*,\s*|\s+)(?P<y>[0-9]+)$").unwrap());
-        let target = input.read_input_lower("> ", |input| match input {
-            "help" | "?" => {
-                println!("Enter an x,y coordinate pair to attack.");
-                None
+        let cmd = read_input(console, "> ", |input| match input {
+            help if help == "?" || help.eq_ignore_ascii_case("help") => {
+                Err("Enter an x,y coordinate pair to attack.
+    mark <x>,<y> <note>  note a cell on the bot's board, shown as ?? until shot. The note
+        \"suspect\" marks a likely ship and \"avoid\" a cell not to shoot; anything else
+        is kept as text.
+    unmark <x>,<y>       remove the note from a cell."
+                    .to_owned())
             }
             other => {
-                if let Some(captures) = COORD.captures(other) {
-                    let x = read_coord(captures.name("x").unwrap().as_str(), "x")?;
-                    let y = read_coord(captures.name("y").unwrap().as_str(), "y")?;
-                    Some(Coordinate::new(x, y))
+                if let Some(captures) = MARK.captures(other) {
+                    let coord = read_coord(captures.name("coord").unwrap().as_str())?;
+                    let note = match captures.name("note").unwrap().as_str() {
+                        text if text.eq_ignore_ascii_case("suspect") => Note::Suspect,
+                        text if text.eq_ignore_ascii_case("avoid") => Note::Avoid,
+                        text => Note::Text(text.to_owned()),
+                    };
+                    Ok(Command::Mark(coord, note))
+                } else if let Some(captures) = UNMARK.captures(other) {
+                    Ok(Command::Unmark(read_coord(
+                        captures.name("coord").unwrap().as_str(),
+                    )?))
                 } else {
-                    println!("Invalid coordinates: {}", other);
-                    None
+                    read_coord(other).map(Command::Shoot)
                 }
             }
         })?;
+        let target = match cmd {
+            Command::Shoot(target) => target,
+            Command::Mark(coord, note) => {
+                console.write_line(&format!("Marked {},{}: {}.", coord.x, coord.y, note))?;
+                game.annotations_mut(player).set(coord, note);
+                continue;
+            }
+            Command::Unmark(coord) => {
+                if game.annotations_mut(player).remove(&coord).is_none() {
+                    console.write_line("That cell has no note.")?;
+                }
+                continue;
+            }
+        };
         match game.shoot(player.opponent(), target) {
             Ok(outcome) => {
                 thread::sleep(Duration::from_secs(1));
-                println!();
-                match outcome {
-                    ShotOutcome::Miss => println!("Miss."),
-                    ShotOutcome::Hit(ship) => println!("Hit {}!", ShipFullName(ship)),
-                    ShotOutcome::Sunk(ship) => println!("Sunk {}!", ShipFullName(ship)),
-                    ShotOutcome::Victory(ship) => {
-                        println!("Sunk {}!", ShipFullName(ship));
-                        println!("Last enemy ship sunk! VICTORY!");
-                    }
-                }
+                console.write_line("")?;
+                console.write_line(&outcome.describe_in(lexicon))?;
                 thread::sleep(Duration::from_secs(2));
                 break;
             }
@@ -334,79 +402,206 @@ fn player_turn(
             Err(CannotShootReason::AlreadyOver) => unreachable!(),
             // Bounds checked during input.
             Err(CannotShootReason::OutOfBounds) => unreachable!(),
-            // Never called on bot turn.
-            Err(CannotShootReason::OutOfTurn) => unreachable!(),
+            // Always shoots the opponent.
+            Err(CannotShootReason::SelfTarget) => unreachable!(),
             Err(CannotShootReason::AlreadyShot) => {
-                println!("That position is already shot, choose a different target.")
+                console.write_line("That position is already shot, choose a different target.")?
             }
+            Err(reason) => console.write_line(&format!("Cannot shoot there: {}.", reason))?,
         }
     }
     Ok(())
 }
 
-fn bot_turn(rng: &mut impl Rng, game: &mut Game, bot: Player) {
-    println!();
-    println!("Bot's turn.");
-    show_status(game, bot.opponent());
+fn bot_turn(
+    console: &mut dyn Console,
+    bot_player: &Bot,
+    rng: &mut impl Rng,
+    game: &mut Game,
+    bot: Player,
+    lexicon: &dyn Lexicon<Ship>,
+) -> io::Result<()> {
+    console.write_line("")?;
+    console.write_line("Bot's turn.")?;
+    show_status(console, game, bot.opponent())?;
     thread::sleep(Duration::from_secs(1));
-    println!("Bot choosing target to attack.");
+    console.write_line("Bot choosing target to attack.")?;
     thread::sleep(Duration::from_secs(1));
-    loop {
-        let target = rng.sample(&*COORD_RANGE);
-        match game.shoot(bot.opponent(), target) {
-            Ok(outcome) => {
-                println!("Bot shoots {},{}", target.x, target.y);
-                thread::sleep(Duration::from_secs(1));
-                match outcome {
-                    ShotOutcome::Miss => println!("Bot missed."),
-                    ShotOutcome::Hit(ship) => println!("Bot hit your {}!", ShipFullName(ship)),
-                    ShotOutcome::Sunk(ship) => println!("Bot sunk your {}!", ShipFullName(ship)),
-                    ShotOutcome::Victory(ship) => {
-                        println!("Bot sunk your {}!", ShipFullName(ship));
-                        println!("All your ships have been sunk! Bot Wins!");
-                    }
-                }
-                thread::sleep(Duration::from_secs(2));
-                break;
-            }
-            Err(CannotShootReason::AlreadyShot) => continue,
-            Err(_) => unreachable!(),
+    let (target, outcome) = match bot_player.take_turn(game, rng) {
+        Ok(shot) => shot,
+        // Method only called on the bot's turn while the game is still going.
+        Err(_) => unreachable!(),
+    };
+    console.write_line(&format!("Bot shoots {},{}", target.x, target.y))?;
+    thread::sleep(Duration::from_secs(1));
+    match outcome {
+        ShotOutcome::Miss => console.write_line("Bot missed.")?,
+        ShotOutcome::Hit(ship) => {
+            console.write_line(&format!("Bot hit your {}!", lexicon.ship_name(&ship)))?
         }
+        ShotOutcome::Sunk(ship) => {
+            console.write_line(&format!("Bot sunk your {}!", lexicon.ship_name(&ship)))?
+        }
+        ShotOutcome::Victory(ship) => {
+            console.write_line(&format!("Bot sunk your {}!", lexicon.ship_name(&ship)))?;
+            console.write_line("All your ships have been sunk! Bot Wins!")?;
+        }
+        ShotOutcome::Wasted => console.write_line("Bot wasted its shot.")?,
+        other => console.write_line(&format!("Bot: {:?}.", other))?,
     }
+    thread::sleep(Duration::from_secs(2));
+    Ok(())
 }
 
-/// Print out the setup board for the given player.
-fn show_setup_board(setup: &GameSetup, player: Player) {
+/// Print out the setup board for the given player. The start cell of each ship is shown
+/// with an uppercase abbreviation.
+fn show_setup_board(
+    console: &mut dyn Console,
+    setup: &GameSetup,
+    player: Player,
+) -> io::Result<()> {
     enum SetupCell {
         Empty,
         Ship(ShipAbbreviation),
+        Start(ShipAbbreviation),
     }
     impl fmt::Display for SetupCell {
         fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
             match self {
                 SetupCell::Empty => f.pad("~~"),
                 SetupCell::Ship(abbrev) => fmt::Display::fmt(abbrev, f),
+                SetupCell::Start(abbrev) => f.pad(&abbrev.abbrev().to_uppercase()),
             }
         }
     }
-    show_board(setup.iter_board(player).map(|row| {
-        row.map(|cell| match cell {
-            Some(ship) => SetupCell::Ship(ShipAbbreviation(ship)),
-            None => SetupCell::Empty,
+    show_board(
+        console,
+        setup.iter_board(player).enumerate().map(|(y, row)| {
+            row.enumerate().map(move |(x, cell)| match cell {
+                Some(ship)
+                    if setup.get_placement(player, ship).unwrap().start()
+                        == &Coordinate::new(x, y) =>
+                {
+                    SetupCell::Start(ShipAbbreviation(ship))
+                }
+                Some(ship) => SetupCell::Ship(ShipAbbreviation(ship)),
+                None => SetupCell::Empty,
+            })
+        }),
+    )
+}
+
+/// Print a table of the player's placed ships, giving the start, direction, and end of
+/// each.
+fn show_placement_summary(
+    console: &mut dyn Console,
+    setup: &GameSetup,
+    player: Player,
+    lexicon: &dyn Lexicon<Ship>,
+) -> io::Result<()> {
+    let placed: Vec<_> = Ship::ALL
+        .iter()
+        .filter_map(|&ship| {
+            setup
+                .get_placement(player, ship)
+                .map(|placement| (ship, placement))
         })
-    }))
+        .collect();
+    if placed.is_empty() {
+        return Ok(());
+    }
+    let width = Ship::ALL
+        .iter()
+        .map(|ship| lexicon.ship_name(ship).chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Ship".len());
+    console.write_line(&format!(
+        "  {:<width$}  {:<5}  {:<9}  {}",
+        "Ship",
+        "Start",
+        "Direction",
+        "End",
+        width = width
+    ))?;
+    for (ship, placement) in placed {
+        let direction = direction_name(placement.orientation());
+        let start = placement.start();
+        let end = placement.end();
+        console.write_line(&format!(
+            "  {:<width$}  {:<5}  {:<9}  {},{}",
+            lexicon.ship_name(&ship),
+            format!("{},{}", start.x, start.y),
+            direction,
+            end.x,
+            end.y,
+            width = width
+        ))?;
+    }
+    Ok(())
 }
 
-fn show_status(game: &Game, player: Player) {
-    println!("Bot's Board:");
-    show_obfuscated_board(game, player.opponent());
-    println!();
-    println!("Your Board:");
-    show_revealed_board(game, player);
+/// Get the name of a direction, as accepted by the place command.
+fn direction_name(dir: Orientation) -> &'static str {
+    match dir {
+        Orientation::Up => "up",
+        Orientation::Down => "down",
+        Orientation::Left => "left",
+        Orientation::Right => "right",
+    }
+}
+
+/// After a failed placement, print a few valid placements for the ship close to where
+/// the player tried to put it.
+fn show_suggestions(
+    console: &mut dyn Console,
+    setup: &GameSetup,
+    player: Player,
+    ship: Ship,
+    near: Coordinate,
+) -> io::Result<()> {
+    let suggestions: Vec<_> = setup
+        .suggest_placements(player, ship, near, 3)
+        .into_iter()
+        .map(|(start, dir)| format!("{},{} {}", start.x, start.y, direction_name(dir)))
+        .collect();
+    match suggestions.split_last() {
+        None => console.write_line("There is no room left for that ship; unplace another first."),
+        Some((last, [])) => console.write_line(&format!("Try {}.", last)),
+        Some((last, [first])) => console.write_line(&format!("Try {} or {}.", first, last)),
+        Some((last, rest)) => console.write_line(&format!("Try {}, or {}.", rest.join(", "), last)),
+    }
+}
+
+fn show_status(console: &mut dyn Console, game: &Game, player: Player) -> io::Result<()> {
+    console.write_line("Bot's Board:")?;
+    show_obfuscated_board(console, game, player.opponent())?;
+    console.write_line("")?;
+    console.write_line("Your Board:")?;
+    show_revealed_board(console, game, player)
+}
+
+/// Print the player's notes on the bot's board, in row-major order.
+fn show_notes(console: &mut dyn Console, game: &Game, player: Player) -> io::Result<()> {
+    let mut notes: Vec<_> = game.annotations(player).iter().collect();
+    if notes.is_empty() {
+        return Ok(());
+    }
+    notes.sort_by_key(|(coord, _)| (coord.y, coord.x));
+    console.write_line("")?;
+    console.write_line("Your notes:")?;
+    for (coord, note) in notes {
+        console.write_line(&format!(
+            "  {:<5}  {}",
+            format!("{},{}", coord.x, coord.y),
+            note
+        ))?;
+    }
+    Ok(())
 }
 
 /// Print out the fully-revealed board for the given player.
-fn show_revealed_board(game: &Game, player: Player) {
+fn show_revealed_board(console: &mut dyn Console, game: &Game, player: Player) -> io::Result<()> {
     enum RevealedCell {
         Empty,
         Shot,
@@ -433,21 +628,25 @@ fn show_revealed_board(game: &Game, player: Player) {
             }
         }
     }
-    show_board(game.iter_board(player).map(|row| {
-        row.map(|cell| match cell.ship() {
-            None if cell.hit() => RevealedCell::Shot,
-            None => RevealedCell::Empty,
-            Some(ship) if ship.sunk() => RevealedCell::Sunk(ShipAbbreviation(*ship.id())),
-            Some(ship) if cell.hit() => RevealedCell::Hit(ShipAbbreviation(*ship.id())),
-            Some(ship) => RevealedCell::NotShot(ShipAbbreviation(*ship.id())),
-        })
-    }))
+    show_board(
+        console,
+        game.iter_board(player).map(|row| {
+            row.map(|cell| match cell.ship() {
+                None if cell.hit() => RevealedCell::Shot,
+                None => RevealedCell::Empty,
+                Some(ship) if ship.sunk() => RevealedCell::Sunk(ShipAbbreviation(*ship.id())),
+                Some(ship) if cell.hit() => RevealedCell::Hit(ShipAbbreviation(*ship.id())),
+                Some(ship) => RevealedCell::NotShot(ShipAbbreviation(*ship.id())),
+            })
+        }),
+    )
 }
 
 /// Print out the obfuscated board for the given player.
-fn show_obfuscated_board(game: &Game, player: Player) {
+fn show_obfuscated_board(console: &mut dyn Console, game: &Game, player: Player) -> io::Result<()> {
     enum HiddenCell {
         NotShot,
+        Noted,
         Miss,
         Hit(ShipAbbreviation),
         Sunk(ShipAbbreviation),
@@ -456,6 +655,7 @@ fn show_obfuscated_board(game: &Game, player: Player) {
         fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
             match self {
                 HiddenCell::NotShot => f.pad("~~"),
+                HiddenCell::Noted => f.pad("??"),
                 HiddenCell::Miss => f.pad("x"),
                 HiddenCell::Hit(ship) => {
                     let mut buf = *b"x00";
@@ -470,53 +670,43 @@ fn show_obfuscated_board(game: &Game, player: Player) {
             }
         }
     }
-    show_board(game.iter_board(player).map(|row| {
-        row.map(|cell| match cell.ship() {
-            _ if !cell.hit() => HiddenCell::NotShot,
-            None => HiddenCell::Miss,
-            Some(ship) if ship.sunk() => HiddenCell::Sunk(ShipAbbreviation(*ship.id())),
-            Some(ship) => HiddenCell::Hit(ShipAbbreviation(*ship.id())),
-        })
-    }))
+    // Notes made by the player viewing this board.
+    let notes = game.annotations(player.opponent());
+    show_board(
+        console,
+        game.iter_board(player).map(|row| {
+            row.map(|cell| match cell.ship() {
+                _ if !cell.hit() && notes.get(cell.coord()).is_some() => HiddenCell::Noted,
+                _ if !cell.hit() => HiddenCell::NotShot,
+                None => HiddenCell::Miss,
+                Some(ship) if ship.sunk() => HiddenCell::Sunk(ShipAbbreviation(*ship.id())),
+                Some(ship) => HiddenCell::Hit(ShipAbbreviation(*ship.id())),
+            })
+        }),
+    )
 }
 
 /// Show the board by printing the grid. Takes an iterator over the rows of iterators over
 /// the items
-fn show_board(rows: impl Iterator<Item = impl Iterator<Item = impl fmt::Display>>) {
-    print!("   ");
+fn show_board(
+    console: &mut dyn Console,
+    rows: impl Iterator<Item = impl Iterator<Item = impl fmt::Display>>,
+) -> io::Result<()> {
+    let mut header = "   ".to_owned();
     for i in 0..10 {
-        print!("{:^4}", i);
+        header.push_str(&format!("{:^4}", i));
     }
-    println!();
+    console.write_line(&header)?;
     for (i, row) in rows.enumerate() {
-        print!("{:>2} ", i);
+        let mut line = format!("{:>2} ", i);
         for cell in row {
-            print!("{:^4}", cell);
-        }
-        println!();
-    }
-}
-
-/// Display helper that prints the ship's full name.
-struct ShipFullName(Ship);
-
-impl ShipFullName {
-    fn name(&self) -> &'static str {
-        match self.0 {
-            Ship::Carrier => "carrier",
-            Ship::Battleship => "battleship",
-            Ship::Cruiser => "cruiser",
-            Ship::Submarine => "submarine",
-            Ship::Destroyer => "destroyer",
+            line.push_str(&format!("{:^4}", cell));
         }
+        console.write_line(&line)?;
     }
+    Ok(())
 }
 
-impl fmt::Display for ShipFullName {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.pad(self.name())
-    }
-}
 /// Display helper that prints the ship's type abbreviation
 struct ShipAbbreviation(Ship);
 
@@ -538,60 +728,259 @@ impl fmt::Display for ShipAbbreviation {
     }
 }
 
-/// Helper to read input from the player.
-struct InputReader<B> {
-    read: B,
-    buf: String,
-}
-
-impl<B> InputReader<B> {
-    fn new(read: B) -> Self {
-        Self {
-            read,
-            buf: String::new(),
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::console::Script;
+
+    /// Setup with every ship of both players placed facing right along the even rows.
+    fn placed_setup() -> GameSetup {
+        let mut setup = GameSetup::new();
+        for player in [Player::P1, Player::P2].iter().copied() {
+            for (i, ship) in Ship::ALL.iter().enumerate() {
+                setup
+                    .place_ship(player, *ship, Coordinate::new(0, 2 * i), Orientation::Right)
+                    .unwrap();
+            }
         }
+        setup
     }
-}
 
-impl<B: BufRead> InputReader<B> {
-    /// Repeatedly tries to read input until the input checker returns `Some`. Converts
-    /// to ascii lower before running the checker.
-    fn read_input_lower<F, T>(&mut self, prompt: &str, mut checker: F) -> io::Result<T>
-    where
-        F: FnMut(&str) -> Option<T>,
-    {
-        loop {
-            self.read_input_inner(prompt)?;
-            self.buf.make_ascii_lowercase();
-            if let Some(val) = checker(self.buf.trim()) {
-                return Ok(val);
-            }
-        }
+    #[test]
+    fn choose_player_retries() {
+        let matches = App::new("test").get_matches_from(vec!["test"]);
+        let mut console = Script::new(vec!["maybe", "N"]);
+        assert_eq!(choose_player(&matches, &mut console).unwrap(), Player::P2);
+        assert_eq!(console.output(), ["Invalid selection."]);
     }
 
-    /// Repeatedly tries to read input until the input checker returns `Some`.
-    #[allow(unused)]
-    fn read_input<F, T>(&mut self, prompt: &str, mut checker: F) -> io::Result<T>
-    where
-        F: FnMut(&str) -> Option<T>,
-    {
-        loop {
-            self.read_input_inner(prompt)?;
-            if let Some(val) = checker(self.buf.trim()) {
-                return Ok(val);
-            }
+    #[test]
+    fn placement_flow() {
+        let mut console = Script::new(vec![
+            "done",
+            "place frigate 0,0 right",
+            "place carrier 0,0 sideways",
+            "bogus",
+            "place carrier 0,0 right",
+            "place battleship 0,0 down",
+            "place battleship 9,0 right",
+            "place bb 0,1 right",
+            "put cl 0 2 r",
+            "place ss at 0,3 east",
+            "place dd 0,4 right",
+            "unplace dd",
+            "PLACE DD 5,9 LEFT",
+            "done",
+        ]);
+        let mut setup = GameSetup::new();
+        choose_placements(
+            &mut rand::thread_rng(),
+            &mut setup,
+            Player::P1,
+            &English,
+            &mut console,
+        )
+        .unwrap();
+        assert_eq!(console.remaining(), 0);
+        assert!(setup.is_player_ready(Player::P1));
+        assert!(!setup.is_player_ready(Player::P2));
+
+        assert!(console.wrote("You must place all your ships first!"));
+        assert!(console.wrote("invalid ship: frigate"));
+        assert!(console.wrote("invalid direction sideways"));
+        assert!(console.wrote("Invalid ship-placement command \"bogus\""));
+        assert!(console.wrote("Invalid placement: overlaps existing ship."));
+        assert!(console.wrote("Invalid placement: not enough space on the board."));
+
+        let carrier = setup.get_placement(Player::P1, Ship::Carrier).unwrap();
+        assert_eq!(carrier.start(), &Coordinate::new(0, 0));
+        assert_eq!(carrier.orientation(), Orientation::Right);
+        let battleship = setup.get_placement(Player::P1, Ship::Battleship).unwrap();
+        assert_eq!(battleship.start(), &Coordinate::new(0, 1));
+        let destroyer = setup.get_placement(Player::P1, Ship::Destroyer).unwrap();
+        assert_eq!(destroyer.start(), &Coordinate::new(5, 9));
+        assert_eq!(destroyer.orientation(), Orientation::Left);
+    }
+
+    #[test]
+    fn turn_flow() {
+        let mut game = placed_setup().start().ok().unwrap();
+        let mut console = Script::new(vec![
+            "?",
+            "12,3",
+            "mark 3,3 suspect",
+            "unmark 4,4",
+            "0,0",
+            "1,0",
+        ]);
+        player_turn(&mut console, &mut game, Player::P1, &English).unwrap();
+        assert_eq!(console.remaining(), 1);
+        assert_eq!(game.current(), Player::P2);
+
+        assert!(console.wrote("Enter an x,y coordinate pair to attack."));
+        assert!(console.wrote("Invalid coordinates"));
+        assert!(console.wrote("Marked 3,3"));
+        assert!(console.wrote("That cell has no note."));
+        assert!(console.wrote(&ShotOutcome::Hit(Ship::Carrier).describe_in(&English)));
+        assert_eq!(
+            game.annotations(Player::P1).get(&Coordinate::new(3, 3)),
+            Some(&Note::Suspect)
+        );
+    }
+
+    #[test]
+    fn turn_ends_with_input() {
+        let mut game = placed_setup().start().ok().unwrap();
+        let mut console = Script::new(vec!["mark 1,1 avoid"]);
+        let err = player_turn(&mut console, &mut game, Player::P1, &English).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        assert_eq!(game.current(), Player::P1);
+    }
+
+    #[test]
+    fn mark_keeps_note_case() {
+        let mut game = placed_setup().start().ok().unwrap();
+        let mut console = Script::new(vec![
+            "MARK 5,5 Probably Carrier",
+            "Mark 4,4 SUSPECT",
+            "mark 6,6 temp",
+            "UnMark 6,6",
+            "HELP",
+        ]);
+        let err = player_turn(&mut console, &mut game, Player::P1, &English).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        assert!(console.wrote("Enter an x,y coordinate pair to attack."));
+
+        let notes = game.annotations(Player::P1);
+        assert_eq!(
+            notes.get(&Coordinate::new(5, 5)),
+            Some(&Note::Text("Probably Carrier".to_owned()))
+        );
+        assert_eq!(notes.get(&Coordinate::new(4, 4)), Some(&Note::Suspect));
+        assert_eq!(notes.get(&Coordinate::new(6, 6)), None);
+        assert!(game.annotations(Player::P2).is_empty());
+    }
+
+    #[test]
+    fn setup_screen_snapshot() {
+        let mut setup = GameSetup::new();
+        let placements = [
+            (Ship::Carrier, Coordinate::new(1, 1), Orientation::Right),
+            (Ship::Cruiser, Coordinate::new(8, 2), Orientation::Down),
+            (Ship::Destroyer, Coordinate::new(4, 9), Orientation::Left),
+        ];
+        for &(ship, start, dir) in placements.iter() {
+            setup.place_ship(Player::P1, ship, start, dir).unwrap();
         }
+        let mut console = Script::new(vec![]);
+        show_setup_board(&mut console, &setup, Player::P1).unwrap();
+        show_placement_summary(&mut console, &setup, Player::P1, &English).unwrap();
+        assert_eq!(
+            console.output(),
+            [
+                "    0   1   2   3   4   5   6   7   8   9  ",
+                " 0  ~~  ~~  ~~  ~~  ~~  ~~  ~~  ~~  ~~  ~~ ",
+                " 1  ~~  CV  cv  cv  cv  cv  ~~  ~~  ~~  ~~ ",
+                " 2  ~~  ~~  ~~  ~~  ~~  ~~  ~~  ~~  CL  ~~ ",
+                " 3  ~~  ~~  ~~  ~~  ~~  ~~  ~~  ~~  cl  ~~ ",
+                " 4  ~~  ~~  ~~  ~~  ~~  ~~  ~~  ~~  cl  ~~ ",
+                " 5  ~~  ~~  ~~  ~~  ~~  ~~  ~~  ~~  ~~  ~~ ",
+                " 6  ~~  ~~  ~~  ~~  ~~  ~~  ~~  ~~  ~~  ~~ ",
+                " 7  ~~  ~~  ~~  ~~  ~~  ~~  ~~  ~~  ~~  ~~ ",
+                " 8  ~~  ~~  ~~  ~~  ~~  ~~  ~~  ~~  ~~  ~~ ",
+                " 9  ~~  ~~  ~~  dd  DD  ~~  ~~  ~~  ~~  ~~ ",
+                "  Ship        Start  Direction  End",
+                "  carrier     1,1    right      5,1",
+                "  cruiser     8,2    down       8,4",
+                "  destroyer   4,9    left       3,9",
+            ]
+        );
     }
 
-    /// Helper to print the prompt, clear the string buffer and read a line.
-    fn read_input_inner(&mut self, prompt: &str) -> io::Result<()> {
-        print!("{} ", prompt);
-        io::stdout().flush()?;
-        self.buf.clear();
-        if self.read.read_line(&mut self.buf)? == 0 {
-            println!();
-            std::process::exit(0);
+    #[test]
+    fn history_replaces_unplaced_ship() {
+        let mut console = Script::new(vec![
+            "place carrier 0,0 right",
+            "unplace carrier",
+            "!place",
+            "!!",
+            "history",
+        ]);
+        let mut setup = GameSetup::new();
+        let err = choose_placements(
+            &mut rand::thread_rng(),
+            &mut setup,
+            Player::P1,
+            &English,
+            &mut console,
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+        let carrier = setup.get_placement(Player::P1, Ship::Carrier).unwrap();
+        assert_eq!(carrier.start(), &Coordinate::new(0, 0));
+        assert_eq!(carrier.orientation(), Orientation::Right);
+        // The carrier was pending at the start and again after it was unplaced.
+        let pending = console
+            .output()
+            .iter()
+            .filter(|line| {
+                line.starts_with("Remaining ships to place:") && line.contains("carrier")
+            })
+            .count();
+        assert_eq!(pending, 2);
+        // Both references echo their expansion, and the expansion is what's recorded.
+        let echoes = console
+            .output()
+            .iter()
+            .filter(|line| *line == "place carrier 0,0 right")
+            .count();
+        assert_eq!(echoes, 2);
+        for line in &[
+            "   1  place carrier 0,0 right",
+            "   2  unplace carrier",
+            "   3  place carrier 0,0 right",
+            "   4  place carrier 0,0 right",
+        ] {
+            assert!(console.wrote(line), "missing {:?}", line);
         }
-        Ok(())
+    }
+
+    #[test]
+    fn place_up_extends_toward_top_row() {
+        let mut console = Script::new(vec!["place carrier 5,5 up"]);
+        let mut setup = GameSetup::new();
+        let err = choose_placements(
+            &mut rand::thread_rng(),
+            &mut setup,
+            Player::P1,
+            &English,
+            &mut console,
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+        let carrier = setup.get_placement(Player::P1, Ship::Carrier).unwrap();
+        let expected: Vec<_> = (1..=5).rev().map(|y| Coordinate::new(5, y)).collect();
+        assert_eq!(&carrier[..], &expected[..]);
+        assert_eq!(carrier.orientation(), Orientation::Up);
+
+        let mut console = Script::new(vec![]);
+        show_setup_board(&mut console, &setup, Player::P1).unwrap();
+        assert_eq!(
+            &console.output()[..7],
+            [
+                "    0   1   2   3   4   5   6   7   8   9  ",
+                " 0  ~~  ~~  ~~  ~~  ~~  ~~  ~~  ~~  ~~  ~~ ",
+                " 1  ~~  ~~  ~~  ~~  ~~  cv  ~~  ~~  ~~  ~~ ",
+                " 2  ~~  ~~  ~~  ~~  ~~  cv  ~~  ~~  ~~  ~~ ",
+                " 3  ~~  ~~  ~~  ~~  ~~  cv  ~~  ~~  ~~  ~~ ",
+                " 4  ~~  ~~  ~~  ~~  ~~  cv  ~~  ~~  ~~  ~~ ",
+                " 5  ~~  ~~  ~~  ~~  ~~  CV  ~~  ~~  ~~  ~~ ",
+            ]
+        );
+        assert!(console.output()[7..]
+            .iter()
+            .all(|row| !row.contains("cv") && !row.contains("CV")));
     }
 }
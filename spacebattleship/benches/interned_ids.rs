@@ -0,0 +1,144 @@
+// Copyright 2020 Zachary Stewart
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compares a four-player game keyed by `String` IDs against the same game keyed by
+//! interned strings, counting heap allocations and timing full games and board lookups.
+//! Run with `cargo bench --bench interned_ids`.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    hint::black_box,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Instant,
+};
+
+use spacebattleship::{
+    board::rectangular::{Coordinate, RectDimensions},
+    game::uniform::{Game, GameSetup, PlayerId},
+    ids::{InternedStr, Interner},
+    ships::{Line, ShipId},
+};
+
+/// Allocator which counts every allocation made through it.
+struct Counting;
+
+/// Number of allocations made so far.
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// Names of the players.
+const PLAYERS: &[&str] = &["alice", "bob", "carol", "dave"];
+
+/// Names and lengths of the ships in every fleet.
+const SHIPS: &[(&str, usize)] = &[
+    ("carrier", 5),
+    ("battleship", 4),
+    ("cruiser", 3),
+    ("submarine", 3),
+    ("destroyer", 2),
+];
+
+/// Number of full games played for each ID type.
+const GAMES: u32 = 200;
+
+/// Number of board lookups made for each ID type.
+const LOOKUPS: u32 = 1_000_000;
+
+/// Set up a four-player game with the given IDs and play it to the end, with each player
+/// shooting the next undefeated opponent in row-major order.
+fn play<P: PlayerId, I: ShipId>(players: &[P], ships: &[(I, usize)]) -> Game<P, I, RectDimensions> {
+    let mut setup = GameSetup::new();
+    for pid in players {
+        let board = setup
+            .add_player(pid.clone(), RectDimensions::new(10, 10))
+            .unwrap();
+        for (y, (ship, len)) in ships.iter().enumerate() {
+            board.add_ship(ship.clone(), Line::new(*len)).unwrap();
+            let cells = (0..*len).map(|x| Coordinate::new(x, 2 * y)).collect();
+            board.get_ship_mut(ship).unwrap().place(cells).unwrap();
+        }
+    }
+    let mut game = setup.start().ok().unwrap();
+    let mut next = vec![0; players.len()];
+    while game.winner().is_none() {
+        let shooter = players.iter().position(|p| p == game.current()).unwrap();
+        let target = (1..players.len())
+            .map(|offset| (shooter + offset) % players.len())
+            .find(|&t| !game.get_board(&players[t]).unwrap().defeated())
+            .unwrap();
+        let cell = next[target];
+        next[target] += 1;
+        game.shoot(
+            players[target].clone(),
+            Coordinate::new(cell % 10, cell / 10),
+        )
+        .unwrap();
+    }
+    game
+}
+
+/// Play [`GAMES`] games and time [`LOOKUPS`] board lookups with the given IDs, printing
+/// the results.
+fn bench<P: PlayerId, I: ShipId>(name: &str, players: &[P], ships: &[(I, usize)]) {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    for _ in 0..GAMES {
+        black_box(play(players, ships));
+    }
+    let elapsed = start.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+
+    let game = play(players, ships);
+    let start = Instant::now();
+    for i in 0..LOOKUPS {
+        let pid = black_box(&players[i as usize % players.len()]);
+        black_box(game.get_board(pid));
+    }
+    let lookups = start.elapsed();
+
+    println!(
+        "{:>8}: {:>7} allocations per game, {:>9?} per game, {:>6?} per board lookup",
+        name,
+        allocations / GAMES as usize,
+        elapsed / GAMES,
+        lookups / LOOKUPS,
+    );
+}
+
+fn main() {
+    let players: Vec<String> = PLAYERS.iter().map(|&p| p.to_owned()).collect();
+    let ships: Vec<(String, usize)> = SHIPS.iter().map(|&(s, l)| (s.to_owned(), l)).collect();
+    bench("String", &players, &ships);
+
+    let mut interner: Interner<str> = Interner::new();
+    let players: Vec<InternedStr> = PLAYERS.iter().map(|&p| interner.intern(p)).collect();
+    let ships: Vec<(InternedStr, usize)> = SHIPS
+        .iter()
+        .map(|&(s, l)| (interner.intern(s), l))
+        .collect();
+    bench("Interned", &players, &ships);
+}
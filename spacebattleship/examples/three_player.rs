@@ -0,0 +1,117 @@
+// Copyright 2020 Zachary Stewart
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Three player game using `String` IDs and a different fleet for each player, driven
+//! entirely through the public [`uniform`] API.
+
+use spacebattleship::prelude::{
+    uniform::{self, GameSetup, ShotOutcome},
+    Coordinate2D as Coordinate, Line, RectDimensions,
+};
+
+type Game = uniform::Game<String, String, RectDimensions>;
+
+/// A ship to place, as `(ship name, length, start, end)`.
+type ShipSpec = (&'static str, usize, (usize, usize), (usize, usize));
+
+/// Fleets for each player.
+const FLEETS: &[(&str, &[ShipSpec])] = &[
+    (
+        "alice",
+        &[
+            ("flagship", 4, (0, 0), (3, 0)),
+            ("escort", 2, (5, 5), (5, 6)),
+        ],
+    ),
+    (
+        "bob",
+        &[
+            ("cruiser", 3, (1, 1), (1, 3)),
+            ("patrol", 2, (4, 2), (5, 2)),
+            ("dinghy", 1, (7, 7), (7, 7)),
+        ],
+    ),
+    ("carol", &[("tanker", 5, (2, 6), (6, 6))]),
+];
+
+fn main() {
+    let mut setup = GameSetup::new();
+    for &(player, fleet) in FLEETS {
+        let board = setup
+            .add_player(player.to_owned(), RectDimensions::new(8, 8))
+            .unwrap();
+        for &(ship, len, _, _) in fleet {
+            board.add_ship(ship.to_owned(), Line::new(len)).unwrap();
+        }
+    }
+    assert!(!setup.ready());
+
+    for &(player, fleet) in FLEETS {
+        let board = setup.get_board_mut(player).unwrap();
+        for &(ship, _, start, end) in fleet {
+            let mut entry = board.get_ship_mut(ship).unwrap();
+            let placement = entry
+                .get_placements(start.into())
+                .find(|proj| proj.last() == Some(&Coordinate::from(end)))
+                .unwrap();
+            entry.place(placement).unwrap();
+        }
+        assert!(board.ready());
+    }
+
+    let mut game = match setup.start() {
+        Ok(game) => game,
+        Err(_) => panic!("all players should be ready"),
+    };
+
+    let mut defeated = Vec::new();
+    let mut turns = 0;
+    while game.winner().is_none() {
+        let shooter = game.current().clone();
+        let (target, coord) = next_target(&game, &shooter);
+        match game.shoot(target.clone(), coord).unwrap() {
+            ShotOutcome::Defeated(_) | ShotOutcome::Victory(_) => defeated.push(target),
+            _ => {}
+        }
+        turns += 1;
+    }
+
+    let winner = game.winner().unwrap().clone();
+    println!("{} wins after {} shots", winner, turns);
+    assert_eq!(defeated.len(), 2);
+    assert!(!defeated.contains(&winner));
+    for (pid, board) in game.iter_boards() {
+        assert_eq!(board.defeated(), pid != &winner);
+    }
+}
+
+/// Pick the first un-shot cell on the first opponent who is still in the game.
+fn next_target(game: &Game, shooter: &str) -> (String, Coordinate) {
+    for (pid, board) in game.iter_boards() {
+        if pid == shooter || board.defeated() {
+            continue;
+        }
+        for coord in board.dimensions().iter_coordinates().flatten() {
+            if !board.get_coord(coord).unwrap().hit() {
+                return (pid.clone(), coord);
+            }
+        }
+    }
+    unreachable!("the game would be over if no opponent had cells left")
+}
+
+#[test]
+fn runs_to_completion() {
+    main();
+}
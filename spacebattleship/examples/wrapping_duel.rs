@@ -0,0 +1,81 @@
+// Copyright 2020 Zachary Stewart
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Two player duel on boards that wrap around horizontally, with ships placed across the
+//! seam.
+
+use spacebattleship::prelude::{
+    uniform::{GameSetup, ShotOutcome},
+    Coordinate2D as Coordinate, Line, RectDimensions, Wrapping,
+};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+enum Player {
+    Red,
+    Blue,
+}
+
+fn main() {
+    let dim = RectDimensions::new_wrapping(6, 6, Wrapping::Horizontal);
+    let mut setup = GameSetup::new();
+    for &player in &[Player::Red, Player::Blue] {
+        let board = setup.add_player(player, dim).unwrap();
+        let mut ship = board.add_ship(0u8, Line::new(3)).unwrap();
+        // Starting in the last column and heading right wraps back around to column 0.
+        let placement = ship
+            .get_placements(Coordinate::new(5, 2))
+            .find(|proj| proj[1] == Coordinate::new(0, 2))
+            .unwrap();
+        assert_eq!(
+            placement,
+            vec![
+                Coordinate::new(5, 2),
+                Coordinate::new(0, 2),
+                Coordinate::new(1, 2),
+            ]
+        );
+        ship.place(placement).unwrap();
+    }
+
+    let mut game = match setup.start() {
+        Ok(game) => game,
+        Err(_) => panic!("both players should be ready"),
+    };
+
+    // Red goes first, and each player aims at the same cells, so Red lands the final
+    // shot first.
+    let targets = [(3, 3), (5, 2), (0, 2), (1, 2)];
+    let mut last = None;
+    for &(x, y) in &targets {
+        for &(shooter, target) in &[(Player::Red, Player::Blue), (Player::Blue, Player::Red)] {
+            if game.winner().is_some() {
+                break;
+            }
+            assert_eq!(game.current(), &shooter);
+            last = Some(game.shoot(target, Coordinate::new(x, y)).unwrap());
+        }
+    }
+
+    match last {
+        Some(ShotOutcome::Victory(0)) => {}
+        _ => panic!("final shot should have won the game"),
+    }
+    assert_eq!(game.winner(), Some(&Player::Red));
+    println!("{:?} wins", game.winner().unwrap());
+}
+
+#[test]
+fn runs_to_completion() {
+    main();
+}
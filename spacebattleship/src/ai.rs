@@ -0,0 +1,96 @@
+// Copyright 2020 Zachary Stewart
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Utilities for computer players which work on any [`Dimensions`].
+//!
+//! These only look at what a player is allowed to know about an opponent's board:
+//! which cells have been shot. They never inspect where the ships are.
+
+use rand::Rng;
+
+use crate::{
+    board::{Board, Dimensions},
+    ships::ShipId,
+};
+
+/// Pick a cell of `board` which has not been shot yet, uniformly at random. Returns
+/// `None` once every cell has been shot.
+///
+/// This samples an index into the board's list of un-shot cells rather than retrying
+/// random coordinates, so it takes constant time no matter how full the board is.
+pub fn sample_unshot_coordinate<I: ShipId, D: Dimensions, R: Rng + ?Sized>(
+    board: &Board<I, D>,
+    rng: &mut R,
+) -> Option<D::Coordinate> {
+    board.sample_unshot(rng)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::{
+        board::{
+            rectangular::{Coordinate, RectDimensions},
+            BoardSetup,
+        },
+        ships::Line,
+    };
+
+    /// Build a started 3x3 board with a single ship of length 2 in the top left corner.
+    fn tiny_board() -> Board<&'static str, RectDimensions> {
+        let mut setup = BoardSetup::new(RectDimensions::new(3, 3));
+        setup
+            .add_ship("destroyer", Line::new(2))
+            .unwrap()
+            .place(vec![Coordinate::new(0, 0), Coordinate::new(1, 0)])
+            .unwrap();
+        setup.start().ok().unwrap()
+    }
+
+    #[test]
+    fn samples_remaining_cells_uniformly() {
+        let mut board = tiny_board();
+        board.shoot(Coordinate::new(0, 0)).unwrap();
+        board.shoot(Coordinate::new(2, 2)).unwrap();
+        let mut rng = StdRng::seed_from_u64(1446);
+        let mut counts = [0; 9];
+        for _ in 0..7000 {
+            let coord = sample_unshot_coordinate(&board, &mut rng).unwrap();
+            counts[board.dimensions().linearize(&coord)] += 1;
+        }
+        for (i, &count) in counts.iter().enumerate() {
+            if [0, 8].contains(&i) {
+                assert_eq!(count, 0, "{:?}", counts);
+            } else {
+                assert!(count > 850 && count < 1150, "{:?}", counts);
+            }
+        }
+    }
+
+    #[test]
+    fn terminates_on_last_cell() {
+        let mut board = tiny_board();
+        let mut rng = StdRng::seed_from_u64(1446);
+        for _ in 0..8 {
+            let coord = sample_unshot_coordinate(&board, &mut rng).unwrap();
+            board.shoot(coord).unwrap();
+        }
+        let last = sample_unshot_coordinate(&board, &mut rng).unwrap();
+        assert!(!board.get_coord(last).unwrap().hit());
+        board.shoot(last).unwrap();
+        assert_eq!(sample_unshot_coordinate(&board, &mut rng), None);
+    }
+}
@@ -14,24 +14,49 @@
 
 //! Types that make up the game board.
 
-use std::{borrow::Borrow, collections::HashMap, hash::Hash};
+use std::{
+    borrow::Borrow,
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    mem,
+};
+
+#[cfg(feature = "rng_gen")]
+use rand::Rng;
 
-use crate::ships::{ShapeProjection, ShipId};
+use crate::{
+    lexicon::{DefaultLexicon, Lexicon},
+    ships::{ShapeProjection, ShipId},
+};
 
-use self::grid::Grid;
+use self::grid::{Grid, UnshotCells};
 pub use self::{
-    dimensions::{ColinearCheck, Coordinate, Dimensions, NeighborIter, NeighborIterState},
-    errors::{AddShipError, CannotPlaceReason, CannotShootReason, PlaceError, ShotError},
+    diff::{BoardDiff, ShipMismatch, ShotMismatch},
+    dimensions::{
+        ColinearCheck, Coordinate, Dimensions, LinearStep, NeighborIter, NeighborIterState,
+    },
+    errors::{
+        AddShipError, BoardCreationError, CannotAddShipReason, CannotCreateBoardReason,
+        CannotPlaceReason, CannotRemoveShipReason, CannotShootReason, PlaceError, ShotError,
+        ShotMaskError,
+    },
     setup::BoardSetup,
 };
 
 pub mod common;
+mod diff;
 mod dimensions;
 mod errors;
 mod grid;
+pub mod masked;
 pub mod rectangular;
 pub mod setup;
 
+/// Largest number of cells a board may have. [`BoardSetup::try_new`] rejects dimensions
+/// with a larger [`total_size`][Dimensions::total_size], rather than trying to allocate
+/// them.
+pub const MAX_CELLS: usize = 1 << 24;
+
 /// Handle to a ship that allows getting information about its status.
 #[derive(Debug)]
 pub struct ShipRef<'a, I, D: Dimensions> {
@@ -67,21 +92,111 @@ impl<'a, I: ShipId, D: Dimensions> ShipRef<'a, I, D> {
         let grid = self.grid;
         self.coords().map(move |coord| (coord, grid[coord].hit))
     }
+
+    /// Get an iterator over the coordinates of this ship which have been hit, in
+    /// placement order.
+    pub fn hit_coords(&self) -> impl 'a + Iterator<Item = &'a D::Coordinate> {
+        self.hits().filter(|(_, hit)| *hit).map(|(coord, _)| coord)
+    }
+
+    /// Check if this ship has been hit at least once but not sunk.
+    pub fn is_damaged(&self) -> bool {
+        self.hit_coords().next().is_some() && !self.sunk()
+    }
+
+    /// Get an owned summary of this ship's status.
+    pub fn to_summary(&self) -> ShipSummary<I, D::Coordinate> {
+        ShipSummary {
+            id: self.id.clone(),
+            coords: self.coords().cloned().collect(),
+            sunk: self.sunk(),
+            hits: self.hit_coords().count(),
+        }
+    }
 }
 
 // Derive for Copy/Clone include bounds on the generic parameters, however, we can
 // implement copy and clone regardless of whether our generics do.
 impl<I, D: Dimensions> Clone for ShipRef<'_, I, D> {
     fn clone(&self) -> Self {
-        Self {
-            id: self.id,
-            grid: self.grid,
-            shape: self.shape,
-        }
+        *self
     }
 }
 impl<I, D: Dimensions> Copy for ShipRef<'_, I, D> {}
 
+/// Owned snapshot of a ship's status, which doesn't borrow the board. Unlike
+/// [`ShipRef`], it can be sent to other threads as long as `I` and `C` can.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ShipSummary<I, C> {
+    /// ID of the ship.
+    pub id: I,
+    /// Coordinates the ship occupies.
+    pub coords: Vec<C>,
+    /// Whether the ship has been sunk.
+    pub sunk: bool,
+    /// Number of the ship's cells which have been hit.
+    pub hits: usize,
+}
+
+/// Secondary effects of a single shot on a board.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct BoardEvents<I, C> {
+    /// The ship which was sunk by the shot, including its full footprint, if any.
+    pub sunk: Option<ShipSummary<I, C>>,
+    /// Whether the shot sunk the last ship on the board, defeating its player.
+    pub defeated: bool,
+    /// Empty cells around the sunk ship which were marked as shot along with it, in the
+    /// order they were revealed. Only a game which reveals water around sunk ships fills
+    /// this in, see [`GameSetup::set_auto_reveal_adjacent_on_sink`]. Shots on a
+    /// [`Board`] never reveal anything.
+    ///
+    /// [`GameSetup::set_auto_reveal_adjacent_on_sink`]: crate::game::uniform::GameSetup::set_auto_reveal_adjacent_on_sink
+    pub revealed: Vec<C>,
+}
+
+/// State of a single cell, as far as shots are concerned.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum CellState<I> {
+    /// The cell has not been shot.
+    Unshot,
+    /// The cell was shot and is empty.
+    Miss,
+    /// The cell was shot and is occupied by the given ship.
+    Hit(I),
+}
+
+/// Whether a cell can be targeted by a shot, from [`Board::target_legality`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum TargetStatus {
+    /// The cell can be shot.
+    Available,
+    /// The cell was already shot.
+    AlreadyShot,
+}
+
+impl TargetStatus {
+    /// Returns true if this is [`TargetStatus::Available`].
+    pub fn is_available(&self) -> bool {
+        matches!(self, TargetStatus::Available)
+    }
+
+    /// Returns true if this is [`TargetStatus::AlreadyShot`].
+    pub fn is_already_shot(&self) -> bool {
+        matches!(self, TargetStatus::AlreadyShot)
+    }
+}
+
+/// Change to a single cell of a [`Board`], recorded while
+/// [`record_changes`][Board::record_changes] is enabled.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CellChange<I, C> {
+    /// Coordinate of the cell which changed.
+    pub coord: C,
+    /// State of the cell after the change.
+    pub state: CellState<I>,
+}
+
 /// Reference to a particular cell in the grid.
 #[derive(Debug, Copy, Clone)]
 pub struct CellRef<'a, I, D: Dimensions> {
@@ -110,9 +225,25 @@ impl<'a, I, D: Dimensions> CellRef<'a, I, D> {
     pub fn ship(&self) -> Option<ShipRef<'a, I, D>> {
         self.ship
     }
+
+    /// Returns true if this cell is in the given state. An unshot cell matches
+    /// [`CellState::Unshot`] whether or not a ship occupies it.
+    pub fn matches(&self, expected: &CellState<I>) -> bool
+    where
+        I: PartialEq,
+    {
+        match (expected, self.hit, self.ship) {
+            (CellState::Unshot, false, _) => true,
+            (CellState::Miss, true, None) => true,
+            (CellState::Hit(id), true, Some(ship)) => ship.id == id,
+            _ => false,
+        }
+    }
 }
 
 /// Result of a shot on a single player's board.
+#[derive(Debug)]
+#[non_exhaustive]
 pub enum ShotOutcome<I> {
     /// The shot did not hit anything.
     Miss,
@@ -126,6 +257,26 @@ pub enum ShotOutcome<I> {
 }
 
 impl<I> ShotOutcome<I> {
+    /// Returns true if this is [`ShotOutcome::Miss`].
+    pub fn is_miss(&self) -> bool {
+        matches!(self, ShotOutcome::Miss)
+    }
+
+    /// Returns true if this is [`ShotOutcome::Hit`].
+    pub fn is_hit(&self) -> bool {
+        matches!(self, ShotOutcome::Hit(_))
+    }
+
+    /// Returns true if this is [`ShotOutcome::Sunk`].
+    pub fn is_sunk(&self) -> bool {
+        matches!(self, ShotOutcome::Sunk(_))
+    }
+
+    /// Returns true if this is [`ShotOutcome::Defeated`].
+    pub fn is_defeated(&self) -> bool {
+        matches!(self, ShotOutcome::Defeated(_))
+    }
+
     /// Get the id of the ship that was hit.
     pub fn ship(&self) -> Option<&I> {
         match self {
@@ -143,6 +294,22 @@ impl<I> ShotOutcome<I> {
             ShotOutcome::Hit(id) | ShotOutcome::Sunk(id) | ShotOutcome::Defeated(id) => Some(id),
         }
     }
+
+    /// Describe this outcome as a human-readable string. Ship names are produced by the
+    /// given `namer`, so callers control how ships are referred to.
+    pub fn describe(&self, namer: impl Fn(&I) -> String) -> String {
+        self.describe_in(&DefaultLexicon(namer))
+    }
+
+    /// Describe this outcome as a human-readable string using the given [`Lexicon`].
+    pub fn describe_in(&self, lexicon: &(impl Lexicon<I> + ?Sized)) -> String {
+        match self {
+            ShotOutcome::Miss => lexicon.miss(),
+            ShotOutcome::Hit(id) => lexicon.hit(id),
+            ShotOutcome::Sunk(id) => lexicon.sunk(id),
+            ShotOutcome::Defeated(id) => lexicon.defeated(id),
+        }
+    }
 }
 
 /// Represents a single player's board, including their ships and their side of the ocean.
@@ -157,6 +324,12 @@ pub struct Board<I: ShipId, D: Dimensions> {
     //   efficiently decide if it was sunk. Requires deduplicating projected points.
     /// Mapping of all ship IDs to their projected positions in the grid.
     ships: HashMap<I, ShapeProjection<D::Coordinate>>,
+
+    /// Log of cell changes since they were last drained, if recording is enabled.
+    changes: Option<Vec<CellChange<I, D::Coordinate>>>,
+
+    /// Cells which haven't been shot yet.
+    unshot: UnshotCells,
 }
 
 impl<I: ShipId, D: Dimensions> Board<I, D> {
@@ -171,18 +344,71 @@ impl<I: ShipId, D: Dimensions> Board<I, D> {
     }
 
     /// Get an iterator over all ships on this board.
-    pub fn iter_ships(&self) -> impl Iterator<Item = ShipRef<I, D>> {
+    pub fn iter_ships(&self) -> impl Iterator<Item = ShipRef<'_, I, D>> {
         let grid = &self.grid;
         self.ships
             .iter()
             .map(move |(id, shape)| ShipRef { id, grid, shape })
     }
 
+    /// Get the number of cells on this board that are occupied by a ship.
+    pub fn occupied_cells(&self) -> usize {
+        self.grid
+            .cells
+            .iter()
+            .filter(|cell| cell.ship.is_some())
+            .count()
+    }
+
+    /// Iterate every cell of the board along with whether it can be shot, in index order.
+    /// Doesn't reveal which cells are occupied, so it is safe to show to the shooter.
+    pub fn target_legality(&self) -> impl '_ + Iterator<Item = (D::Coordinate, TargetStatus)> {
+        self.grid
+            .cells
+            .iter()
+            .enumerate()
+            .filter_map(move |(i, cell)| {
+                // Skip indexes which don't correspond to a cell, such as blocked cells.
+                let coord = self.grid.dim.try_un_linearize(i)?;
+                let status = if cell.hit {
+                    TargetStatus::AlreadyShot
+                } else {
+                    TargetStatus::Available
+                };
+                Some((coord, status))
+            })
+    }
+
+    /// Get the fraction of this board's cells that are occupied by a ship, from 0 to 1.
+    /// Useful for comparing fleet density across boards of different sizes. Returns 0
+    /// for a board with no cells.
+    pub fn occupancy_ratio(&self) -> f64 {
+        let total = self.grid.dim.total_size();
+        if total == 0 {
+            0.0
+        } else {
+            self.occupied_cells() as f64 / total as f64
+        }
+    }
+
+    /// Get an iterator over ships which have been hit at least once but not sunk, along
+    /// with the coordinates of their hit cells in placement order.
+    pub fn damaged_ships(&self) -> impl Iterator<Item = (ShipRef<'_, I, D>, Vec<&D::Coordinate>)> {
+        self.iter_ships()
+            .filter(|ship| ship.is_damaged())
+            .map(|ship| (ship, ship.hit_coords().collect()))
+    }
+
+    /// Get owned summaries of all ships on this board.
+    pub fn ship_summaries(&self) -> Vec<ShipSummary<I, D::Coordinate>> {
+        self.iter_ships().map(|ship| ship.to_summary()).collect()
+    }
+
     /// Get the ship with the specified ID if it exists.
-    pub fn get_ship<Q: ?Sized>(&self, ship: &Q) -> Option<ShipRef<I, D>>
+    pub fn get_ship<Q>(&self, ship: &Q) -> Option<ShipRef<'_, I, D>>
     where
         I: Borrow<Q>,
-        Q: Hash + Eq,
+        Q: Hash + Eq + ?Sized,
     {
         self.ships.get_key_value(ship).map(|(id, shape)| ShipRef {
             id,
@@ -191,9 +417,27 @@ impl<I: ShipId, D: Dimensions> Board<I, D> {
         })
     }
 
+    /// Check whether any cell of ship `a` neighbors any cell of ship `b`, using the
+    /// neighbors defined by this board's [`Dimensions`]. Returns `None` if either ship
+    /// doesn't exist.
+    pub fn ships_adjacent<Q>(&self, a: &Q, b: &Q) -> Option<bool>
+    where
+        I: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let a = self.ships.get(a)?;
+        let (b, _) = self.ships.get_key_value(b)?;
+        Some(a.iter().any(|coord| {
+            self.grid
+                .dim
+                .neighbors(coord.clone())
+                .any(|neighbor| self.ship_id_at(&neighbor) == Some(b))
+        }))
+    }
+
     /// Get a reference to the cell at the given coordinate. Returns None if the
     /// coordinate is out of bounds.
-    pub fn get_coord(&self, coord: D::Coordinate) -> Option<CellRef<I, D>> {
+    pub fn get_coord(&self, coord: D::Coordinate) -> Option<CellRef<'_, I, D>> {
         self.grid.get(&coord).map(|cell| CellRef {
             coord,
             hit: cell.hit,
@@ -201,12 +445,237 @@ impl<I: ShipId, D: Dimensions> Board<I, D> {
         })
     }
 
+    /// Get the ID of the ship occupying the given coordinate, without resolving the full
+    /// [`ShipRef`]. Returns `None` if the cell is empty or the coordinate is out of
+    /// bounds.
+    pub fn ship_id_at(&self, coord: &D::Coordinate) -> Option<&I> {
+        self.grid.get(coord).and_then(|cell| cell.ship.as_ref())
+    }
+
+    /// Get the distinct IDs of the ships occupying any of the given cells, in the order
+    /// they are first found. Empty cells and out of bounds coordinates are skipped.
+    pub fn ships_in_region<'a>(
+        &'a self,
+        coords: &'a [D::Coordinate],
+    ) -> impl 'a + Iterator<Item = &'a I> {
+        let mut seen = HashSet::new();
+        coords
+            .iter()
+            .filter_map(move |coord| self.ship_id_at(coord))
+            .filter(move |id| seen.insert(*id))
+    }
+
+    /// Get an iterator over shots that would sink a ship. For each ship that has been hit
+    /// but has exactly one un-hit cell remaining, yields that cell along with the ID of
+    /// the ship.
+    pub fn finishing_shots(&self) -> impl Iterator<Item = (D::Coordinate, I)> + '_ {
+        self.iter_ships().filter_map(|ship| {
+            let mut remaining = ship.hits().filter(|(_, hit)| !hit).map(|(coord, _)| coord);
+            match (remaining.next(), remaining.next()) {
+                (Some(coord), None) if ship.hits().any(|(_, hit)| hit) => {
+                    Some((coord.clone(), ship.id().clone()))
+                }
+                _ => None,
+            }
+        })
+    }
+
+    /// Get a bitset of which cells have been shot. Bit `i % 64` of word `i / 64` is set
+    /// iff the cell with linear index `i` (see [`Dimensions::linearize`]) was shot. Two
+    /// masks from boards with the same dimensions can be diffed word by word.
+    pub fn shot_mask(&self) -> Vec<u64> {
+        let mut mask = vec![0u64; Self::mask_words(self.grid.cells.len())];
+        for (i, cell) in self.grid.cells.iter().enumerate() {
+            if cell.hit {
+                mask[i / 64] |= 1 << (i % 64);
+            }
+        }
+        mask
+    }
+
+    /// Restore which cells have been shot from a mask produced by
+    /// [`shot_mask`][Board::shot_mask]. Cells whose bits are clear become un-shot. Bits
+    /// past the last cell are ignored. Returns an error without changing the board if the
+    /// mask has the wrong number of words for this board, or if it sets the bit of an
+    /// index the dimensions don't use.
+    pub fn apply_shot_mask(&mut self, mask: &[u64]) -> Result<(), ShotMaskError> {
+        let expected = Self::mask_words(self.grid.cells.len());
+        if mask.len() != expected {
+            return Err(ShotMaskError::WrongLength {
+                expected,
+                actual: mask.len(),
+            });
+        }
+        let is_set = |i: usize| mask[i / 64] & (1 << (i % 64)) != 0;
+        if let Some(i) = (0..self.grid.cells.len())
+            .find(|&i| is_set(i) && self.grid.dim.try_un_linearize(i).is_none())
+        {
+            return Err(ShotMaskError::Unaddressable(i));
+        }
+        for i in 0..self.grid.cells.len() {
+            let hit = is_set(i);
+            if self.grid.cells[i].hit != hit {
+                // Unused indexes are never shot and their bits were checked above, so
+                // only addressable cells change.
+                let coord = self.grid.dim.un_linearize(i);
+                self.grid.cells[i].hit = hit;
+                self.log_change(&coord);
+                self.unshot.set_hit(i, hit);
+            }
+        }
+        Ok(())
+    }
+
+    /// Clear every shot from this board, keeping the ship placements, so the same layout
+    /// can be played again. While recording, each cleared cell is logged as
+    /// [`CellState::Unshot`].
+    pub fn reset_damage(&mut self) {
+        let clear = vec![0; Self::mask_words(self.grid.cells.len())];
+        // The mask is sized for this board, so it is always accepted.
+        self.apply_shot_mask(&clear).unwrap();
+    }
+
+    /// Apply pre-existing damage to this board, for starting from a mid-game position.
+    /// Each coordinate is shot in order as by [`shoot`][Board::shoot]. Fails if any shot
+    /// fails, or with [`CannotShootReason::AlreadyDefeated`] if the damage would sink
+    /// every ship, since the board would already be defeated.
+    pub fn with_damage(
+        mut self,
+        shots: impl IntoIterator<Item = D::Coordinate>,
+    ) -> Result<Self, ShotError<D::Coordinate>> {
+        self.apply_damage(shots)?;
+        Ok(self)
+    }
+
+    /// Apply pre-existing damage to this board in place, like
+    /// [`with_damage`][Board::with_damage]. If any shot fails, the board is left as it
+    /// was before.
+    pub(crate) fn apply_damage(
+        &mut self,
+        shots: impl IntoIterator<Item = D::Coordinate>,
+    ) -> Result<(), ShotError<D::Coordinate>> {
+        let before = self.shot_mask();
+        for coord in shots {
+            let res = match self.shoot(coord.clone()) {
+                Ok(ShotOutcome::Defeated(_)) => {
+                    Err(ShotError::new(CannotShootReason::AlreadyDefeated, coord))
+                }
+                Ok(_) => Ok(()),
+                Err(err) => Err(err),
+            };
+            if let Err(err) = res {
+                self.apply_shot_mask(&before).unwrap();
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether changes to cells are being recorded for
+    /// [`drain_changes`][Board::drain_changes].
+    pub fn record_changes(&self) -> bool {
+        self.changes.is_some()
+    }
+
+    /// Set whether changes to cells are recorded. Recording is off by default. Turning it
+    /// off discards any changes which haven't been drained.
+    pub fn set_record_changes(&mut self, record: bool) {
+        match (record, &self.changes) {
+            (true, None) => self.changes = Some(Vec::new()),
+            (false, _) => self.changes = None,
+            (true, Some(_)) => {}
+        }
+    }
+
+    /// Take the cell changes recorded since the last call, in the order they happened.
+    /// Applying them with [`apply_change`][Board::apply_change] to a copy of this board
+    /// taken at the time of the last call brings it up to date. Returns an empty list if
+    /// recording is off.
+    pub fn drain_changes(&mut self) -> Vec<CellChange<I, D::Coordinate>> {
+        self.changes.as_mut().map(mem::take).unwrap_or_default()
+    }
+
+    /// Apply a change drained from another board with the same ships and dimensions.
+    /// Only whether the cell was shot is applied; the ship in the cell always comes from
+    /// this board's own placements.
+    pub fn apply_change(
+        &mut self,
+        change: &CellChange<I, D::Coordinate>,
+    ) -> Result<(), ShotError<D::Coordinate>> {
+        let index = match self.grid.dim.try_linearize(&change.coord) {
+            None => {
+                return Err(ShotError::new(
+                    CannotShootReason::OutOfBounds,
+                    change.coord.clone(),
+                ))
+            }
+            Some(index) => index,
+        };
+        let hit = change.state != CellState::Unshot;
+        self.grid.cells[index].hit = hit;
+        self.log_change(&change.coord);
+        self.unshot.set_hit(index, hit);
+        Ok(())
+    }
+
+    /// Record the current state of the given cell, if recording is enabled.
+    fn log_change(&mut self, coord: &D::Coordinate) {
+        if let Some(changes) = &mut self.changes {
+            let cell = &self.grid[coord];
+            let state = match (cell.hit, &cell.ship) {
+                (false, _) => CellState::Unshot,
+                (true, None) => CellState::Miss,
+                (true, Some(id)) => CellState::Hit(id.clone()),
+            };
+            changes.push(CellChange {
+                coord: coord.clone(),
+                state,
+            });
+        }
+    }
+
+    /// Number of words needed for a shot mask covering the given number of cells.
+    fn mask_words(cells: usize) -> usize {
+        cells.div_ceil(64)
+    }
+
     /// Fire a shot at this player, returning a result indicating why the shot was aborted
     /// or the result of the shot on this player.
     pub fn shoot(
         &mut self,
         coord: D::Coordinate,
     ) -> Result<ShotOutcome<I>, ShotError<D::Coordinate>> {
+        self.shoot_with_events(coord).map(|(outcome, _)| outcome)
+    }
+
+    /// Fire a shot at this player like [`shoot`][Board::shoot], additionally returning
+    /// the secondary effects of the shot, so callers don't have to re-query the board to
+    /// find out which ship sank or whether the player was defeated.
+    #[allow(clippy::type_complexity)]
+    pub fn shoot_with_events(
+        &mut self,
+        coord: D::Coordinate,
+    ) -> Result<(ShotOutcome<I>, BoardEvents<I, D::Coordinate>), ShotError<D::Coordinate>> {
+        #[cfg(feature = "tracing")]
+        let traced = coord.clone();
+        let res = self.fire(coord);
+        #[cfg(feature = "tracing")]
+        match &res {
+            Ok((outcome, _)) => tracing::debug!(coord = ?traced, ?outcome, "board shot"),
+            Err(err) => {
+                tracing::debug!(coord = ?traced, reason = ?err.reason(), "board shot rejected")
+            }
+        }
+        res
+    }
+
+    /// Implementation of [`shoot_with_events`][Board::shoot_with_events], without
+    /// instrumentation.
+    #[allow(clippy::type_complexity)]
+    fn fire(
+        &mut self,
+        coord: D::Coordinate,
+    ) -> Result<(ShotOutcome<I>, BoardEvents<I, D::Coordinate>), ShotError<D::Coordinate>> {
         if self.defeated() {
             return Err(ShotError::new(CannotShootReason::AlreadyDefeated, coord));
         }
@@ -220,11 +689,783 @@ impl<I: ShipId, D: Dimensions> Board<I, D> {
                 cell.ship.as_ref().cloned()
             }
         };
-        Ok(match hit_ship {
-            None => ShotOutcome::Miss,
-            Some(ship) if self.defeated() => ShotOutcome::Defeated(ship),
-            Some(ship) if self.get_ship(&ship).unwrap().sunk() => ShotOutcome::Sunk(ship),
-            Some(ship) => ShotOutcome::Hit(ship),
-        })
+        self.log_change(&coord);
+        self.unshot.set_hit(self.grid.dim.linearize(&coord), true);
+        let ship = match hit_ship {
+            None => {
+                let events = BoardEvents {
+                    sunk: None,
+                    defeated: false,
+                    revealed: Vec::new(),
+                };
+                return Ok((ShotOutcome::Miss, events));
+            }
+            Some(ship) => ship,
+        };
+        let sunk = Some(self.get_ship(&ship).unwrap().to_summary()).filter(|ship| ship.sunk);
+        let defeated = sunk.is_some() && self.defeated();
+        let outcome = if defeated {
+            ShotOutcome::Defeated(ship)
+        } else if sunk.is_some() {
+            ShotOutcome::Sunk(ship)
+        } else {
+            ShotOutcome::Hit(ship)
+        };
+        let events = BoardEvents {
+            sunk,
+            defeated,
+            revealed: Vec::new(),
+        };
+        Ok((outcome, events))
+    }
+
+    /// Mark every un-shot, empty cell neighboring the given cells as shot, without
+    /// counting as shots. Returns the cells which were revealed, in order. Cells holding
+    /// a ship are left alone, even if they are neighbors.
+    pub(crate) fn reveal_around<'a>(
+        &mut self,
+        coords: impl IntoIterator<Item = &'a D::Coordinate>,
+    ) -> Vec<D::Coordinate>
+    where
+        D::Coordinate: 'a,
+    {
+        let mut revealed = Vec::new();
+        for coord in coords {
+            let neighbors: Vec<_> = self.grid.dim.neighbors(coord.clone()).collect();
+            for neighbor in neighbors {
+                match self.grid.get_mut(&neighbor) {
+                    Some(cell) if !cell.hit && cell.ship.is_none() => cell.hit = true,
+                    _ => continue,
+                }
+                self.log_change(&neighbor);
+                self.unshot
+                    .set_hit(self.grid.dim.linearize(&neighbor), true);
+                revealed.push(neighbor);
+            }
+        }
+        revealed
+    }
+}
+
+#[cfg(feature = "rng_gen")]
+impl<I: ShipId, D: Dimensions> Board<I, D> {
+    /// Pick a cell which has not been shot yet uniformly at random. Returns `None` if
+    /// every cell has been shot. The board keeps a list of the remaining cells, so this
+    /// takes constant time however full the board is.
+    pub fn sample_unshot<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<D::Coordinate> {
+        let unshot = self.unshot.indexes();
+        if unshot.is_empty() {
+            None
+        } else {
+            let index = unshot[rng.gen_range(0, unshot.len())];
+            Some(self.grid.dim.un_linearize(index))
+        }
+    }
+
+    /// Shoot a cell which has not been shot yet, picked uniformly at random, and return
+    /// the cell along with the outcome. Returns `None` if every cell has been shot or the
+    /// player has already been defeated.
+    pub fn shoot_random<R: Rng + ?Sized>(
+        &mut self,
+        rng: &mut R,
+    ) -> Option<(D::Coordinate, ShotOutcome<I>)> {
+        if self.defeated() {
+            return None;
+        }
+        let coord = self.sample_unshot(rng)?;
+        // The cell is in bounds and unshot, and the board isn't defeated.
+        let outcome = self.shoot(coord.clone()).ok().unwrap();
+        Some((coord, outcome))
+    }
+}
+
+impl<I: ShipId, D: LinearStep> Board<I, D> {
+    /// Fire a torpedo which travels in a straight line from `start` through `next`, which
+    /// must be a neighbor of `start`, until it reaches a ship cell which has not been hit
+    /// yet, and shoots only that cell. Unshot empty cells along the way are marked as
+    /// misses, while cells which were already shot are passed over. Returns the cells
+    /// newly marked as misses, in order, and the outcome of the hit, or `None` if the
+    /// torpedo left the board or came back around to `start` without hitting anything.
+    /// Nothing is shot if the player has already been defeated.
+    ///
+    /// The direction is given as a cell rather than a direction value because
+    /// [`Dimensions`] have no direction type in general: a [`LinearStep`] follows a line
+    /// from two neighboring cells, so `start` and `next` are exactly what it needs on any
+    /// board. On a [`RectDimensions`] board, firing up from `(x, y)` means passing
+    /// `(x, y - 1)` as `next`. If `next` is not a neighbor of `start`, the torpedo stops
+    /// after `start`.
+    ///
+    /// [`RectDimensions`]: crate::board::rectangular::RectDimensions
+    pub fn shoot_torpedo(
+        &mut self,
+        start: D::Coordinate,
+        next: D::Coordinate,
+    ) -> (Vec<D::Coordinate>, Option<ShotOutcome<I>>) {
+        let mut missed = Vec::new();
+        if self.defeated() {
+            return (missed, None);
+        }
+        let mut prev: Option<D::Coordinate> = None;
+        let mut cur = start.clone();
+        loop {
+            match self.grid.get(&cur) {
+                None => break,
+                Some(cell) if cell.hit => {}
+                Some(cell) if cell.ship.is_some() => {
+                    // The cell is in bounds and not yet hit, and the board isn't defeated.
+                    return (missed, Some(self.shoot(cur).ok().unwrap()));
+                }
+                Some(_) => {
+                    self.shoot(cur.clone()).ok().unwrap();
+                    missed.push(cur.clone());
+                }
+            }
+            let following = match prev {
+                None => {
+                    Some(next.clone()).filter(|next| self.dimensions().is_neighbor(&start, next))
+                }
+                Some(ref prev) => self.dimensions().step(prev, &cur),
+            };
+            match following {
+                Some(following) if following != start => {
+                    prev = Some(mem::replace(&mut cur, following))
+                }
+                _ => break,
+            }
+        }
+        (missed, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        board::{
+            masked::MaskedDimensions,
+            rectangular::{Coordinate, RectDimensions},
+        },
+        ships::Line,
+    };
+
+    /// Build a started 4x4 board with a ship of length 3 on row 0 from column 0 and a
+    /// ship of length 2 on column 3 from row 2, both pointing away from the origin.
+    fn small_board() -> Board<&'static str, RectDimensions> {
+        let mut setup = BoardSetup::new(RectDimensions::new(4, 4));
+        let cells = |coords: &[(usize, usize)]| {
+            coords
+                .iter()
+                .map(|&(x, y)| Coordinate::new(x, y))
+                .collect::<Vec<_>>()
+        };
+        setup
+            .add_ship("cruiser", Line::new(3))
+            .unwrap()
+            .place(cells(&[(0, 0), (1, 0), (2, 0)]))
+            .unwrap();
+        setup
+            .add_ship("destroyer", Line::new(2))
+            .unwrap()
+            .place(cells(&[(3, 2), (3, 3)]))
+            .unwrap();
+        setup.start().ok().unwrap()
+    }
+
+    #[test]
+    fn describe_each_outcome() {
+        let namer = |id: &&str| id.to_uppercase();
+        assert_eq!(ShotOutcome::<&str>::Miss.describe(namer), "Miss.");
+        assert_eq!(ShotOutcome::Hit("sub").describe(namer), "Hit SUB!");
+        assert_eq!(ShotOutcome::Sunk("sub").describe(namer), "Sunk SUB!");
+        assert_eq!(
+            ShotOutcome::Defeated("sub").describe(namer),
+            "Sunk SUB! All ships sunk."
+        );
+    }
+
+    #[test]
+    fn ship_id_at_cells() {
+        let board = small_board();
+        assert_eq!(board.ship_id_at(&Coordinate::new(1, 0)), Some(&"cruiser"));
+        assert_eq!(board.ship_id_at(&Coordinate::new(3, 3)), Some(&"destroyer"));
+        assert_eq!(board.ship_id_at(&Coordinate::new(1, 1)), None);
+        assert_eq!(board.ship_id_at(&Coordinate::new(4, 0)), None);
+        assert_eq!(board.ship_id_at(&Coordinate::new(0, 4)), None);
+    }
+
+    #[test]
+    fn finishing_shot_for_ship_missing_one_cell() {
+        let mut board = small_board();
+        assert_eq!(board.finishing_shots().count(), 0);
+        board.shoot(Coordinate::new(0, 0)).unwrap();
+        // Two cells of the cruiser remain, so no single shot sinks it yet.
+        assert_eq!(board.finishing_shots().count(), 0);
+        board.shoot(Coordinate::new(2, 0)).unwrap();
+        let shots: Vec<_> = board.finishing_shots().collect();
+        assert_eq!(shots, [(Coordinate::new(1, 0), "cruiser")]);
+        board.shoot(Coordinate::new(1, 0)).unwrap();
+        assert_eq!(board.finishing_shots().count(), 0);
+    }
+
+    #[cfg(feature = "rng_gen")]
+    #[test]
+    fn sample_unshot_is_uniform() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut board = small_board();
+        board.shoot(Coordinate::new(0, 0)).unwrap();
+        board.shoot(Coordinate::new(2, 2)).unwrap();
+        board.shoot(Coordinate::new(3, 3)).unwrap();
+        let mut rng = StdRng::seed_from_u64(1446);
+        let mut counts = [0; 16];
+        for _ in 0..13000 {
+            let coord = board.sample_unshot(&mut rng).unwrap();
+            counts[board.dimensions().linearize(&coord)] += 1;
+        }
+        for (i, &count) in counts.iter().enumerate() {
+            if [0, 10, 15].contains(&i) {
+                assert_eq!(count, 0, "{:?}", counts);
+            } else {
+                assert!(count > 850 && count < 1150, "{:?}", counts);
+            }
+        }
+    }
+
+    #[cfg(feature = "rng_gen")]
+    #[test]
+    fn sample_unshot_last_cell() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut board = small_board();
+        let mut rng = StdRng::seed_from_u64(1446);
+        // Shoot everything except the end of the destroyer, which has index 15.
+        board.apply_shot_mask(&[!(1 << 15)]).unwrap();
+        for _ in 0..10 {
+            assert_eq!(board.sample_unshot(&mut rng), Some(Coordinate::new(3, 3)));
+        }
+        let (coord, outcome) = board.shoot_random(&mut rng).unwrap();
+        assert_eq!(coord, Coordinate::new(3, 3));
+        assert!(matches!(outcome, ShotOutcome::Defeated("destroyer")));
+        assert_eq!(board.sample_unshot(&mut rng), None);
+
+        // Un-shooting cells makes them available again.
+        board.reset_damage();
+        let mut seen = HashSet::new();
+        while seen.len() < 16 {
+            seen.insert(board.sample_unshot(&mut rng).unwrap());
+        }
+    }
+
+    #[test]
+    fn ship_summaries_sent_to_thread() {
+        let mut board = small_board();
+        board.shoot(Coordinate::new(1, 0)).unwrap();
+        board.shoot(Coordinate::new(3, 2)).unwrap();
+        board.shoot(Coordinate::new(3, 3)).unwrap();
+        let summaries = board.ship_summaries();
+        let mut summaries = std::thread::spawn(move || summaries).join().unwrap();
+        summaries.sort_by_key(|summary| summary.id);
+        assert_eq!(
+            summaries,
+            vec![
+                ShipSummary {
+                    id: "cruiser",
+                    coords: vec![
+                        Coordinate::new(0, 0),
+                        Coordinate::new(1, 0),
+                        Coordinate::new(2, 0)
+                    ],
+                    sunk: false,
+                    hits: 1,
+                },
+                ShipSummary {
+                    id: "destroyer",
+                    coords: vec![Coordinate::new(3, 2), Coordinate::new(3, 3)],
+                    sunk: true,
+                    hits: 2,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn damaged_ship_hit_coords() {
+        let mut board = small_board();
+        let cruiser = board.get_ship(&"cruiser").unwrap();
+        assert!(!cruiser.is_damaged());
+        assert_eq!(cruiser.hit_coords().count(), 0);
+
+        board.shoot(Coordinate::new(2, 0)).unwrap();
+        board.shoot(Coordinate::new(0, 0)).unwrap();
+        let cruiser = board.get_ship(&"cruiser").unwrap();
+        assert!(cruiser.is_damaged());
+        let hits: Vec<_> = cruiser.hit_coords().cloned().collect();
+        assert_eq!(hits, [Coordinate::new(0, 0), Coordinate::new(2, 0)]);
+
+        board.shoot(Coordinate::new(1, 0)).unwrap();
+        let cruiser = board.get_ship(&"cruiser").unwrap();
+        assert!(!cruiser.is_damaged());
+        assert_eq!(cruiser.hit_coords().count(), 3);
+    }
+
+    #[test]
+    fn torpedo_hits_first_ship_cell() {
+        let mut board = small_board();
+        let (missed, outcome) = board.shoot_torpedo(Coordinate::new(2, 3), Coordinate::new(2, 2));
+        assert_eq!(
+            missed,
+            [
+                Coordinate::new(2, 3),
+                Coordinate::new(2, 2),
+                Coordinate::new(2, 1)
+            ]
+        );
+        assert!(matches!(outcome, Some(ShotOutcome::Hit("cruiser"))));
+        let hits: Vec<_> = board
+            .get_ship(&"cruiser")
+            .unwrap()
+            .hit_coords()
+            .cloned()
+            .collect();
+        assert_eq!(hits, [Coordinate::new(2, 0)]);
+        let shot = board.shot_mask()[0].count_ones();
+        assert_eq!(shot, 4);
+
+        // Cells which were already shot are passed over.
+        let (missed, outcome) = board.shoot_torpedo(Coordinate::new(1, 2), Coordinate::new(2, 2));
+        assert_eq!(missed, [Coordinate::new(1, 2)]);
+        assert!(matches!(outcome, Some(ShotOutcome::Hit("destroyer"))));
+        assert!(board.get_coord(Coordinate::new(3, 2)).unwrap().hit());
+        assert!(!board.get_coord(Coordinate::new(3, 3)).unwrap().hit());
+    }
+
+    #[test]
+    fn torpedo_leaves_board() {
+        let mut board = small_board();
+        let (missed, outcome) = board.shoot_torpedo(Coordinate::new(1, 2), Coordinate::new(1, 3));
+        assert_eq!(missed, [Coordinate::new(1, 2), Coordinate::new(1, 3)]);
+        assert!(outcome.is_none());
+    }
+
+    #[test]
+    fn drained_changes_rebuild_board() {
+        let mut board = small_board();
+        let mut copy = small_board();
+        board.shoot(Coordinate::new(1, 1)).unwrap();
+        // Nothing is recorded until recording is turned on.
+        assert!(!board.record_changes());
+        assert!(board.drain_changes().is_empty());
+        copy.shoot(Coordinate::new(1, 1)).unwrap();
+
+        board.set_record_changes(true);
+        board.shoot(Coordinate::new(0, 3)).unwrap();
+        board.shoot(Coordinate::new(3, 2)).unwrap();
+        let first = board.drain_changes();
+        assert_eq!(
+            first,
+            [
+                CellChange {
+                    coord: Coordinate::new(0, 3),
+                    state: CellState::Miss,
+                },
+                CellChange {
+                    coord: Coordinate::new(3, 2),
+                    state: CellState::Hit("destroyer"),
+                },
+            ]
+        );
+        board.shoot(Coordinate::new(3, 3)).unwrap();
+        board.shoot(Coordinate::new(0, 0)).unwrap();
+        board.shoot(Coordinate::new(2, 1)).unwrap();
+        let second = board.drain_changes();
+        assert_eq!(second.len(), 3);
+        assert!(board.drain_changes().is_empty());
+
+        for change in first.iter().chain(&second) {
+            copy.apply_change(change).unwrap();
+        }
+        assert!(copy.diff(&board).is_empty(), "{}", copy.diff(&board));
+        assert_eq!(copy.shot_mask(), board.shot_mask());
+        // Ships are listed in arbitrary order.
+        let summaries = |board: &Board<&'static str, RectDimensions>| {
+            let mut summaries = board.ship_summaries();
+            summaries.sort_by_key(|summary| summary.id);
+            summaries
+        };
+        assert_eq!(summaries(&copy), summaries(&board));
+        assert!(copy.get_ship(&"destroyer").unwrap().sunk());
+
+        let out_of_bounds = CellChange {
+            coord: Coordinate::new(4, 0),
+            state: CellState::Miss,
+        };
+        assert_eq!(
+            copy.apply_change(&out_of_bounds),
+            Err(ShotError::new(
+                CannotShootReason::OutOfBounds,
+                Coordinate::new(4, 0)
+            ))
+        );
+    }
+
+    #[test]
+    fn events_for_sinking_shots() {
+        let mut board = small_board();
+        let (outcome, events) = board.shoot_with_events(Coordinate::new(3, 2)).unwrap();
+        assert!(matches!(outcome, ShotOutcome::Hit("destroyer")));
+        assert_eq!(events.sunk, None);
+        assert!(!events.defeated);
+
+        let (outcome, events) = board.shoot_with_events(Coordinate::new(3, 3)).unwrap();
+        assert!(matches!(outcome, ShotOutcome::Sunk("destroyer")));
+        assert!(!events.defeated);
+        assert!(events.revealed.is_empty());
+        let sunk = events.sunk.unwrap();
+        assert_eq!(sunk.id, "destroyer");
+        assert_eq!(sunk.coords, [Coordinate::new(3, 2), Coordinate::new(3, 3)]);
+        assert!(sunk.sunk);
+        assert_eq!(sunk.hits, 2);
+
+        board.shoot(Coordinate::new(0, 0)).unwrap();
+        board.shoot(Coordinate::new(2, 0)).unwrap();
+        let (outcome, events) = board.shoot_with_events(Coordinate::new(1, 0)).unwrap();
+        assert!(matches!(outcome, ShotOutcome::Defeated("cruiser")));
+        assert!(events.defeated);
+        assert!(events.revealed.is_empty());
+        assert_eq!(
+            events.sunk,
+            Some(ShipSummary {
+                id: "cruiser",
+                coords: vec![
+                    Coordinate::new(0, 0),
+                    Coordinate::new(1, 0),
+                    Coordinate::new(2, 0)
+                ],
+                sunk: true,
+                hits: 3,
+            })
+        );
+        let err = board.shoot_with_events(Coordinate::new(3, 0)).unwrap_err();
+        assert_eq!(
+            err,
+            ShotError::new(CannotShootReason::AlreadyDefeated, Coordinate::new(3, 0))
+        );
+    }
+
+    #[test]
+    fn torpedo_stops_without_neighbor() {
+        let mut board = small_board();
+        let (missed, outcome) = board.shoot_torpedo(Coordinate::new(1, 2), Coordinate::new(3, 2));
+        assert_eq!(missed, [Coordinate::new(1, 2)]);
+        assert!(outcome.is_none());
+        assert!(!board.get_coord(Coordinate::new(2, 2)).unwrap().hit());
+    }
+
+    #[cfg(feature = "rng_gen")]
+    #[test]
+    fn shoot_random_covers_board() {
+        // Every cell holds a ship, so the board is only defeated by the last cell.
+        let mut setup = BoardSetup::new(RectDimensions::new(4, 4));
+        for y in 0..4 {
+            let cells = (0..4).map(|x| Coordinate::new(x, y)).collect();
+            setup
+                .add_ship(y, Line::new(4))
+                .unwrap()
+                .place(cells)
+                .unwrap();
+        }
+        let mut board = setup.start().ok().unwrap();
+        let mut rng = rand::thread_rng();
+        let mut seen = HashSet::new();
+        for shot in 1..=16 {
+            let (coord, outcome) = board.shoot_random(&mut rng).unwrap();
+            assert!(seen.insert(coord), "{:?} shot twice", coord);
+            assert_eq!(matches!(outcome, ShotOutcome::Defeated(_)), shot == 16);
+        }
+        assert!(board.shoot_random(&mut rng).is_none());
+    }
+
+    #[cfg(feature = "rng_gen")]
+    #[test]
+    fn shoot_random_stops_at_defeat() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..20 {
+            let mut board = small_board();
+            let mut seen = HashSet::new();
+            while let Some((coord, _)) = board.shoot_random(&mut rng) {
+                assert!(seen.insert(coord), "{:?} shot twice", coord);
+            }
+            assert!(board.defeated());
+            assert_eq!(seen.len(), board.shot_mask()[0].count_ones() as usize);
+        }
+    }
+
+    #[test]
+    fn cell_matches_state() {
+        let mut board = small_board();
+        board.shoot(Coordinate::new(0, 0)).unwrap();
+        board.shoot(Coordinate::new(1, 1)).unwrap();
+        let cell = |x, y| board.get_coord(Coordinate::new(x, y)).unwrap();
+
+        let hit = cell(0, 0);
+        assert!(hit.matches(&CellState::Hit("cruiser")));
+        assert!(!hit.matches(&CellState::Hit("destroyer")));
+        assert!(!hit.matches(&CellState::Miss));
+        assert!(!hit.matches(&CellState::Unshot));
+
+        let miss = cell(1, 1);
+        assert!(miss.matches(&CellState::Miss));
+        assert!(!miss.matches(&CellState::Hit("cruiser")));
+        assert!(!miss.matches(&CellState::Unshot));
+
+        // Unshot cells match Unshot whether or not they hold a ship.
+        for &(x, y) in &[(1, 0), (2, 2)] {
+            let unshot = cell(x, y);
+            assert!(unshot.matches(&CellState::Unshot));
+            assert!(!unshot.matches(&CellState::Miss));
+            assert!(!unshot.matches(&CellState::Hit("cruiser")));
+        }
+    }
+
+    #[test]
+    fn shot_mask_round_trip() {
+        let mut board = small_board();
+        for &(x, y) in &[(0, 0), (1, 1), (3, 3), (2, 3)] {
+            board.shoot(Coordinate::new(x, y)).unwrap();
+        }
+        let mask = board.shot_mask();
+        let shot: HashSet<_> = board
+            .target_legality()
+            .filter(|(_, status)| status.is_already_shot())
+            .map(|(coord, _)| coord)
+            .collect();
+        for i in 0..16 {
+            let set = mask[0] & (1 << i) != 0;
+            let coord = board.dimensions().un_linearize(i);
+            assert_eq!(set, shot.contains(&coord), "{:?}", coord);
+        }
+
+        let mut restored = small_board();
+        restored.apply_shot_mask(&mask).unwrap();
+        assert_eq!(restored.shot_mask(), mask);
+        assert!(restored.diff(&board).is_empty());
+
+        assert_eq!(
+            restored.apply_shot_mask(&[]),
+            Err(ShotMaskError::WrongLength {
+                expected: 1,
+                actual: 0
+            })
+        );
+    }
+
+    #[test]
+    fn shot_mask_on_masked_board() {
+        let blocked = Coordinate::new(1, 1);
+        let dim = MaskedDimensions::new(RectDimensions::new(3, 3), vec![blocked]);
+        let blocked_bit = 1 << dim.base().linearize(&blocked);
+        let mut setup = BoardSetup::new(dim);
+        setup
+            .add_ship("destroyer", Line::new(2))
+            .unwrap()
+            .place(vec![Coordinate::new(0, 0), Coordinate::new(1, 0)])
+            .unwrap();
+        let mut board = setup.start().ok().unwrap();
+        board.shoot(Coordinate::new(0, 0)).unwrap();
+        board.shoot(Coordinate::new(2, 2)).unwrap();
+        let mask = board.shot_mask();
+        assert_eq!(mask[0] & blocked_bit, 0);
+
+        // Setting the bit of the blocked cell is rejected without changing the board.
+        assert_eq!(
+            board.apply_shot_mask(&[mask[0] | blocked_bit]),
+            Err(ShotMaskError::Unaddressable(
+                blocked_bit.trailing_zeros() as usize
+            ))
+        );
+        assert_eq!(board.shot_mask(), mask);
+
+        board.reset_damage();
+        assert_eq!(board.shot_mask(), [0]);
+        board.apply_shot_mask(&mask).unwrap();
+        assert_eq!(board.shot_mask(), mask);
+        assert!(board.get_coord(Coordinate::new(0, 0)).unwrap().hit());
+    }
+
+    #[test]
+    fn setup_errors_compare_whole() {
+        let mut setup = BoardSetup::new(RectDimensions::new(4, 4));
+        setup.add_ship("cruiser", Line::new(3)).unwrap();
+        let err = setup.add_ship("cruiser", Line::new(2)).err().unwrap();
+        assert_eq!(
+            err,
+            AddShipError::new(CannotAddShipReason::AlreadyExists, "cruiser", Line::new(2))
+        );
+        assert_ne!(
+            err,
+            AddShipError::new(CannotAddShipReason::AlreadyExists, "cruiser", Line::new(3))
+        );
+        assert_eq!(
+            format!("{:?}", err),
+            "AddShipError { reason: AlreadyExists, id: \"cruiser\", .. }"
+        );
+
+        let diagonal = vec![
+            Coordinate::new(0, 0),
+            Coordinate::new(1, 1),
+            Coordinate::new(2, 2),
+        ];
+        let err = setup
+            .get_ship_mut(&"cruiser")
+            .unwrap()
+            .place(diagonal.clone())
+            .unwrap_err();
+        assert_eq!(
+            err,
+            PlaceError::new(CannotPlaceReason::InvalidProjection, diagonal)
+        );
+        assert_eq!(
+            format!(
+                "{:?}",
+                ShotError::new(CannotShootReason::AlreadyShot, Coordinate::new(1, 2))
+            ),
+            format!(
+                "ShotError {{ reason: AlreadyShot, coord: {:?} }}",
+                Coordinate::new(1, 2)
+            )
+        );
+    }
+
+    #[test]
+    fn target_legality_matches_shots() {
+        let mut board = small_board();
+        let shots = [(0, 0), (2, 1), (3, 2), (1, 3)];
+        for &(x, y) in &shots {
+            board.shoot(Coordinate::new(x, y)).unwrap();
+        }
+        let legality: Vec<_> = board.target_legality().collect();
+        assert_eq!(legality.len(), 16);
+        for (i, (coord, status)) in legality.into_iter().enumerate() {
+            assert_eq!(coord, Coordinate::new(i % 4, i / 4));
+            let expected = if shots.contains(&(coord.x, coord.y)) {
+                TargetStatus::AlreadyShot
+            } else {
+                TargetStatus::Available
+            };
+            assert_eq!(status, expected, "{:?}", coord);
+            assert_eq!(
+                status.is_already_shot(),
+                board.get_coord(coord).unwrap().hit()
+            );
+        }
+    }
+
+    /// Build a started 4x4 board with a ship of length 3 on row 0 from column 0 and a
+    /// ship of length 2 on column 1 from row 1, directly below the first.
+    fn touching_board() -> Board<&'static str, RectDimensions> {
+        let mut setup = BoardSetup::new(RectDimensions::new(4, 4));
+        let ships: [(&str, &[(usize, usize)]); 2] = [
+            ("cruiser", &[(0, 0), (1, 0), (2, 0)]),
+            ("destroyer", &[(1, 1), (1, 2)]),
+        ];
+        for &(id, coords) in &ships {
+            let cells = coords.iter().map(|&(x, y)| Coordinate::new(x, y)).collect();
+            setup
+                .add_ship(id, Line::new(coords.len()))
+                .unwrap()
+                .place(cells)
+                .unwrap();
+        }
+        setup.start().ok().unwrap()
+    }
+
+    #[test]
+    fn ships_in_region_lists_each_ship_once() {
+        let board = touching_board();
+        // Covers two cells of the cruiser and one of the destroyer.
+        let region = [
+            Coordinate::new(1, 0),
+            Coordinate::new(2, 0),
+            Coordinate::new(1, 1),
+            Coordinate::new(2, 1),
+        ];
+        let ids: Vec<_> = board.ships_in_region(&region).collect();
+        assert_eq!(ids, [&"cruiser", &"destroyer"]);
+
+        let empty = [Coordinate::new(3, 3), Coordinate::new(9, 9)];
+        assert_eq!(board.ships_in_region(&empty).count(), 0);
+    }
+
+    #[test]
+    fn standard_fleet_occupancy() {
+        let game = crate::fixtures::uniform_setup().start().ok().unwrap();
+        let board = game.get_board(&0).unwrap();
+        assert_eq!(board.occupied_cells(), 17);
+        assert_eq!(board.occupancy_ratio(), 0.17);
+        let small = small_board();
+        assert_eq!(small.occupancy_ratio(), 5.0 / 16.0);
+    }
+
+    #[test]
+    fn adjacent_ships() {
+        let board = touching_board();
+        assert_eq!(board.ships_adjacent("cruiser", "destroyer"), Some(true));
+        assert_eq!(board.ships_adjacent("destroyer", "cruiser"), Some(true));
+
+        let board = small_board();
+        assert_eq!(board.ships_adjacent("cruiser", "destroyer"), Some(false));
+        assert_eq!(board.ships_adjacent("destroyer", "cruiser"), Some(false));
+        assert_eq!(board.ships_adjacent("cruiser", "carrier"), None);
+        assert_eq!(board.ships_adjacent("carrier", "cruiser"), None);
+    }
+
+    #[test]
+    fn mixed_fleet_summaries() {
+        use crate::game::simple::Ship;
+
+        let mut game = crate::fixtures::uniform_setup().start().ok().unwrap();
+        let board = game.get_board_mut(&0).unwrap();
+        // Carrier and Cruiser damaged, Destroyer sunk, Battleship and Submarine untouched.
+        for &(x, y) in &[(0, 0), (2, 0), (1, 4), (0, 8), (1, 8), (9, 9)] {
+            board.shoot(Coordinate::new(x, y)).unwrap();
+        }
+        let rank = |ship: &Ship| Ship::ALL.iter().position(|s| s == ship).unwrap();
+
+        let mut damaged: Vec<_> = board
+            .damaged_ships()
+            .map(|(ship, hits)| (*ship.id(), hits.into_iter().cloned().collect::<Vec<_>>()))
+            .collect();
+        damaged.sort_by_key(|(id, _)| rank(id));
+        assert_eq!(
+            damaged,
+            [
+                (
+                    Ship::Carrier,
+                    vec![Coordinate::new(0, 0), Coordinate::new(2, 0)]
+                ),
+                (Ship::Cruiser, vec![Coordinate::new(1, 4)]),
+            ]
+        );
+
+        let mut summaries = board.ship_summaries();
+        summaries.sort_by_key(|summary| rank(&summary.id));
+        let states: Vec<_> = summaries
+            .iter()
+            .map(|summary| (summary.id, summary.sunk, summary.hits, summary.coords.len()))
+            .collect();
+        assert_eq!(
+            states,
+            [
+                (Ship::Carrier, false, 2, 5),
+                (Ship::Battleship, false, 0, 4),
+                (Ship::Cruiser, false, 1, 3),
+                (Ship::Submarine, false, 0, 3),
+                (Ship::Destroyer, true, 2, 2),
+            ]
+        );
+        assert_eq!(
+            summaries[4].coords,
+            [Coordinate::new(0, 8), Coordinate::new(1, 8)]
+        );
     }
 }
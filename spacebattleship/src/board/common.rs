@@ -14,6 +14,8 @@
 
 //! Common types that are useful to various types of boards.
 
-pub use coordinate2d::Coordinate2D;
+#[cfg(feature = "rng_gen")]
+pub use coordinate2d::UniformCoordinate2D;
+pub use coordinate2d::{Coordinate2D, ParseCoordinateError};
 
 mod coordinate2d;
@@ -12,6 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::{num::IntErrorKind, str::FromStr};
+
+use thiserror::Error;
+
 use crate::board::Coordinate;
 
 /// The corrdinates of a [`GridCell`][crate::board::GridCell] in the board.
@@ -46,6 +50,61 @@ impl From<Coordinate2D> for (usize, usize) {
     }
 }
 
+impl FromStr for Coordinate2D {
+    type Err = ParseCoordinateError;
+
+    /// Parse a coordinate from an `x,y` pair. The numbers may also be separated by
+    /// whitespace instead of a comma.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let split = match s.find(|c: char| c == ',' || c.is_whitespace()) {
+            Some(split) => split,
+            None => return Err(ParseCoordinateError::Malformed(s.to_owned())),
+        };
+        let (x, rest) = s.split_at(split);
+        let rest = rest.trim_start();
+        let y = rest.strip_prefix(',').unwrap_or(rest).trim_start();
+        Ok(Self::new(parse_component(x)?, parse_component(y)?))
+    }
+}
+
+/// Parse a single component of a [`Coordinate2D`].
+fn parse_component(src: &str) -> Result<usize, ParseCoordinateError> {
+    src.parse()
+        .map_err(|err: std::num::ParseIntError| match err.kind() {
+            IntErrorKind::PosOverflow => ParseCoordinateError::TooLarge(src.to_owned()),
+            _ => ParseCoordinateError::NotANumber(src.to_owned()),
+        })
+}
+
+/// Error returned when a coordinate could not be parsed.
+#[derive(Debug, Error, Clone, Eq, PartialEq)]
+pub enum ParseCoordinateError {
+    /// The input was not a pair of values.
+    #[error("expected a coordinate pair like \"x,y\", got {0:?}")]
+    Malformed(String),
+    /// One of the values was not a non-negative integer.
+    #[error("{0:?} is not a number")]
+    NotANumber(String),
+    /// One of the values was a number, but too large to fit in a coordinate.
+    #[error("{0} is too large to be a coordinate")]
+    TooLarge(String),
+    /// The coordinate was parsed, but is outside of the board it was parsed for.
+    #[error(
+        "{},{} is out of bounds for a {width}x{height} board",
+        coord.x,
+        coord.y
+    )]
+    OutOfBounds {
+        /// The coordinate that was parsed.
+        coord: Coordinate2D,
+        /// Width of the board.
+        width: usize,
+        /// Height of the board.
+        height: usize,
+    },
+}
+
 #[cfg(feature = "rng_gen")]
 pub use rand_impl::UniformCoordinate2D;
 
@@ -95,3 +154,69 @@ mod rand_impl {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::rectangular::RectDimensions;
+
+    #[test]
+    fn parses_pairs() {
+        assert_eq!("3,4".parse(), Ok(Coordinate2D::new(3, 4)));
+        assert_eq!(" 3 , 4 ".parse(), Ok(Coordinate2D::new(3, 4)));
+        assert_eq!("3 4".parse(), Ok(Coordinate2D::new(3, 4)));
+    }
+
+    #[test]
+    fn malformed() {
+        let err = "34".parse::<Coordinate2D>().unwrap_err();
+        assert_eq!(err, ParseCoordinateError::Malformed("34".to_owned()));
+        assert_eq!(
+            err.to_string(),
+            "expected a coordinate pair like \"x,y\", got \"34\""
+        );
+    }
+
+    #[test]
+    fn not_a_number() {
+        let err = "3,b".parse::<Coordinate2D>().unwrap_err();
+        assert_eq!(err, ParseCoordinateError::NotANumber("b".to_owned()));
+        assert_eq!(err.to_string(), "\"b\" is not a number");
+        let err = "-1,2".parse::<Coordinate2D>().unwrap_err();
+        assert_eq!(err, ParseCoordinateError::NotANumber("-1".to_owned()));
+        let err = "1,".parse::<Coordinate2D>().unwrap_err();
+        assert_eq!(err, ParseCoordinateError::NotANumber("".to_owned()));
+    }
+
+    #[test]
+    fn too_large() {
+        let big = "99999999999999999999999";
+        let err = format!("1,{}", big).parse::<Coordinate2D>().unwrap_err();
+        assert_eq!(err, ParseCoordinateError::TooLarge(big.to_owned()));
+        assert_eq!(
+            err.to_string(),
+            "99999999999999999999999 is too large to be a coordinate"
+        );
+    }
+
+    #[test]
+    fn out_of_bounds() {
+        let dim = RectDimensions::new(10, 8);
+        assert_eq!(dim.parse_coordinate("9,7"), Ok(Coordinate2D::new(9, 7)));
+        let err = dim.parse_coordinate("9,8").unwrap_err();
+        assert_eq!(
+            err,
+            ParseCoordinateError::OutOfBounds {
+                coord: Coordinate2D::new(9, 8),
+                width: 10,
+                height: 8,
+            }
+        );
+        assert_eq!(err.to_string(), "9,8 is out of bounds for a 10x8 board");
+        // Parse errors come through unchanged.
+        assert_eq!(
+            dim.parse_coordinate("x"),
+            Err(ParseCoordinateError::Malformed("x".to_owned()))
+        );
+    }
+}
@@ -0,0 +1,287 @@
+// Copyright 2020 Zachary Stewart
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Comparison of two boards, for finding where copies of a game disagree.
+
+use std::fmt::{self, Debug};
+
+use crate::{
+    board::{Board, Dimensions},
+    ships::{ShapeProjection, ShipId},
+};
+
+/// A cell which has been shot on only one of two compared boards.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ShotMismatch<C> {
+    /// Coordinate of the cell.
+    pub coord: C,
+    /// Whether the cell is shot on the board [`diff`][Board::diff] was called on. The
+    /// other board has the opposite.
+    pub shot_here: bool,
+}
+
+/// A ship placed differently on two compared boards.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ShipMismatch<I, C> {
+    /// ID of the ship.
+    pub id: I,
+    /// Placement on the board [`diff`][Board::diff] was called on, or `None` if the ship
+    /// isn't on that board.
+    pub here: Option<ShapeProjection<C>>,
+    /// Placement on the other board, or `None` if the ship isn't on that board.
+    pub there: Option<ShapeProjection<C>>,
+}
+
+/// Differences between two boards, from [`Board::diff`]. Displays as a compact report
+/// with one difference per line.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct BoardDiff<I, C> {
+    /// Number of cells on each board, if they differ. Only the cells both boards have are
+    /// compared.
+    pub sizes: Option<(usize, usize)>,
+    /// Cells shot on only one of the boards, in index order.
+    pub shots: Vec<ShotMismatch<C>>,
+    /// Ships placed differently or only on one board, in arbitrary order.
+    pub ships: Vec<ShipMismatch<I, C>>,
+}
+
+impl<I, C> BoardDiff<I, C> {
+    /// Returns true if no differences were found.
+    pub fn is_empty(&self) -> bool {
+        self.sizes.is_none() && self.shots.is_empty() && self.ships.is_empty()
+    }
+}
+
+impl<I: Debug, C: Debug> fmt::Display for BoardDiff<I, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "boards match");
+        }
+        let mut lines = Vec::new();
+        if let Some((here, there)) = self.sizes {
+            lines.push(format!("size: {} cells here, {} there", here, there));
+        }
+        for shot in &self.shots {
+            let (here, there) = if shot.shot_here {
+                ("shot", "unshot")
+            } else {
+                ("unshot", "shot")
+            };
+            lines.push(format!(
+                "cell {:?}: {} here, {} there",
+                shot.coord, here, there
+            ));
+        }
+        let describe = |placement: &Option<ShapeProjection<C>>| match placement {
+            Some(placement) => format!("{:?}", placement),
+            None => "missing".to_owned(),
+        };
+        for ship in &self.ships {
+            lines.push(format!(
+                "ship {:?}: {} here, {} there",
+                ship.id,
+                describe(&ship.here),
+                describe(&ship.there)
+            ));
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl<I: ShipId, D: Dimensions> Board<I, D> {
+    /// Compare this board with another copy, such as the same player's board from a game
+    /// that should be in sync with this one. Cells are compared by index, so boards with
+    /// different dimensions only have their shared indexes compared.
+    pub fn diff(&self, other: &Board<I, D>) -> BoardDiff<I, D::Coordinate> {
+        let (here, there) = (&self.grid.cells, &other.grid.cells);
+        let sizes = if here.len() != there.len() {
+            Some((here.len(), there.len()))
+        } else {
+            None
+        };
+        let shots = here
+            .iter()
+            .zip(there.iter())
+            .enumerate()
+            .filter(|(_, (here, there))| here.hit != there.hit)
+            .filter_map(|(i, (here, _))| {
+                Some(ShotMismatch {
+                    coord: self.grid.dim.try_un_linearize(i)?,
+                    shot_here: here.hit,
+                })
+            })
+            .collect();
+        let mut ships: Vec<_> = self
+            .ships
+            .iter()
+            .filter(|(id, placement)| other.ships.get(*id) != Some(placement))
+            .map(|(id, placement)| ShipMismatch {
+                id: id.clone(),
+                here: Some(placement.clone()),
+                there: other.ships.get(id).cloned(),
+            })
+            .collect();
+        ships.extend(
+            other
+                .ships
+                .iter()
+                .filter(|(id, _)| !self.ships.contains_key(*id))
+                .map(|(id, placement)| ShipMismatch {
+                    id: id.clone(),
+                    here: None,
+                    there: Some(placement.clone()),
+                }),
+        );
+        BoardDiff {
+            sizes,
+            shots,
+            ships,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        board::{
+            rectangular::{Coordinate, RectDimensions},
+            BoardSetup,
+        },
+        ships::Line,
+    };
+
+    /// Build a started board with the given dimensions and ships, each placed on the
+    /// listed cells.
+    fn board(
+        dim: RectDimensions,
+        ships: &[(&'static str, &[(usize, usize)])],
+    ) -> Board<&'static str, RectDimensions> {
+        let mut setup = BoardSetup::new(dim);
+        for &(id, cells) in ships {
+            let cells: Vec<_> = cells.iter().map(|&(x, y)| Coordinate::new(x, y)).collect();
+            setup
+                .add_ship(id, Line::new(cells.len()))
+                .unwrap()
+                .place(cells)
+                .unwrap();
+        }
+        setup.start().ok().unwrap()
+    }
+
+    /// Destroyer in the top left corner.
+    const DESTROYER: (&str, &[(usize, usize)]) = ("destroyer", &[(0, 0), (1, 0)]);
+
+    #[test]
+    fn matching_boards() {
+        let mut here = board(RectDimensions::new(4, 4), &[DESTROYER]);
+        let mut there = board(RectDimensions::new(4, 4), &[DESTROYER]);
+        here.shoot(Coordinate::new(0, 0)).unwrap();
+        there.shoot(Coordinate::new(0, 0)).unwrap();
+        let diff = here.diff(&there);
+        assert!(diff.is_empty());
+        assert_eq!(diff.to_string(), "boards match");
+    }
+
+    #[test]
+    fn shot_differences() {
+        let mut here = board(RectDimensions::new(4, 4), &[DESTROYER]);
+        let mut there = board(RectDimensions::new(4, 4), &[DESTROYER]);
+        here.shoot(Coordinate::new(2, 2)).unwrap();
+        here.shoot(Coordinate::new(0, 0)).unwrap();
+        there.shoot(Coordinate::new(1, 1)).unwrap();
+        there.shoot(Coordinate::new(0, 0)).unwrap();
+        let diff = here.diff(&there);
+        assert_eq!(
+            diff,
+            BoardDiff {
+                sizes: None,
+                shots: vec![
+                    ShotMismatch {
+                        coord: Coordinate::new(1, 1),
+                        shot_here: false,
+                    },
+                    ShotMismatch {
+                        coord: Coordinate::new(2, 2),
+                        shot_here: true,
+                    },
+                ],
+                ships: vec![],
+            }
+        );
+        assert!(there.diff(&here).shots[0].shot_here);
+    }
+
+    #[test]
+    fn ship_differences() {
+        let here = board(RectDimensions::new(4, 4), &[DESTROYER]);
+        let there = board(
+            RectDimensions::new(4, 4),
+            &[("destroyer", &[(0, 1), (1, 1)]), ("sub", &[(3, 0)])],
+        );
+        let diff = here.diff(&there);
+        assert_eq!(diff.sizes, None);
+        assert!(diff.shots.is_empty());
+        assert_eq!(
+            diff.ships,
+            [
+                ShipMismatch {
+                    id: "destroyer",
+                    here: Some(vec![Coordinate::new(0, 0), Coordinate::new(1, 0)]),
+                    there: Some(vec![Coordinate::new(0, 1), Coordinate::new(1, 1)]),
+                },
+                ShipMismatch {
+                    id: "sub",
+                    here: None,
+                    there: Some(vec![Coordinate::new(3, 0)]),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn size_difference_compares_shared_indexes() {
+        let here = board(RectDimensions::new(4, 4), &[DESTROYER]);
+        let mut there = board(RectDimensions::new(3, 3), &[DESTROYER]);
+        // Index 8 is the last cell of the 3x3 board, and (0,2) on the 4x4 board.
+        there.shoot(Coordinate::new(2, 2)).unwrap();
+        let diff = here.diff(&there);
+        assert_eq!(diff.sizes, Some((16, 9)));
+        assert_eq!(
+            diff.shots,
+            [ShotMismatch {
+                coord: Coordinate::new(0, 2),
+                shot_here: false,
+            }]
+        );
+        assert!(diff.ships.is_empty());
+    }
+
+    #[test]
+    fn report_snapshot() {
+        let mut here = board(RectDimensions::new(4, 4), &[DESTROYER]);
+        let there = board(
+            RectDimensions::new(3, 3),
+            &[("destroyer", &[(0, 1), (1, 1)])],
+        );
+        here.shoot(Coordinate::new(1, 0)).unwrap();
+        assert_eq!(
+            here.diff(&there).to_string(),
+            "size: 16 cells here, 9 there
+cell Coordinate2D { x: 1, y: 0 }: shot here, unshot there
+ship \"destroyer\": [Coordinate2D { x: 0, y: 0 }, Coordinate2D { x: 1, y: 0 }] here, \
+[Coordinate2D { x: 0, y: 1 }, Coordinate2D { x: 1, y: 1 }] there"
+        );
+    }
+}
@@ -14,6 +14,9 @@
 
 use std::{fmt::Debug, hash::Hash};
 
+#[cfg(feature = "rng_gen")]
+use rand::{seq::IteratorRandom, Rng};
+
 /// Trait for coordinates used in [`Dimensions`].
 /// Requires [`Debug`] to enable certain common panic messages on misuse.
 /// Coordinates are treated as disposable and cheaply cloneable. If you need a complex
@@ -23,6 +26,22 @@ pub trait Coordinate: Debug + Clone + Eq + Hash {}
 /// Dimensions of a board.
 /// Implements methods needed for the board to check bounds, linearize indexes, and compute
 /// neighbor cells.
+///
+/// Implementations must uphold the following contract, which boards rely on:
+///
+/// - [`total_size`][Dimensions::total_size] is an upper bound on the indexes of cells,
+///   and does not change for the lifetime of the dimensions.
+/// - [`try_linearize`][Dimensions::try_linearize] maps every valid coordinate to a
+///   distinct index less than `total_size`, and returns `None` for every other
+///   coordinate.
+/// - [`try_un_linearize`][Dimensions::try_un_linearize] is its inverse: it returns the
+///   coordinate for every index produced by `try_linearize`, and `None` for every other
+///   index. Indexes may be left unused, such as for cells which are blocked.
+/// - Neighbors of a valid coordinate are themselves valid coordinates.
+///
+/// Boards with no cells or more than [`MAX_CELLS`][crate::board::MAX_CELLS] cells are
+/// rejected by [`BoardSetup::try_new`][crate::board::BoardSetup::try_new], and in debug
+/// builds the indexing is spot-checked there.
 pub trait Dimensions: Debug {
     /// The type used to identify cells on the board.
     type Coordinate: Coordinate;
@@ -46,8 +65,42 @@ pub trait Dimensions: Debug {
     /// Returns `None` if the coordinate is out of bound for the dimension.
     fn try_linearize(&self, coord: &Self::Coordinate) -> Option<usize>;
 
+    /// Convert a linear index back to the coordinate it was computed from. This is the
+    /// inverse of [`linearize`][Dimensions::linearize].
+    /// Panics if the index is not less than [`total_size`][Dimensions::total_size].
+    fn un_linearize(&self, index: usize) -> Self::Coordinate {
+        match self.try_un_linearize(index) {
+            Some(coord) => coord,
+            None => panic!("{} is out of bounds for {:?}", index, self),
+        }
+    }
+
+    /// Convert a linear index back to the coordinate it was computed from. This is the
+    /// inverse of [`try_linearize`][Dimensions::try_linearize].
+    /// Returns `None` if the index is not less than [`total_size`][Dimensions::total_size].
+    /// There is no default, since a coordinate can't be built from an index generically,
+    /// so implementations from before this method was added need to provide it. See the
+    /// changelog.
+    fn try_un_linearize(&self, index: usize) -> Option<Self::Coordinate>;
+
+    /// Pick a coordinate uniformly at random from all the coordinates in these
+    /// dimensions. Indexes which don't correspond to a coordinate are skipped rather than
+    /// retried, so this always finishes in a single pass over the indexes. Panics if no
+    /// index corresponds to a coordinate. Dimensions which use every index should
+    /// override this to pick an index directly.
+    #[cfg(feature = "rng_gen")]
+    fn sample_coordinate<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::Coordinate {
+        match (0..self.total_size())
+            .filter_map(|i| self.try_un_linearize(i))
+            .choose(rng)
+        {
+            Some(coord) => coord,
+            None => panic!("{:?} has no coordinates", self),
+        }
+    }
+
     /// Iterate the neighbors of the given coordinate.
-    fn neighbors(&self, coord: Self::Coordinate) -> NeighborIter<Self::NeighborIterState> {
+    fn neighbors(&self, coord: Self::Coordinate) -> NeighborIter<'_, Self::NeighborIterState> {
         NeighborIter {
             dim: self,
             state: Self::NeighborIterState::start(self, coord),
@@ -72,6 +125,16 @@ pub trait ColinearCheck: Dimensions {
     ) -> bool;
 }
 
+/// Trait for [`Dimensions`] that can continue a line of cells in a consistent direction.
+/// This is an alternative to [`ColinearCheck`] for dimensions where straight lines are
+/// easier to follow than to test for.
+pub trait LinearStep: Dimensions {
+    /// Get the cell after `cur` on the line running from `prev` through `cur`, where
+    /// `prev` and `cur` are neighbors. Returns `None` if the line leaves the board or
+    /// `prev` and `cur` are not neighbors.
+    fn step(&self, prev: &Self::Coordinate, cur: &Self::Coordinate) -> Option<Self::Coordinate>;
+}
+
 /// State type for the neighbor iterator.
 pub trait NeighborIterState {
     type Dimensions: Dimensions + ?Sized;
@@ -18,21 +18,92 @@ use std::fmt::{self, Debug};
 
 use thiserror::Error;
 
-/// Error returned when trying to add a ship that already existed.
-#[derive(Error)]
-#[error("ship with id {id:?} already exists")]
+/// Reason why a ship could not be added to a board.
+#[derive(Debug, Error, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum CannotAddShipReason {
+    /// A ship with the same ID was already added.
+    #[error("a ship with that id already exists")]
+    AlreadyExists,
+    /// The board is locked and its roster can't be changed.
+    #[error("the board is locked")]
+    Locked,
+    /// The board's roster was fixed by fleet negotiation, so ships can be placed but not
+    /// added or removed.
+    #[error("the board's roster is locked")]
+    RosterLocked,
+}
+
+impl CannotAddShipReason {
+    /// Returns true if this is [`CannotAddShipReason::AlreadyExists`].
+    pub fn is_already_exists(&self) -> bool {
+        matches!(self, CannotAddShipReason::AlreadyExists)
+    }
+
+    /// Returns true if this is [`CannotAddShipReason::Locked`].
+    pub fn is_locked(&self) -> bool {
+        matches!(self, CannotAddShipReason::Locked)
+    }
+
+    /// Returns true if this is [`CannotAddShipReason::RosterLocked`].
+    pub fn is_roster_locked(&self) -> bool {
+        matches!(self, CannotAddShipReason::RosterLocked)
+    }
+}
+
+/// Reason why a ship could not be removed from a board.
+#[derive(Debug, Error, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum CannotRemoveShipReason {
+    /// No ship with the given ID was added.
+    #[error("no ship with that id exists")]
+    NotFound,
+    /// The board is locked and its roster can't be changed.
+    #[error("the board is locked")]
+    Locked,
+    /// The board's roster was fixed by fleet negotiation.
+    #[error("the board's roster is locked")]
+    RosterLocked,
+}
+
+impl CannotRemoveShipReason {
+    /// Returns true if this is [`CannotRemoveShipReason::NotFound`].
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, CannotRemoveShipReason::NotFound)
+    }
+
+    /// Returns true if this is [`CannotRemoveShipReason::Locked`].
+    pub fn is_locked(&self) -> bool {
+        matches!(self, CannotRemoveShipReason::Locked)
+    }
+
+    /// Returns true if this is [`CannotRemoveShipReason::RosterLocked`].
+    pub fn is_roster_locked(&self) -> bool {
+        matches!(self, CannotRemoveShipReason::RosterLocked)
+    }
+}
+
+/// Error returned when trying to add a ship that already existed or to a locked board.
+#[derive(Error, Clone, Eq, PartialEq)]
+#[error("could not add ship with id {id:?}: {reason}")]
 pub struct AddShipError<I: Debug, S> {
+    /// Reason the ship could not be added.
+    reason: CannotAddShipReason,
     /// ID of the ship that was attempted to be added.
     id: I,
-    /// The shape that was not added because another shape with the same ID already
-    /// existed.
+    /// The shape that was not added.
     shape: S,
 }
 
 impl<I: Debug, S> AddShipError<I, S> {
     /// Create an [`AddShipError`] for the ship with the given ID and shape.
-    pub(super) fn new(id: I, shape: S) -> Self {
-        Self { id, shape }
+    pub(super) fn new(reason: CannotAddShipReason, id: I, shape: S) -> Self {
+        Self { reason, id, shape }
+    }
+
+    /// Get the reason the ship could not be added.
+    pub fn reason(&self) -> CannotAddShipReason {
+        self.reason
     }
 
     /// The id that was added.
@@ -49,6 +120,13 @@ impl<I: Debug, S> AddShipError<I, S> {
     pub fn into_inner(self) -> (I, S) {
         (self.id, self.shape)
     }
+
+    /// Get the shape paired with a new ID, to try adding it again. Only useful if the
+    /// reason is [`CannotAddShipReason::AlreadyExists`], since a locked board rejects any
+    /// ID.
+    pub fn retry_with_id(self, new_id: I) -> (I, S) {
+        (new_id, self.shape)
+    }
 }
 
 impl<I: Debug, S> From<AddShipError<I, S>> for (I, S) {
@@ -60,12 +138,73 @@ impl<I: Debug, S> From<AddShipError<I, S>> for (I, S) {
 
 impl<I: Debug, S> Debug for AddShipError<I, S> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        fmt::Display::fmt(self, f)
+        // Shapes aren't required to implement Debug, so the shape is left out.
+        f.debug_struct("AddShipError")
+            .field("reason", &self.reason)
+            .field("id", &self.id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Reason why a board could not be created with the given dimensions.
+#[derive(Debug, Error, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum CannotCreateBoardReason {
+    /// The dimensions have no cells.
+    #[error("the dimensions have no cells")]
+    Empty,
+    /// The dimensions have more than [`MAX_CELLS`][crate::board::MAX_CELLS] cells.
+    #[error("the dimensions have too many cells")]
+    TooLarge,
+}
+
+impl CannotCreateBoardReason {
+    /// Returns true if this is [`CannotCreateBoardReason::Empty`].
+    pub fn is_empty(&self) -> bool {
+        matches!(self, CannotCreateBoardReason::Empty)
+    }
+
+    /// Returns true if this is [`CannotCreateBoardReason::TooLarge`].
+    pub fn is_too_large(&self) -> bool {
+        matches!(self, CannotCreateBoardReason::TooLarge)
+    }
+}
+
+/// Error returned when trying to create a board with dimensions that can't be used.
+#[derive(Debug, Error, Clone, Eq, PartialEq)]
+#[error("could not create board with dimensions {dim:?}: {reason}")]
+pub struct BoardCreationError<D: Debug> {
+    /// Reason the board could not be created.
+    reason: CannotCreateBoardReason,
+    /// The dimensions that were rejected.
+    dim: D,
+}
+
+impl<D: Debug> BoardCreationError<D> {
+    /// Create a [`BoardCreationError`] for the given dimensions.
+    pub(super) fn new(reason: CannotCreateBoardReason, dim: D) -> Self {
+        Self { reason, dim }
+    }
+
+    /// Get the reason the board could not be created.
+    pub fn reason(&self) -> CannotCreateBoardReason {
+        self.reason
+    }
+
+    /// The dimensions that were rejected.
+    pub fn dimensions(&self) -> &D {
+        &self.dim
+    }
+
+    /// Extract the rejected dimensions from this error.
+    pub fn into_inner(self) -> D {
+        self.dim
     }
 }
 
 /// Reason why a ship could not be placed with a given projection.
 #[derive(Debug, Error, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum CannotPlaceReason {
     /// The given ShipId was already placed.
     #[error("ship was already placed")]
@@ -76,24 +215,43 @@ pub enum CannotPlaceReason {
     /// One or more of the cells in the projection was already occupied.
     #[error("the requested position was already occupied")]
     AlreadyOccupied,
+    /// The board is locked, so placements can't be changed.
+    #[error("the board is locked")]
+    Locked,
+}
+
+impl CannotPlaceReason {
+    /// Returns true if this is [`CannotPlaceReason::AlreadyPlaced`].
+    pub fn is_already_placed(&self) -> bool {
+        matches!(self, CannotPlaceReason::AlreadyPlaced)
+    }
+
+    /// Returns true if this is [`CannotPlaceReason::InvalidProjection`].
+    pub fn is_invalid_projection(&self) -> bool {
+        matches!(self, CannotPlaceReason::InvalidProjection)
+    }
+
+    /// Returns true if this is [`CannotPlaceReason::AlreadyOccupied`].
+    pub fn is_already_occupied(&self) -> bool {
+        matches!(self, CannotPlaceReason::AlreadyOccupied)
+    }
+
+    /// Returns true if this is [`CannotPlaceReason::Locked`].
+    pub fn is_locked(&self) -> bool {
+        matches!(self, CannotPlaceReason::Locked)
+    }
 }
 
 /// Error caused when attempting to place a ship in an invalid position.
-#[derive(Error)]
+#[derive(Debug, Error, Clone, Eq, PartialEq)]
 #[error("could not place ship: {reason:?}")]
-pub struct PlaceError<P> {
+pub struct PlaceError<P: Debug> {
     #[source]
     reason: CannotPlaceReason,
     placement: P,
 }
 
-impl<P> Debug for PlaceError<P> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        fmt::Display::fmt(self, f)
-    }
-}
-
-impl<P> PlaceError<P> {
+impl<P: Debug> PlaceError<P> {
     /// Construct a placement error from a reason, ID, and placement.
     pub(super) fn new(reason: CannotPlaceReason, placement: P) -> Self {
         Self { reason, placement }
@@ -117,6 +275,7 @@ impl<P> PlaceError<P> {
 
 /// Reason why a particular tile could not be shot.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum CannotShootReason {
     /// The player being attacked was already defeated.
     AlreadyDefeated,
@@ -128,8 +287,25 @@ pub enum CannotShootReason {
     AlreadyShot,
 }
 
+impl CannotShootReason {
+    /// Returns true if this is [`CannotShootReason::AlreadyDefeated`].
+    pub fn is_already_defeated(&self) -> bool {
+        matches!(self, CannotShootReason::AlreadyDefeated)
+    }
+
+    /// Returns true if this is [`CannotShootReason::OutOfBounds`].
+    pub fn is_out_of_bounds(&self) -> bool {
+        matches!(self, CannotShootReason::OutOfBounds)
+    }
+
+    /// Returns true if this is [`CannotShootReason::AlreadyShot`].
+    pub fn is_already_shot(&self) -> bool {
+        matches!(self, CannotShootReason::AlreadyShot)
+    }
+}
+
 /// Error returned when trying to shoot a cell.
-#[derive(Debug, Error)]
+#[derive(Debug, Error, Clone, Eq, PartialEq)]
 #[error("could not shoot cell {coord:?}: {reason:?}")]
 pub struct ShotError<C: Debug> {
     /// Reason why the cell could not be shot.
@@ -160,3 +336,33 @@ impl<C: Debug> ShotError<C> {
         self.coord
     }
 }
+
+/// Error returned when a shot mask can't be applied to a board.
+#[derive(Debug, Error, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ShotMaskError {
+    /// The mask doesn't have the number of words the board requires.
+    #[error("shot mask has {actual} words but the board requires {expected}")]
+    WrongLength {
+        /// Number of words required by the board.
+        expected: usize,
+        /// Number of words in the mask.
+        actual: usize,
+    },
+    /// The mask marks as shot a linear index which the dimensions don't use, such as a
+    /// blocked cell of [`MaskedDimensions`][crate::board::masked::MaskedDimensions].
+    #[error("shot mask sets bit {0}, which is not a cell of the board")]
+    Unaddressable(usize),
+}
+
+impl ShotMaskError {
+    /// Returns true if this is [`ShotMaskError::WrongLength`].
+    pub fn is_wrong_length(&self) -> bool {
+        matches!(self, ShotMaskError::WrongLength { .. })
+    }
+
+    /// Returns true if this is [`ShotMaskError::Unaddressable`].
+    pub fn is_unaddressable(&self) -> bool {
+        matches!(self, ShotMaskError::Unaddressable(_))
+    }
+}
@@ -20,7 +20,7 @@ use std::{
     ops::{Index, IndexMut},
 };
 
-use crate::board::Dimensions;
+use crate::board::{BoardCreationError, CannotCreateBoardReason, Dimensions, MAX_CELLS};
 
 /// A single cell in the player's grid.
 #[derive(Debug)]
@@ -51,9 +51,28 @@ pub(super) struct Grid<I, D> {
 }
 
 impl<I, D: Dimensions> Grid<I, D> {
-    pub(super) fn new(dim: D) -> Self {
-        let cells = (0..dim.total_size()).map(|_| Default::default()).collect();
-        Self { dim, cells }
+    /// Allocate a grid for the given dimensions, rejecting dimensions which are empty or
+    /// too large. In debug builds, also spot-checks that the dimensions' indexing
+    /// round-trips, panicking if it doesn't.
+    pub(super) fn try_new(dim: D) -> Result<Self, BoardCreationError<D>> {
+        let size = dim.total_size();
+        if size == 0 {
+            return Err(BoardCreationError::new(CannotCreateBoardReason::Empty, dim));
+        }
+        if size > MAX_CELLS {
+            return Err(BoardCreationError::new(
+                CannotCreateBoardReason::TooLarge,
+                dim,
+            ));
+        }
+        #[cfg(debug_assertions)]
+        check_indexing(&dim, size);
+        let mut cells = Vec::with_capacity(size);
+        cells.resize_with(size, Default::default);
+        Ok(Self {
+            dim,
+            cells: cells.into_boxed_slice(),
+        })
     }
 
     /// Get a reference to the cell at the given [`Coordinate`].
@@ -74,6 +93,84 @@ impl<I, D: Dimensions> Grid<I, D> {
     }
 }
 
+/// Linear indexes of the cells which haven't been shot, in no particular order, so that
+/// a random one can be picked without scanning the grid.
+#[derive(Debug)]
+pub(super) struct UnshotCells {
+    /// Linear indexes of the un-shot cells.
+    indexes: Vec<usize>,
+    /// Position of each linear index in `indexes`, or `None` if the cell has been shot or
+    /// the index isn't used by the dimensions.
+    positions: Box<[Option<usize>]>,
+}
+
+impl UnshotCells {
+    /// Collect the un-shot cells of the given grid.
+    pub(super) fn new<I, D: Dimensions>(grid: &Grid<I, D>) -> Self {
+        let mut positions = vec![None; grid.cells.len()].into_boxed_slice();
+        let mut indexes = Vec::with_capacity(grid.cells.len());
+        for (i, cell) in grid.cells.iter().enumerate() {
+            if !cell.hit && grid.dim.try_un_linearize(i).is_some() {
+                positions[i] = Some(indexes.len());
+                indexes.push(i);
+            }
+        }
+        Self { indexes, positions }
+    }
+
+    /// Linear indexes of the un-shot cells.
+    #[cfg(feature = "rng_gen")]
+    pub(super) fn indexes(&self) -> &[usize] {
+        &self.indexes
+    }
+
+    /// Update the list after the cell with the given linear index was shot or un-shot.
+    /// The index must be used by the dimensions.
+    pub(super) fn set_hit(&mut self, index: usize, hit: bool) {
+        match (hit, self.positions[index]) {
+            (true, Some(pos)) => {
+                self.indexes.swap_remove(pos);
+                if let Some(&moved) = self.indexes.get(pos) {
+                    self.positions[moved] = Some(pos);
+                }
+                self.positions[index] = None;
+            }
+            (false, None) => {
+                self.positions[index] = Some(self.indexes.len());
+                self.indexes.push(index);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Check that `try_linearize` is the inverse of `try_un_linearize` for a sample of up to
+/// 64 evenly spaced indexes, including the first and last, and that the index just past
+/// the end is out of bounds.
+#[cfg(debug_assertions)]
+fn check_indexing<D: Dimensions>(dim: &D, size: usize) {
+    let stride = (size / 64).max(1);
+    for index in (0..size).step_by(stride).chain(Some(size - 1)) {
+        // Indexes may be left unused, for example by blocked cells.
+        let coord = match dim.try_un_linearize(index) {
+            Some(coord) => coord,
+            None => continue,
+        };
+        assert_eq!(
+            dim.try_linearize(&coord),
+            Some(index),
+            "{:?} does not linearize {:?} back to its index",
+            dim,
+            coord
+        );
+    }
+    assert!(
+        dim.try_un_linearize(size).is_none(),
+        "{:?} has a coordinate past its total size",
+        dim
+    );
+}
+
 impl<I, D: Dimensions, B: Borrow<D::Coordinate>> Index<B> for Grid<I, D> {
     type Output = GridCell<I>;
 
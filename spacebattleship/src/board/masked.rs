@@ -0,0 +1,239 @@
+// Copyright 2020 Zachary Stewart
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implements dimensions with some cells blocked off, such as land masses on a map.
+use std::collections::HashSet;
+
+use crate::board::{ColinearCheck, Dimensions, LinearStep, NeighborIterState};
+
+/// Wraps another [`Dimensions`], blocking off some of its cells. Blocked cells are treated
+/// as out of bounds: they can't be linearized, they are never neighbors of any cell, so
+/// ships can't be placed on them, and shooting them fails. Linear indexes are those of
+/// the base dimensions, so blocked cells still take up space in the board's storage.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MaskedDimensions<D: Dimensions> {
+    /// Dimensions being masked.
+    base: D,
+    /// Cells of the base dimensions which are blocked.
+    blocked: HashSet<D::Coordinate>,
+}
+
+impl<D: Dimensions> MaskedDimensions<D> {
+    /// Construct [`MaskedDimensions`] from the base dimensions and the cells to block.
+    /// Cells which are out of bounds for the base dimensions are ignored.
+    pub fn new(base: D, blocked: impl IntoIterator<Item = D::Coordinate>) -> Self {
+        let blocked = blocked
+            .into_iter()
+            .filter(|coord| base.try_linearize(coord).is_some())
+            .collect();
+        Self { base, blocked }
+    }
+
+    /// Get the base dimensions.
+    pub fn base(&self) -> &D {
+        &self.base
+    }
+
+    /// Returns true if the given cell is blocked.
+    pub fn is_blocked(&self, coord: &D::Coordinate) -> bool {
+        self.blocked.contains(coord)
+    }
+
+    /// Get an iterator over the blocked cells, in no particular order.
+    pub fn iter_blocked(&self) -> impl Iterator<Item = &D::Coordinate> {
+        self.blocked.iter()
+    }
+}
+
+impl<D: Dimensions> Dimensions for MaskedDimensions<D> {
+    type Coordinate = D::Coordinate;
+
+    type NeighborIterState = MaskedNeighbors<D>;
+
+    /// Compute the total size of the base dimensions, including blocked cells.
+    fn total_size(&self) -> usize {
+        self.base.total_size()
+    }
+
+    /// Convert a coordinate to a linear index in the base dimensions. Returns `None` if
+    /// the coordinate is blocked or out of range.
+    fn try_linearize(&self, coord: &Self::Coordinate) -> Option<usize> {
+        if self.is_blocked(coord) {
+            None
+        } else {
+            self.base.try_linearize(coord)
+        }
+    }
+
+    /// Convert a linear index back to a coordinate. Returns `None` if the coordinate is
+    /// blocked or the index is out of range.
+    fn try_un_linearize(&self, index: usize) -> Option<Self::Coordinate> {
+        self.base
+            .try_un_linearize(index)
+            .filter(|coord| !self.is_blocked(coord))
+    }
+}
+
+impl<D: ColinearCheck> ColinearCheck for MaskedDimensions<D> {
+    fn is_colinear(
+        &self,
+        c1: &Self::Coordinate,
+        c2: &Self::Coordinate,
+        c3: &Self::Coordinate,
+    ) -> bool {
+        self.base.is_colinear(c1, c2, c3)
+    }
+}
+
+impl<D: LinearStep> LinearStep for MaskedDimensions<D> {
+    fn step(&self, prev: &Self::Coordinate, cur: &Self::Coordinate) -> Option<Self::Coordinate> {
+        if self.is_blocked(prev) || self.is_blocked(cur) {
+            None
+        } else {
+            self.base
+                .step(prev, cur)
+                .filter(|next| !self.is_blocked(next))
+        }
+    }
+}
+
+/// State of the neighbors iter for [`MaskedDimensions`]. Wraps the base dimensions'
+/// neighbor iter, skipping blocked cells.
+pub struct MaskedNeighbors<D: Dimensions> {
+    /// State of the base neighbor iter, or `None` if the start cell is blocked.
+    inner: Option<D::NeighborIterState>,
+}
+
+impl<D: Dimensions> NeighborIterState for MaskedNeighbors<D> {
+    type Dimensions = MaskedDimensions<D>;
+
+    fn start(dim: &MaskedDimensions<D>, coord: D::Coordinate) -> Self {
+        Self {
+            inner: if dim.is_blocked(&coord) {
+                None
+            } else {
+                Some(D::NeighborIterState::start(&dim.base, coord))
+            },
+        }
+    }
+
+    fn next(&mut self, dim: &MaskedDimensions<D>) -> Option<D::Coordinate> {
+        let inner = self.inner.as_mut()?;
+        loop {
+            match inner.next(&dim.base) {
+                Some(coord) if dim.is_blocked(&coord) => {}
+                next => return next,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        board::{
+            rectangular::{Coordinate, RectDimensions},
+            BoardSetup, CannotPlaceReason,
+        },
+        ships::Line,
+    };
+
+    /// Build 3x3 dimensions with the center cell blocked.
+    fn ring() -> MaskedDimensions<RectDimensions> {
+        MaskedDimensions::new(RectDimensions::new(3, 3), vec![Coordinate::new(1, 1)])
+    }
+
+    #[test]
+    fn blocked_cells_are_out_of_bounds() {
+        let dim = ring();
+        let center = Coordinate::new(1, 1);
+        let center_index = dim.base().linearize(&center);
+        assert_eq!(dim.try_linearize(&center), None);
+        assert_eq!(dim.try_un_linearize(center_index), None);
+        assert_eq!(dim.try_un_linearize(9), None);
+        for i in (0..9).filter(|&i| i != center_index) {
+            let coord = dim.try_un_linearize(i).unwrap();
+            assert_eq!(dim.try_linearize(&coord), Some(i));
+        }
+
+        let mut neighbors: Vec<_> = dim.neighbors(Coordinate::new(1, 0)).collect();
+        neighbors.sort_by_key(|c| (c.x, c.y));
+        assert_eq!(neighbors, [Coordinate::new(0, 0), Coordinate::new(2, 0)]);
+        assert_eq!(dim.neighbors(center).count(), 0);
+        assert_eq!(dim.step(&Coordinate::new(1, 0), &center), None);
+        assert_eq!(
+            dim.step(&Coordinate::new(0, 1), &Coordinate::new(0, 0)),
+            None
+        );
+    }
+
+    #[test]
+    fn line_routes_around_blocked_cell() {
+        let base = *ring().base();
+        let mut setup = BoardSetup::<_, _, Line>::new(ring());
+        let mut ship = setup.add_ship("cruiser", Line::new(3)).unwrap();
+        let placements: Vec<_> = (0..9)
+            .map(|i| base.un_linearize(i))
+            .flat_map(|coord| ship.get_placements(coord).collect::<Vec<_>>())
+            .collect();
+        // Only the four edges of the ring fit, each from either end.
+        assert_eq!(placements.len(), 8);
+        for placement in &placements {
+            assert!(placement.iter().all(|c| *c != Coordinate::new(1, 1)));
+            assert_eq!(ship.check_placement(placement), Ok(()));
+        }
+
+        let through_center = vec![
+            Coordinate::new(0, 1),
+            Coordinate::new(1, 1),
+            Coordinate::new(2, 1),
+        ];
+        assert_eq!(
+            ship.place(through_center).unwrap_err().reason(),
+            CannotPlaceReason::InvalidProjection
+        );
+        ship.place(placements[0].clone()).unwrap();
+    }
+
+    #[cfg(feature = "rng_gen")]
+    #[test]
+    fn sample_coordinate_skips_blocked() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let blocked = vec![Coordinate::new(0, 0), Coordinate::new(2, 1)];
+        let dim = MaskedDimensions::new(RectDimensions::new(3, 2), blocked);
+        let mut rng = StdRng::seed_from_u64(1446);
+        let mut counts = [0; 6];
+        for _ in 0..4000 {
+            counts[dim.linearize(&dim.sample_coordinate(&mut rng))] += 1;
+        }
+        assert_eq!(counts[0], 0);
+        assert_eq!(counts[5], 0);
+        for &count in &counts[1..5] {
+            assert!(count > 850 && count < 1150, "{:?}", counts);
+        }
+    }
+
+    #[cfg(feature = "rng_gen")]
+    #[test]
+    fn sample_coordinate_one_unblocked() {
+        let blocked = (0..3).map(|x| Coordinate::new(x, 0)).skip(1);
+        let dim = MaskedDimensions::new(RectDimensions::new(3, 1), blocked);
+        let mut rng = rand::thread_rng();
+        for _ in 0..10 {
+            assert_eq!(dim.sample_coordinate(&mut rng), Coordinate::new(0, 0));
+        }
+    }
+}
@@ -13,13 +13,18 @@
 // limitations under the License.
 
 //! Implements a basic rectangular board.
-use std::borrow::Borrow;
+use std::{borrow::Borrow, fmt};
 
 use enumflags2::BitFlags;
+#[cfg(feature = "rng_gen")]
+use rand::Rng;
 
-use crate::board::{ColinearCheck, Dimensions, NeighborIterState};
+use crate::{
+    board::{Board, CellRef, ColinearCheck, Dimensions, LinearStep, NeighborIterState},
+    ships::ShipId,
+};
 
-pub use crate::board::common::Coordinate2D as Coordinate;
+pub use crate::board::common::{Coordinate2D as Coordinate, ParseCoordinateError};
 
 /// Controls which dimensions the grid wraps around in.
 #[derive(BitFlags, Debug, Copy, Clone, Eq, PartialEq)]
@@ -45,14 +50,14 @@ pub struct RectDimensions {
 
 impl RectDimensions {
     /// Create new [`RectDimensions`] with the specified width and height. Defaults to no wrapping.
-    /// Panics if `width * height` exceeds `usize::max_value()` or if `width` or `height` is 0.
+    /// Panics if `width * height` exceeds `usize::MAX` or if `width` or `height` is 0.
     pub fn new(width: usize, height: usize) -> Self {
         Self::new_wrapping(width, height, BitFlags::empty())
     }
 
     /// Create new [`RectDimensions`] with the specified width and height, wrapping on the
     /// specified axes.
-    /// Panics if `width * height` exceeds `usize::max_value()` or if `width` or `height` is 0.
+    /// Panics if `width * height` exceeds `usize::MAX` or if `width` or `height` is 0.
     pub fn new_wrapping<B: Into<BitFlags<Wrapping>>>(
         width: usize,
         height: usize,
@@ -68,7 +73,7 @@ impl RectDimensions {
                         "RectDimesnsions too large: {} * {} > {}",
                         width,
                         height,
-                        usize::max_value()
+                        usize::MAX
                     );
                 }
             }
@@ -76,14 +81,14 @@ impl RectDimensions {
     }
 
     /// Create new [`RectDimensions`] with the specified width and height. Defaults to no wrapping.
-    /// Returns `None` if `width * height` exceeds `usize::max_value()` or if `width` or `height`
+    /// Returns `None` if `width * height` exceeds `usize::MAX` or if `width` or `height`
     /// is 0.
     pub fn try_new(width: usize, height: usize) -> Option<Self> {
         Self::try_new_wrapping(width, height, BitFlags::empty())
     }
 
     /// Create new [`RectDimensions`] with the specified width and height.
-    /// Returns `None` if `width * height` exceeds `usize::max_value()` or if `width` or `height`
+    /// Returns `None` if `width * height` exceeds `usize::MAX` or if `width` or `height`
     /// is 0.
     pub fn try_new_wrapping<B: Into<BitFlags<Wrapping>>>(
         width: usize,
@@ -126,6 +131,21 @@ impl RectDimensions {
         self.wrapping.contains(Wrapping::Vertical)
     }
 
+    /// Parse a [`Coordinate`] from an `x,y` pair, checking that it is within these
+    /// dimensions.
+    pub fn parse_coordinate(&self, src: &str) -> Result<Coordinate, ParseCoordinateError> {
+        let coord: Coordinate = src.parse()?;
+        if coord.x < self.width && coord.y < self.height {
+            Ok(coord)
+        } else {
+            Err(ParseCoordinateError::OutOfBounds {
+                coord,
+                width: self.width,
+                height: self.height,
+            })
+        }
+    }
+
     /// Get an iterator over rows of this grid. Each row is an iterator over the coordinates of
     /// that row.
     pub fn iter_coordinates(&self) -> impl Iterator<Item = impl Iterator<Item = Coordinate>> {
@@ -162,6 +182,23 @@ impl Dimensions for RectDimensions {
         self.check_bounds(coord)
             .map(|coord| coord.y * self.width + coord.x)
     }
+
+    /// Convert a linear index back to a coordinate within this dimension.
+    /// Returns `None` if the index is out of range for the dimension.
+    fn try_un_linearize(&self, index: usize) -> Option<Self::Coordinate> {
+        if index < self.total_size() {
+            Some(Coordinate::new(index % self.width, index / self.width))
+        } else {
+            None
+        }
+    }
+
+    /// Pick a coordinate uniformly at random. Every index is used, so the index is picked
+    /// directly.
+    #[cfg(feature = "rng_gen")]
+    fn sample_coordinate<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::Coordinate {
+        self.un_linearize(rng.gen_range(0, self.total_size()))
+    }
 }
 
 impl ColinearCheck for RectDimensions {
@@ -173,6 +210,49 @@ impl ColinearCheck for RectDimensions {
     }
 }
 
+impl LinearStep for RectDimensions {
+    fn step(&self, prev: &Coordinate, cur: &Coordinate) -> Option<Coordinate> {
+        if !self.is_neighbor(prev, cur) {
+            None
+        } else if prev.y == cur.y {
+            step_axis(prev.x, cur.x, self.width, self.wrap_x()).map(|x| Coordinate::new(x, cur.y))
+        } else {
+            step_axis(prev.y, cur.y, self.height, self.wrap_y()).map(|y| Coordinate::new(cur.x, y))
+        }
+    }
+}
+
+impl<I: ShipId> Board<I, RectDimensions> {
+    /// Render this board to the given writer, one row per line, using `render` to pick
+    /// the character for each cell. Every row, including the last, ends with a newline.
+    pub fn write_grid<W: fmt::Write>(
+        &self,
+        w: &mut W,
+        render: impl Fn(CellRef<'_, I, RectDimensions>) -> char,
+    ) -> fmt::Result {
+        for row in self.dimensions().iter_coordinates() {
+            for coord in row {
+                w.write_char(render(self.get_coord(coord).unwrap()))?;
+            }
+            w.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+/// Continue a step from `prev` to `cur` along a single axis of the given size.
+fn step_axis(prev: usize, cur: usize, size: usize, wrap: bool) -> Option<usize> {
+    if wrap {
+        // Neighbors on a wrapping axis differ by either 1 or size - 1 modulo size.
+        let delta = (cur + size - prev) % size;
+        Some((cur + delta) % size)
+    } else if cur > prev {
+        Some(cur + 1).filter(|&next| next < size)
+    } else {
+        cur.checked_sub(1)
+    }
+}
+
 impl Default for RectDimensions {
     /// Construct the default rectangular dimensions, a 10x10 board with no wrapping.
     fn default() -> Self {
@@ -257,3 +337,50 @@ impl NeighborIterState for RectNeighbors {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{board::BoardSetup, ships::Line};
+
+    #[test]
+    fn write_grid_to_string() {
+        let mut setup = BoardSetup::new(RectDimensions::new(3, 2));
+        let cells = vec![Coordinate::new(1, 0), Coordinate::new(1, 1)];
+        setup
+            .add_ship('d', Line::new(2))
+            .unwrap()
+            .place(cells)
+            .unwrap();
+        let mut board = setup.start().ok().unwrap();
+        board.shoot(Coordinate::new(1, 1)).unwrap();
+        board.shoot(Coordinate::new(2, 0)).unwrap();
+
+        let mut out = String::new();
+        board
+            .write_grid(&mut out, |cell| match (cell.hit(), cell.ship()) {
+                (true, Some(_)) => 'X',
+                (true, None) => 'o',
+                (false, Some(ship)) => *ship.id(),
+                (false, None) => '.',
+            })
+            .unwrap();
+        assert_eq!(out, ".do\n.X.\n");
+    }
+
+    #[cfg(feature = "rng_gen")]
+    #[test]
+    fn sample_coordinate_is_uniform() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let dim = RectDimensions::new(3, 2);
+        let mut rng = StdRng::seed_from_u64(1446);
+        let mut counts = [0; 6];
+        for _ in 0..6000 {
+            counts[dim.linearize(&dim.sample_coordinate(&mut rng))] += 1;
+        }
+        for &count in &counts {
+            assert!(count > 850 && count < 1150, "{:?}", counts);
+        }
+    }
+}
@@ -13,10 +13,17 @@
 // limitations under the License.
 
 //! Implements the setup phase of the board.
-use std::collections::{hash_map::Entry, HashMap};
+use std::{
+    borrow::Borrow,
+    collections::{hash_map::Entry, HashMap},
+    hash::Hash,
+};
 
 use crate::{
-    board::{AddShipError, Board, CannotPlaceReason, Dimensions, Grid, PlaceError},
+    board::{
+        AddShipError, Board, BoardCreationError, CannotAddShipReason, CannotPlaceReason,
+        CannotRemoveShipReason, Dimensions, Grid, PlaceError, UnshotCells,
+    },
     ships::{ProjectIter, ShapeProjection, ShipId, ShipShape},
 };
 
@@ -29,6 +36,8 @@ pub struct ShipEntry<'a, I, D: Dimensions, S> {
     grid: &'a Grid<I, D>,
     /// Placement info for the ship.
     ship: &'a ShipPlacementInfo<S, D::Coordinate>,
+    /// Whether the board is locked against changes.
+    locked: bool,
 }
 
 impl<'a, I: ShipId, D: Dimensions, S: ShipShape<D>> ShipEntry<'a, I, D, S> {
@@ -53,6 +62,9 @@ pub struct ShipEntryMut<'a, I, D: Dimensions, S> {
 
     /// Back ref to the ship.
     ship: &'a mut ShipPlacementInfo<S, D::Coordinate>,
+
+    /// Whether the board is locked against changes.
+    locked: bool,
 }
 
 /// Implementation of the shared parts of ShipEntry.
@@ -69,6 +81,18 @@ macro_rules! ship_entry_shared {
                 self.ship.placement.is_some()
             }
 
+            /// Returns true if the board is locked, so this ship can't be placed or
+            /// unplaced.
+            pub fn locked(&self) -> bool {
+                self.locked
+            }
+
+            /// Get the shape of this ship, for example to compute alternative placements
+            /// when moving it.
+            pub fn shape(&self) -> &S {
+                &self.ship.shape
+            }
+
             /// Get an interator over possible projections of the shape for this ship that
             /// start from the given [`Coordinate`]. If there are no possible placements
             /// from the given coordinate, including if the coordinate is out of bounds,
@@ -76,7 +100,7 @@ macro_rules! ship_entry_shared {
             pub fn get_placements(
                 &self,
                 coord: D::Coordinate,
-            ) -> ProjectIter<D, S::ProjectIterState> {
+            ) -> ProjectIter<'_, D, S::ProjectIterState> {
                 self.ship.shape.project(coord, &self.grid.dim)
             }
 
@@ -85,7 +109,9 @@ macro_rules! ship_entry_shared {
                 &self,
                 placement: &ShapeProjection<D::Coordinate>,
             ) -> Result<(), CannotPlaceReason> {
-                if self.placed() {
+                if self.locked {
+                    Err(CannotPlaceReason::Locked)
+                } else if self.placed() {
                     Err(CannotPlaceReason::AlreadyPlaced)
                 } else if !self
                     .ship
@@ -129,7 +155,9 @@ impl<'a, I: ShipId, D: Dimensions, S: ShipShape<D>> ShipEntryMut<'a, I, D, S> {
         &mut self,
         placement: ShapeProjection<D::Coordinate>,
     ) -> Result<(), PlaceError<ShapeProjection<D::Coordinate>>> {
-        if self.placed() {
+        if self.locked {
+            Err(PlaceError::new(CannotPlaceReason::Locked, placement))
+        } else if self.placed() {
             Err(PlaceError::new(CannotPlaceReason::AlreadyPlaced, placement))
         } else if !self
             .ship
@@ -164,21 +192,29 @@ impl<'a, I: ShipId, D: Dimensions, S: ShipShape<D>> ShipEntryMut<'a, I, D, S> {
             for coord in placement.iter() {
                 self.grid[coord].ship = Some(self.id.to_owned());
             }
+            #[cfg(feature = "tracing")]
+            tracing::debug!(ship = ?self.id, ?placement, "placed ship");
             self.ship.placement = Some(placement);
             Ok(())
         }
     }
 
     /// Attempt to clear the placement of the ship. Returns the previous placement of the
-    /// ship if any. Returns `None` if the ship has not been placed.
-    pub fn unplace(&mut self) -> Option<ShapeProjection<D::Coordinate>> {
-        self.ship.placement.take().map(|placement| {
+    /// ship, or `None` if it had not been placed. Fails with
+    /// [`CannotPlaceReason::Locked`] if the board is locked, like
+    /// [`place`][ShipEntryMut::place].
+    pub fn unplace(&mut self) -> Result<Option<ShapeProjection<D::Coordinate>>, CannotPlaceReason> {
+        if self.locked {
+            return Err(CannotPlaceReason::Locked);
+        }
+        Ok(self.ship.placement.take().inspect(|placement| {
             for coord in placement.iter() {
                 // We should only allow placement on valid cells, so unwrap is fine.
                 self.grid[coord].ship = None;
             }
-            placement
-        })
+            #[cfg(feature = "tracing")]
+            tracing::debug!(ship = ?self.id, ?placement, "unplaced ship");
+        }))
     }
 }
 
@@ -198,17 +234,99 @@ pub struct BoardSetup<I: ShipId, D: Dimensions, S: ShipShape<D>> {
 
     /// Mapping of added ShipIds to coresponding placement info.
     ships: HashMap<I, ShipPlacementInfo<S, D::Coordinate>>,
+
+    /// Whether ships can be added, placed, or unplaced.
+    locked: bool,
+
+    /// Whether ships can be added or removed. Placement is unaffected.
+    roster_locked: bool,
 }
 
 impl<I: ShipId, D: Dimensions, S: ShipShape<D>> BoardSetup<I, D, S> {
     /// Begin game setup by constructing a new board with the given [`Dimensions`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the dimensions are rejected by [`try_new`][BoardSetup::try_new]: if they
+    /// have no cells or more than [`MAX_CELLS`][crate::board::MAX_CELLS] cells, or, in
+    /// debug builds, if they break the [`Dimensions`] indexing contract. Use `try_new` for
+    /// dimensions which aren't known to be valid, such as ones built from user input.
     pub fn new(dim: D) -> Self {
-        Self {
-            grid: Grid::new(dim),
-            ships: HashMap::new(),
+        match Self::try_new(dim) {
+            Ok(setup) => setup,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Begin game setup by constructing a new board with the given [`Dimensions`]. Fails
+    /// if the dimensions have no cells or more than [`MAX_CELLS`][crate::board::MAX_CELLS]
+    /// cells. In debug builds, also spot-checks that the dimensions follow the
+    /// [`Dimensions`] indexing contract, and panics if they don't.
+    pub fn try_new(dim: D) -> Result<Self, BoardCreationError<D>> {
+        Self::try_with_capacity(dim, 0)
+    }
+
+    /// Begin game setup like [`new`][BoardSetup::new], reserving space for
+    /// `ship_count` ships up front so adding them doesn't reallocate.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases as [`new`][BoardSetup::new].
+    pub fn with_capacity(dim: D, ship_count: usize) -> Self {
+        match Self::try_with_capacity(dim, ship_count) {
+            Ok(setup) => setup,
+            Err(err) => panic!("{}", err),
         }
     }
 
+    /// Begin game setup like [`try_new`][BoardSetup::try_new], reserving space for
+    /// `ship_count` ships up front so adding them doesn't reallocate.
+    pub fn try_with_capacity(dim: D, ship_count: usize) -> Result<Self, BoardCreationError<D>> {
+        Ok(Self {
+            grid: Grid::try_new(dim)?,
+            ships: HashMap::with_capacity(ship_count),
+            locked: false,
+            roster_locked: false,
+        })
+    }
+
+    /// Returns true if this board is locked. No ships can be added, placed, or unplaced on
+    /// a locked board.
+    pub fn locked(&self) -> bool {
+        self.locked
+    }
+
+    /// Lock or unlock this board. Locking is controlled by the game setup so it can
+    /// enforce its own rules about when players may change their boards.
+    pub(crate) fn set_locked(&mut self, locked: bool) {
+        self.locked = locked;
+    }
+
+    /// Returns true if this board's roster is locked. Ships can still be placed and
+    /// unplaced, but none can be added or removed.
+    pub fn roster_locked(&self) -> bool {
+        self.roster_locked
+    }
+
+    /// Replace every ship on this board with the given fleet, all unplaced, and lock the
+    /// roster. Used by the game setup once players agree on their fleets, so it ignores
+    /// both locks and unlocks the board, since its old placements are gone.
+    pub(crate) fn replace_roster(&mut self, fleet: impl IntoIterator<Item = (I, S)>) {
+        for cell in self.grid.cells.iter_mut() {
+            cell.ship = None;
+        }
+        self.ships.clear();
+        for (id, shape) in fleet {
+            let ship = ShipPlacementInfo {
+                shape,
+                placement: None,
+            };
+            self.ships.insert(id, ship);
+        }
+        self.locked = false;
+        self.roster_locked = true;
+    }
+
     /// Get the [`Dimesnsions`] of this [`Board`].
     pub fn dimensions(&self) -> &D {
         &self.grid.dim
@@ -222,6 +340,7 @@ impl<I: ShipId, D: Dimensions, S: ShipShape<D>> BoardSetup<I, D, S> {
             Err(self)
         } else {
             Ok(Board {
+                unshot: UnshotCells::new(&self.grid),
                 grid: self.grid,
                 ships: self
                     .ships
@@ -231,10 +350,37 @@ impl<I: ShipId, D: Dimensions, S: ShipShape<D>> BoardSetup<I, D, S> {
                         None => unreachable!(),
                     })
                     .collect(),
+                changes: None,
             })
         }
     }
 
+    /// Check that no two placed ships share a cell. Placement through [`ShipEntryMut`]
+    /// never allows overlaps, so this always passes unless placements were corrupted.
+    /// Otherwise, returns every pair of ships found sharing a cell, along with the shared
+    /// cell.
+    pub fn check_integrity(&self) -> Result<(), Vec<(I, I, D::Coordinate)>> {
+        let mut owners: HashMap<&D::Coordinate, &I> = HashMap::new();
+        let mut overlaps = Vec::new();
+        for (id, ship) in &self.ships {
+            for coord in ship.placement.iter().flatten() {
+                match owners.entry(coord) {
+                    Entry::Occupied(entry) => {
+                        overlaps.push(((*entry.get()).clone(), id.clone(), coord.clone()))
+                    }
+                    Entry::Vacant(entry) => {
+                        entry.insert(id);
+                    }
+                }
+            }
+        }
+        if overlaps.is_empty() {
+            Ok(())
+        } else {
+            Err(overlaps)
+        }
+    }
+
     /// Checks if this board is ready to start. Returns `true` if at least one ship has
     /// been added and all ships are placed.
     pub fn ready(&self) -> bool {
@@ -242,25 +388,41 @@ impl<I: ShipId, D: Dimensions, S: ShipShape<D>> BoardSetup<I, D, S> {
     }
 
     /// Get an iterator over the ships configured on this board.
-    pub fn iter_ships(&self) -> impl Iterator<Item = ShipEntry<I, D, S>> {
+    pub fn iter_ships(&self) -> impl Iterator<Item = ShipEntry<'_, I, D, S>> {
         let grid = &self.grid;
+        let locked = self.locked;
         self.ships.iter().map(move |(id, ship)| ShipEntry {
             id: id.clone(),
             grid,
             ship,
+            locked,
         })
     }
 
-    /// Attempts to add a ship with the given ID. If the given ShipID is already used,
-    /// returns the shape passed to this function. Otherwise adds the shape and returns
-    /// the ShipEntryMut for it to allow placement.
+    /// Attempts to add a ship with the given ID. If the given ShipID is already used, or
+    /// the board or its roster is locked, returns the shape passed to this function.
+    /// Otherwise adds the shape and returns the ShipEntryMut for it to allow placement.
     pub fn add_ship(
         &mut self,
         id: I,
         shape: S,
-    ) -> Result<ShipEntryMut<I, D, S>, AddShipError<I, S>> {
+    ) -> Result<ShipEntryMut<'_, I, D, S>, AddShipError<I, S>> {
+        if self.locked {
+            return Err(AddShipError::new(CannotAddShipReason::Locked, id, shape));
+        }
+        if self.roster_locked {
+            return Err(AddShipError::new(
+                CannotAddShipReason::RosterLocked,
+                id,
+                shape,
+            ));
+        }
         match self.ships.entry(id.clone()) {
-            Entry::Occupied(_) => Err(AddShipError::new(id, shape)),
+            Entry::Occupied(_) => Err(AddShipError::new(
+                CannotAddShipReason::AlreadyExists,
+                id,
+                shape,
+            )),
             Entry::Vacant(entry) => {
                 let ship = entry.insert(ShipPlacementInfo {
                     shape,
@@ -270,25 +432,98 @@ impl<I: ShipId, D: Dimensions, S: ShipShape<D>> BoardSetup<I, D, S> {
                     id,
                     grid: &mut self.grid,
                     ship,
+                    locked: false,
                 })
             }
         }
     }
 
+    /// Like [`add_ship`][BoardSetup::add_ship], but if a ship with the same ID and an
+    /// equal shape was already added, returns the existing ship's entry instead of an
+    /// error. This lets the same fleet be registered more than once, even after the board
+    /// is locked. A ship with the same ID but a different shape is still rejected; the
+    /// existing ship can be inspected with [`get_ship`][BoardSetup::get_ship].
+    pub fn add_ship_idempotent(
+        &mut self,
+        id: I,
+        shape: S,
+    ) -> Result<ShipEntryMut<'_, I, D, S>, AddShipError<I, S>>
+    where
+        S: PartialEq,
+    {
+        if self.ships.get(&id).is_some_and(|ship| ship.shape == shape) {
+            return Ok(ShipEntryMut {
+                grid: &mut self.grid,
+                ship: match self.ships.get_mut(&id) {
+                    Some(ship) => ship,
+                    None => unreachable!(),
+                },
+                id,
+                locked: self.locked,
+            });
+        }
+        self.add_ship(id, shape)
+    }
+
+    /// Remove the ship with the given ID from the board, clearing its placement, and
+    /// return its shape.
+    pub fn remove_ship<Q>(&mut self, id: &Q) -> Result<S, CannotRemoveShipReason>
+    where
+        I: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if self.locked {
+            return Err(CannotRemoveShipReason::Locked);
+        }
+        if self.roster_locked {
+            return Err(CannotRemoveShipReason::RosterLocked);
+        }
+        let ship = self
+            .ships
+            .remove(id)
+            .ok_or(CannotRemoveShipReason::NotFound)?;
+        for coord in ship.placement.iter().flatten() {
+            // Ships are only placed on valid cells.
+            self.grid[coord].ship = None;
+        }
+        Ok(ship.shape)
+    }
+
     /// Get the [`ShipEntry`] for the ship with the specified ID if such a ship exists.
-    pub fn get_ship(&self, id: I) -> Option<ShipEntry<I, D, S>> {
+    pub fn get_ship<Q>(&self, id: &Q) -> Option<ShipEntry<'_, I, D, S>>
+    where
+        I: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
         let grid = &self.grid;
+        let locked = self.locked;
         self.ships
-            .get(&id)
-            .map(move |ship| ShipEntry { id, grid, ship })
+            .get_key_value(id)
+            .map(move |(id, ship)| ShipEntry {
+                id: id.clone(),
+                grid,
+                ship,
+                locked,
+            })
     }
 
     /// Get the [`ShipEntryMut`] for the ship with the specified ID if such a ship exists.
-    pub fn get_ship_mut(&mut self, id: I) -> Option<ShipEntryMut<I, D, S>> {
+    pub fn get_ship_mut<Q>(&mut self, id: &Q) -> Option<ShipEntryMut<'_, I, D, S>>
+    where
+        I: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        // HashMap has no mutable equivalent of get_key_value, so look up the owned key
+        // first.
+        let id = self.ships.get_key_value(id)?.0.clone();
         let grid = &mut self.grid;
-        self.ships
-            .get_mut(&id)
-            .map(move |ship| ShipEntryMut { id, grid, ship })
+        let locked = self.locked;
+        self.ships.get_mut::<I>(&id).map(move |ship| ShipEntryMut {
+            id,
+            grid,
+            ship,
+            locked,
+        })
     }
 
     /// Get the ID of the ship placed at the specified coordinate if any. Returns None if
@@ -297,3 +532,257 @@ impl<I: ShipId, D: Dimensions, S: ShipShape<D>> BoardSetup<I, D, S> {
         self.grid.get(coord).and_then(|cell| cell.ship.as_ref())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        board::{CannotCreateBoardReason, ColinearCheck, Coordinate, NeighborIterState, MAX_CELLS},
+        ships::Line,
+    };
+
+    /// Coordinate of [`Mock`] dimensions.
+    #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+    struct Cell(usize);
+
+    impl Coordinate for Cell {}
+
+    /// Dimensions of a row of `size` cells which claim any size they're given, and whose
+    /// linearization is offset by `shift`, so a nonzero shift breaks the contract.
+    #[derive(Debug, Eq, PartialEq)]
+    struct Mock {
+        size: usize,
+        shift: usize,
+    }
+
+    impl Dimensions for Mock {
+        type Coordinate = Cell;
+        type NeighborIterState = NoNeighbors;
+
+        fn total_size(&self) -> usize {
+            self.size
+        }
+
+        fn try_linearize(&self, coord: &Cell) -> Option<usize> {
+            if coord.0 < self.size {
+                Some((coord.0 + self.shift) % self.size)
+            } else {
+                None
+            }
+        }
+
+        fn try_un_linearize(&self, index: usize) -> Option<Cell> {
+            if index < self.size {
+                Some(Cell(index))
+            } else {
+                None
+            }
+        }
+    }
+
+    impl ColinearCheck for Mock {
+        fn is_colinear(&self, _c1: &Cell, _c2: &Cell, _c3: &Cell) -> bool {
+            // Every cell is on the same row.
+            true
+        }
+    }
+
+    /// Neighbor iter for [`Mock`], which has no neighbors.
+    struct NoNeighbors;
+
+    impl NeighborIterState for NoNeighbors {
+        type Dimensions = Mock;
+
+        fn start(_dim: &Mock, _coord: Cell) -> Self {
+            NoNeighbors
+        }
+
+        fn next(&mut self, _dim: &Mock) -> Option<Cell> {
+            None
+        }
+    }
+
+    type MockSetup = BoardSetup<&'static str, Mock, Line>;
+
+    #[test]
+    fn rejects_unusable_dimensions() {
+        let err = MockSetup::try_new(Mock { size: 0, shift: 0 })
+            .err()
+            .unwrap();
+        assert_eq!(err.reason(), CannotCreateBoardReason::Empty);
+        let huge = Mock {
+            size: usize::MAX,
+            shift: 0,
+        };
+        let err = MockSetup::try_new(huge).err().unwrap();
+        assert_eq!(err.reason(), CannotCreateBoardReason::TooLarge);
+        assert_eq!(err.dimensions().size, usize::MAX);
+        let err = MockSetup::try_with_capacity(
+            Mock {
+                size: MAX_CELLS + 1,
+                shift: 0,
+            },
+            4,
+        );
+        assert!(err.err().unwrap().reason().is_too_large());
+
+        let setup = MockSetup::try_new(Mock { size: 8, shift: 0 }).ok().unwrap();
+        assert_eq!(setup.dimensions().size, 8);
+    }
+
+    #[test]
+    #[should_panic(expected = "have no cells")]
+    fn new_panics_on_empty_dimensions() {
+        MockSetup::new(Mock { size: 0, shift: 0 });
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "does not linearize")]
+    fn contract_violation_caught_in_debug() {
+        let _ = MockSetup::try_new(Mock { size: 8, shift: 1 });
+    }
+
+    #[test]
+    fn check_integrity_finds_injected_overlap() {
+        use crate::board::{common::Coordinate2D, rectangular::RectDimensions};
+
+        let cells = |coords: &[(usize, usize)]| {
+            coords
+                .iter()
+                .map(|&(x, y)| Coordinate2D::new(x, y))
+                .collect::<Vec<_>>()
+        };
+        let mut setup = BoardSetup::new(RectDimensions::new(4, 4));
+        for &(id, row) in &[("a", 0), ("b", 1)] {
+            setup
+                .add_ship(id, Line::new(2))
+                .unwrap()
+                .place(cells(&[(0, row), (1, row)]))
+                .unwrap();
+        }
+        assert_eq!(setup.check_integrity(), Ok(()));
+
+        // Corrupt the placements directly, since placing normally refuses overlaps.
+        setup.ships.get_mut("b").unwrap().placement = Some(cells(&[(1, 0), (1, 1)]));
+        let overlaps = setup.check_integrity().unwrap_err();
+        assert_eq!(overlaps.len(), 1);
+        let (first, second, coord) = overlaps[0];
+        assert_eq!(coord, Coordinate2D::new(1, 0));
+        // Ships are visited in arbitrary order.
+        let mut pair = [first, second];
+        pair.sort_unstable();
+        assert_eq!(pair, ["a", "b"]);
+    }
+
+    #[test]
+    fn shape_reads_back_line_length() {
+        let mut setup = MockSetup::new(Mock { size: 8, shift: 0 });
+        setup.add_ship("cruiser", Line::new(3)).unwrap();
+        setup.add_ship("destroyer", Line::new(2)).unwrap();
+        assert_eq!(setup.get_ship(&"cruiser").unwrap().shape().len(), 3);
+        let destroyer = setup.get_ship_mut(&"destroyer").unwrap();
+        assert_eq!(destroyer.shape(), &Line::new(2));
+        assert_eq!(destroyer.shape().len(), 2);
+    }
+
+    #[test]
+    fn fleet_renames_conflicting_id() {
+        let mut setup = MockSetup::new(Mock { size: 8, shift: 0 });
+        let fleet = [("cruiser", 3), ("destroyer", 2), ("cruiser", 4)];
+        let mut added = Vec::new();
+        for &(id, len) in &fleet {
+            let (id, shape) = match setup.add_ship(id, Line::new(len)) {
+                Ok(ship) => {
+                    added.push(*ship.id());
+                    continue;
+                }
+                Err(err) => {
+                    assert_eq!(err.reason(), CannotAddShipReason::AlreadyExists);
+                    err.retry_with_id("cruiser-2")
+                }
+            };
+            let ship = setup.add_ship(id, shape).unwrap();
+            added.push(*ship.id());
+        }
+        assert_eq!(added, ["cruiser", "destroyer", "cruiser-2"]);
+        assert_eq!(setup.get_ship(&"cruiser").unwrap().shape(), &Line::new(3));
+        assert_eq!(setup.get_ship(&"cruiser-2").unwrap().shape(), &Line::new(4));
+    }
+
+    #[test]
+    fn capacity_hint_avoids_reallocation() {
+        use crate::board::rectangular::RectDimensions;
+
+        let mut setup =
+            BoardSetup::<usize, RectDimensions, Line>::with_capacity(RectDimensions::new(8, 8), 32);
+        assert_eq!(setup.grid.cells.len(), 64);
+        let capacity = setup.ships.capacity();
+        assert!(capacity >= 32);
+        for id in 0..32 {
+            setup.add_ship(id, Line::new(1)).unwrap();
+        }
+        assert_eq!(setup.ships.capacity(), capacity);
+
+        let setup = MockSetup::try_with_capacity(Mock { size: 8, shift: 0 }, 4)
+            .ok()
+            .unwrap();
+        assert!(setup.ships.capacity() >= 4);
+        assert!(MockSetup::try_with_capacity(Mock { size: 0, shift: 0 }, 4)
+            .err()
+            .unwrap()
+            .reason()
+            .is_empty());
+    }
+
+    #[test]
+    fn idempotent_ship_re_adds() {
+        use crate::board::{common::Coordinate2D, rectangular::RectDimensions};
+
+        let mut setup = BoardSetup::new(RectDimensions::new(4, 4));
+        let cells = vec![Coordinate2D::new(0, 0), Coordinate2D::new(1, 0)];
+        setup
+            .add_ship("destroyer", Line::new(2))
+            .unwrap()
+            .place(cells.clone())
+            .unwrap();
+
+        // Identical: the existing ship is returned, keeping its placement.
+        let ship = setup
+            .add_ship_idempotent("destroyer", Line::new(2))
+            .unwrap();
+        assert_eq!(ship.placement(), Some(&cells));
+
+        // Conflicting: rejected with the new shape, and the existing ship is untouched.
+        let err = setup
+            .add_ship_idempotent("destroyer", Line::new(3))
+            .err()
+            .unwrap();
+        assert_eq!(
+            err,
+            AddShipError::new(
+                CannotAddShipReason::AlreadyExists,
+                "destroyer",
+                Line::new(3)
+            )
+        );
+        assert_eq!(setup.get_ship(&"destroyer").unwrap().shape(), &Line::new(2));
+
+        // Strict: the plain method still rejects even an identical re-add.
+        let err = setup.add_ship("destroyer", Line::new(2)).err().unwrap();
+        assert_eq!(err.reason(), CannotAddShipReason::AlreadyExists);
+        assert_eq!(setup.iter_ships().count(), 1);
+
+        // On a locked board an identical re-add still succeeds, but stays locked.
+        setup.set_locked(true);
+        let mut ship = setup
+            .add_ship_idempotent("destroyer", Line::new(2))
+            .unwrap();
+        assert_eq!(ship.unplace(), Err(CannotPlaceReason::Locked));
+        let err = setup
+            .add_ship_idempotent("cruiser", Line::new(3))
+            .err()
+            .unwrap();
+        assert_eq!(err.reason(), CannotAddShipReason::Locked);
+    }
+}
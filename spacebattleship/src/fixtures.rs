@@ -0,0 +1,297 @@
+// Copyright 2020 Zachary Stewart
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Ready-made setups and games in fixed, documented positions, for testing code built on
+//! top of the game. Only available with the `test-fixtures` feature.
+//!
+//! Every player in every fixture uses the same layout on a 10x10 board, with each ship
+//! placed horizontally starting from column 0 and facing right:
+//!
+//! | Ship       | Cells         |
+//! |------------|---------------|
+//! | Carrier    | (0,0)..=(4,0) |
+//! | Battleship | (0,2)..=(3,2) |
+//! | Cruiser    | (0,4)..=(2,4) |
+//! | Submarine  | (0,6)..=(2,6) |
+//! | Destroyer  | (0,8)..=(1,8) |
+//!
+//! All odd rows are empty, so shots there are always misses. Fixtures are built only
+//! through the public API, and panic if any step is rejected.
+use crate::{
+    board::rectangular::RectDimensions,
+    game::{
+        simple::{self, Coordinate, Orientation, Player, Ship},
+        uniform,
+    },
+    ships::Line,
+};
+
+/// Uniform game setup used by the three-player fixtures. Players are numbered 0 to 2
+/// and take turns in that order.
+pub type UniformSetup = uniform::GameSetup<usize, Ship, RectDimensions, Line>;
+
+/// Uniform game used by the three-player fixtures.
+pub type UniformGame = uniform::Game<usize, Ship, RectDimensions>;
+
+/// Get the cells of every ship in the fixture layout, in placement order.
+fn layout() -> impl Iterator<Item = (Ship, Vec<Coordinate>)> {
+    Ship::ALL.iter().enumerate().map(|(row, &ship)| {
+        let cells = (0..ship.len())
+            .map(|x| Coordinate::new(x, row * 2))
+            .collect();
+        (ship, cells)
+    })
+}
+
+/// Get the cells of the odd rows in row-major order. None of them are occupied.
+fn misses() -> impl Iterator<Item = Coordinate> {
+    (1..10)
+        .step_by(2)
+        .flat_map(|y| (0..10).map(move |x| Coordinate::new(x, y)))
+}
+
+/// Simple setup with both players' ships placed in the fixture layout, ready to start.
+/// [`Player::P1`] moves first.
+pub fn simple_setup() -> simple::GameSetup {
+    let mut setup = simple::GameSetup::new();
+    for &player in &[Player::P1, Player::P2] {
+        for (ship, cells) in layout() {
+            setup
+                .place_ship(player, ship, cells[0], Orientation::Right)
+                .unwrap();
+        }
+    }
+    setup
+}
+
+/// Simple game in progress. The following shots have been fired, in order, and it is
+/// [`Player::P1`]'s turn:
+///
+/// 1. P1 hits P2's Carrier at (0,0).
+/// 2. P2 misses P1 at (9,9).
+/// 3. P1 hits P2's Destroyer at (0,8).
+/// 4. P2 hits P1's Battleship at (0,2).
+/// 5. P1 sinks P2's Destroyer at (1,8).
+/// 6. P2 misses P1 at (9,7).
+pub fn simple_mid_game() -> simple::Game {
+    let mut game = simple_setup().start().ok().unwrap();
+    for &(target, x, y) in &[
+        (Player::P2, 0, 0),
+        (Player::P1, 9, 9),
+        (Player::P2, 0, 8),
+        (Player::P1, 0, 2),
+        (Player::P2, 1, 8),
+        (Player::P1, 9, 7),
+    ] {
+        game.shoot(target, Coordinate::new(x, y)).unwrap();
+    }
+    game
+}
+
+/// Simple game one shot from victory. [`Player::P1`] has hit every cell of P2's fleet
+/// except the Destroyer at (1,8), and P2 has missed once after each of those shots,
+/// working through the odd rows in row-major order. It is P1's turn, and shooting P2 at
+/// (1,8) wins the game.
+pub fn simple_near_victory() -> simple::Game {
+    let mut game = simple_setup().start().ok().unwrap();
+    let mut misses = misses();
+    let mut targets: Vec<_> = layout().flat_map(|(_, cells)| cells).collect();
+    targets.pop();
+    for coord in targets {
+        game.shoot(Player::P2, coord).unwrap();
+        game.shoot(Player::P1, misses.next().unwrap()).unwrap();
+    }
+    game
+}
+
+/// Uniform setup with three players, numbered 0 to 2, each with the standard fleet
+/// placed in the fixture layout, ready to start. Player 0 moves first.
+pub fn uniform_setup() -> UniformSetup {
+    let mut setup = UniformSetup::new();
+    for pid in 0..3 {
+        let board = setup.add_player(pid, RectDimensions::new(10, 10)).unwrap();
+        for (ship, cells) in layout() {
+            board.add_ship(ship, Line::new(ship.len())).unwrap();
+            board.get_ship_mut(&ship).unwrap().place(cells).unwrap();
+        }
+    }
+    setup
+}
+
+/// Three-player uniform game in progress. The following shots have been fired, in
+/// order, and it is player 0's turn:
+///
+/// 1. Player 0 hits player 1's Carrier at (0,0).
+/// 2. Player 1 misses player 2 at (9,9).
+/// 3. Player 2 hits player 0's Destroyer at (0,8).
+/// 4. Player 0 hits player 2's Destroyer at (0,8).
+/// 5. Player 1 misses player 0 at (9,9).
+/// 6. Player 2 hits player 1's Battleship at (0,2).
+pub fn uniform_mid_game() -> UniformGame {
+    let mut game = uniform_setup().start().ok().unwrap();
+    for &(target, x, y) in &[
+        (1, 0, 0),
+        (2, 9, 9),
+        (0, 0, 8),
+        (2, 0, 8),
+        (0, 9, 9),
+        (1, 0, 2),
+    ] {
+        game.shoot(target, Coordinate::new(x, y)).unwrap();
+    }
+    game
+}
+
+/// Three-player uniform game one shot from victory. Player 0 has sunk all of player 2's
+/// ships, then hit every cell of player 1's fleet except the Destroyer at (1,8). Players
+/// 1 and 2 only ever missed player 0, working through the odd rows in row-major order,
+/// until player 2 was defeated. It is player 0's turn, and shooting player 1 at (1,8)
+/// wins the game.
+pub fn uniform_near_victory() -> UniformGame {
+    let mut game = uniform_setup().start().ok().unwrap();
+    let mut misses = misses();
+    let mut targets: Vec<_> = layout()
+        .flat_map(|(_, cells)| cells)
+        .map(|coord| (2, coord))
+        .chain(
+            layout()
+                .flat_map(|(_, cells)| cells)
+                .map(|coord| (1, coord)),
+        )
+        .collect();
+    targets.pop();
+    let mut targets = targets.into_iter().peekable();
+    while targets.peek().is_some() || *game.current() != 0 {
+        if *game.current() == 0 {
+            let (target, coord) = targets.next().unwrap();
+            game.shoot(target, coord).unwrap();
+        } else {
+            game.shoot(0, misses.next().unwrap()).unwrap();
+        }
+    }
+    game
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::simple::ShotOutcome;
+
+    /// Collect the cells of a 10x10 board for which `hit` returns true, as `(x, y)`
+    /// pairs in row-major order.
+    fn hits(hit: impl Fn(Coordinate) -> bool) -> Vec<(usize, usize)> {
+        (0..10)
+            .flat_map(|y| (0..10).map(move |x| (x, y)))
+            .filter(|&(x, y)| hit(Coordinate::new(x, y)))
+            .collect()
+    }
+
+    /// Cells hit on the given player's board in a simple game.
+    fn simple_hits(game: &simple::Game, player: Player) -> Vec<(usize, usize)> {
+        hits(|coord| game.get_coord(player, coord).unwrap().hit())
+    }
+
+    /// Cells hit on the given player's board in a uniform game.
+    fn uniform_hits(game: &UniformGame, pid: usize) -> Vec<(usize, usize)> {
+        let board = game.get_board(&pid).unwrap();
+        hits(|coord| board.get_coord(coord).unwrap().hit())
+    }
+
+    /// Ship cells of the fixture layout, as `(x, y)` pairs in row-major order.
+    fn ship_cells() -> Vec<(usize, usize)> {
+        layout()
+            .flat_map(|(_, cells)| cells)
+            .map(|coord| (coord.x, coord.y))
+            .collect()
+    }
+
+    #[test]
+    fn simple_setup_is_ready() {
+        let setup = simple_setup();
+        assert!(setup.ready());
+        let game = setup.start().ok().unwrap();
+        assert_eq!(game.current(), Player::P1);
+        assert_eq!(simple_hits(&game, Player::P1), []);
+        assert_eq!(simple_hits(&game, Player::P2), []);
+    }
+
+    #[test]
+    fn simple_mid_game_position() {
+        let game = simple_mid_game();
+        assert_eq!(game.current(), Player::P1);
+        assert_eq!(game.winner(), None);
+        assert_eq!(simple_hits(&game, Player::P1), [(0, 2), (9, 7), (9, 9)]);
+        assert_eq!(simple_hits(&game, Player::P2), [(0, 0), (0, 8), (1, 8)]);
+    }
+
+    #[test]
+    fn simple_near_victory_position() {
+        let mut game = simple_near_victory();
+        assert_eq!(game.current(), Player::P1);
+        assert_eq!(game.winner(), None);
+        let mut expected = ship_cells();
+        expected.retain(|&cell| cell != (1, 8));
+        assert_eq!(simple_hits(&game, Player::P2), expected);
+        assert!(simple_hits(&game, Player::P1)
+            .iter()
+            .all(|&(_, y)| y % 2 == 1));
+
+        assert_eq!(
+            game.shoot(Player::P2, Coordinate::new(1, 8)),
+            Ok(ShotOutcome::Victory(Ship::Destroyer))
+        );
+        assert_eq!(game.winner(), Some(Player::P1));
+    }
+
+    #[test]
+    fn uniform_setup_is_valid() {
+        let setup = uniform_setup();
+        assert_eq!(setup.validate(), Ok(()));
+        let game = setup.start().ok().unwrap();
+        assert_eq!(*game.current(), 0);
+        for pid in 0..3 {
+            assert_eq!(uniform_hits(&game, pid), []);
+        }
+    }
+
+    #[test]
+    fn uniform_mid_game_position() {
+        let game = uniform_mid_game();
+        assert_eq!(*game.current(), 0);
+        assert_eq!(game.winner(), None);
+        assert_eq!(uniform_hits(&game, 0), [(0, 8), (9, 9)]);
+        assert_eq!(uniform_hits(&game, 1), [(0, 0), (0, 2)]);
+        assert_eq!(uniform_hits(&game, 2), [(0, 8), (9, 9)]);
+    }
+
+    #[test]
+    fn uniform_near_victory_position() {
+        let mut game = uniform_near_victory();
+        assert_eq!(*game.current(), 0);
+        assert_eq!(game.winner(), None);
+        assert!(game.get_board(&2).unwrap().defeated());
+        assert_eq!(uniform_hits(&game, 2), ship_cells());
+        let mut expected = ship_cells();
+        expected.retain(|&cell| cell != (1, 8));
+        assert_eq!(uniform_hits(&game, 1), expected);
+        assert!(uniform_hits(&game, 0).iter().all(|&(_, y)| y % 2 == 1));
+
+        let outcome = game.shoot(1, Coordinate::new(1, 8)).unwrap();
+        assert!(matches!(
+            outcome,
+            uniform::ShotOutcome::Victory(Ship::Destroyer)
+        ));
+        assert_eq!(game.winner(), Some(&0));
+    }
+}
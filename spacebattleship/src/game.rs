@@ -17,17 +17,30 @@
 //!
 //! [`simple`] provides an implementation of the simplest form of the game: 10x10 grid
 //! with two players and the same set of sandard ships for both players. This is a simple
-//! wrapper around the [`uniform`] game.
+//! wrapper around the [`uniform`] game. With the `rng_gen` feature, [`simple::ai`]
+//! provides a computer opponent for it.
 //!
 //! [`uniform`] provides an implementation that allows a fair amount of flexibility in
 //! terms of the number of players, the ships available to each player, and the exact
 //! dimensions of each player's board, but requires uniform generic arguments for all
 //! players.
 //!
+//! [`run`] provides a generic loop for playing out a [`uniform`] game, with moves
+//! supplied by a [`run::MoveProvider`].
+//!
+//! [`annotations`] provides the notes players can keep on cells of their opponents'
+//! boards while playing.
+//!
+//! [`achievements`] derives notable feats, such as first blood, from the shots fired in
+//! a [`uniform`] game.
+//!
 //! [`dynamic`] provides support for fully-dynamic games where every player might be
 //! playing on a completely different board type with different ships and coordinate
 //! formats.
 
+pub mod achievements;
+pub mod annotations;
+pub mod run;
 pub mod simple;
 pub mod uniform;
 pub mod dynamic {
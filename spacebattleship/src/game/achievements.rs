@@ -0,0 +1,300 @@
+// Copyright 2020 Zachary Stewart
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Achievements players earn over the course of a [`uniform`] game, for frontends which
+//! want to celebrate notable shots. Achievements are worked out from the shots fed to
+//! an [`Achievements`] collector, which never changes the game itself.
+//!
+//! [`uniform`]: crate::game::uniform
+use std::collections::HashMap;
+
+use crate::{
+    board::{BoardEvents, Dimensions},
+    game::uniform::{Game, PlayerId, ShotOutcome},
+    ships::ShipId,
+};
+
+/// Number of the shooter's own turns within which two sinks count as a double kill.
+const DOUBLE_KILL_TURNS: usize = 3;
+
+/// A notable feat by the given player.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Achievement<P> {
+    /// The player sank the first ship of the game.
+    FirstBlood(P),
+    /// The player sank two ships within three of their own turns.
+    DoubleKill(P),
+    /// The player won without losing a ship.
+    Flawless(P),
+    /// The player won after being down to their last ship.
+    Comeback(P),
+}
+
+impl<P> Achievement<P> {
+    /// Get the player who earned the achievement.
+    pub fn player(&self) -> &P {
+        match self {
+            Achievement::FirstBlood(player)
+            | Achievement::DoubleKill(player)
+            | Achievement::Flawless(player)
+            | Achievement::Comeback(player) => player,
+        }
+    }
+
+    /// Returns true if this is [`Achievement::FirstBlood`].
+    pub fn is_first_blood(&self) -> bool {
+        matches!(self, Achievement::FirstBlood(_))
+    }
+
+    /// Returns true if this is [`Achievement::DoubleKill`].
+    pub fn is_double_kill(&self) -> bool {
+        matches!(self, Achievement::DoubleKill(_))
+    }
+
+    /// Returns true if this is [`Achievement::Flawless`].
+    pub fn is_flawless(&self) -> bool {
+        matches!(self, Achievement::Flawless(_))
+    }
+
+    /// Returns true if this is [`Achievement::Comeback`].
+    pub fn is_comeback(&self) -> bool {
+        matches!(self, Achievement::Comeback(_))
+    }
+}
+
+/// What the collector remembers about a single player.
+#[derive(Debug, Clone)]
+struct Tally {
+    /// Number of turns the player has taken.
+    turns: usize,
+    /// Turn on which the player last sank a ship, if any.
+    last_sink: Option<usize>,
+    /// Number of ships the player started with.
+    fleet: usize,
+    /// Number of the player's ships which are still afloat.
+    afloat: usize,
+}
+
+/// Collects the achievements earned in a game from the shots fired in it.
+///
+/// Feed every shot to [`record`][Achievements::record] in the order it was fired, using
+/// the outcome and events from [`Game::shoot_with_events`]. Shots which failed should
+/// not be recorded.
+#[derive(Debug, Clone)]
+pub struct Achievements<P: PlayerId> {
+    /// Progress of each player.
+    tallies: HashMap<P, Tally>,
+    /// Whether any ship has been sunk yet.
+    blooded: bool,
+    /// Achievements earned so far, in the order they were earned.
+    earned: Vec<Achievement<P>>,
+}
+
+impl<P: PlayerId> Achievements<P> {
+    /// Create a collector for the given game, which should not have had any shots fired
+    /// yet. Only reads the size of each player's fleet.
+    pub fn new<I: ShipId, D: Dimensions>(game: &Game<P, I, D>) -> Self {
+        let tallies = game
+            .iter_boards()
+            .map(|(pid, board)| {
+                let afloat = board.iter_ships().filter(|ship| !ship.sunk()).count();
+                let tally = Tally {
+                    turns: 0,
+                    last_sink: None,
+                    fleet: board.iter_ships().count(),
+                    afloat,
+                };
+                (pid.clone(), tally)
+            })
+            .collect();
+        Self {
+            tallies,
+            blooded: false,
+            earned: Vec::new(),
+        }
+    }
+
+    /// Record a shot by `shooter` at `target`, returning the achievements it earned, if
+    /// any. Panics if either player was not part of the game.
+    pub fn record<I, C>(
+        &mut self,
+        shooter: &P,
+        target: &P,
+        outcome: &ShotOutcome<I>,
+        events: &BoardEvents<I, C>,
+    ) -> &[Achievement<P>] {
+        let start = self.earned.len();
+        let turn = {
+            let tally = self.tallies.get_mut(shooter).unwrap();
+            tally.turns += 1;
+            tally.turns
+        };
+        if events.sunk.is_some() {
+            self.tallies.get_mut(target).unwrap().afloat -= 1;
+            if !self.blooded {
+                self.blooded = true;
+                self.earned.push(Achievement::FirstBlood(shooter.clone()));
+            }
+            let tally = self.tallies.get_mut(shooter).unwrap();
+            if let Some(last) = tally.last_sink.replace(turn) {
+                if turn - last < DOUBLE_KILL_TURNS {
+                    self.earned.push(Achievement::DoubleKill(shooter.clone()));
+                }
+            }
+        }
+        if outcome.is_victory() {
+            let tally = &self.tallies[shooter];
+            if tally.afloat == tally.fleet {
+                self.earned.push(Achievement::Flawless(shooter.clone()));
+            } else if tally.afloat == 1 {
+                self.earned.push(Achievement::Comeback(shooter.clone()));
+            }
+        }
+        &self.earned[start..]
+    }
+
+    /// Get every achievement earned so far, in the order they were earned.
+    pub fn earned(&self) -> &[Achievement<P>] {
+        &self.earned
+    }
+
+    /// Consume the collector, returning every achievement earned, in order.
+    pub fn into_earned(self) -> Vec<Achievement<P>> {
+        self.earned
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        board::rectangular::{Coordinate, RectDimensions},
+        game::uniform::GameSetup,
+        ships::Line,
+    };
+
+    /// Start a two-player game on 4x4 boards. Each player has single-cell ships 0 at
+    /// (0,0) and 1 at (2,0), and a two-cell ship 2 at (0,2) and (1,2).
+    fn duel() -> Game<u8, u8, RectDimensions> {
+        let mut setup = GameSetup::new();
+        for pid in 0..2 {
+            let board = setup.add_player(pid, RectDimensions::new(4, 4)).unwrap();
+            for (ship, cells) in [
+                (0, vec![(0, 0)]),
+                (1, vec![(2, 0)]),
+                (2, vec![(0, 2), (1, 2)]),
+            ] {
+                board.add_ship(ship, Line::new(cells.len())).unwrap();
+                let cells = cells.into_iter().map(|(x, y)| Coordinate::new(x, y));
+                board
+                    .get_ship_mut(&ship)
+                    .unwrap()
+                    .place(cells.collect())
+                    .unwrap();
+            }
+        }
+        setup.start().ok().unwrap()
+    }
+
+    /// Fire the given shots, each by the current player, returning every achievement
+    /// earned.
+    fn play(shots: &[(u8, usize, usize)]) -> Vec<Achievement<u8>> {
+        let mut game = duel();
+        let mut achievements = Achievements::new(&game);
+        for &(target, x, y) in shots {
+            let shooter = *game.current();
+            let (outcome, events) = game
+                .shoot_with_events(target, Coordinate::new(x, y))
+                .unwrap();
+            achievements.record(&shooter, &target, &outcome, &events);
+        }
+        achievements.into_earned()
+    }
+
+    #[test]
+    fn first_blood_double_kill_and_flawless() {
+        let earned = play(&[
+            (1, 0, 0),
+            (0, 3, 3),
+            (1, 2, 0),
+            (0, 3, 2),
+            (1, 0, 2),
+            (0, 3, 1),
+            (1, 1, 2),
+        ]);
+        assert_eq!(
+            earned,
+            [
+                Achievement::FirstBlood(0),
+                Achievement::DoubleKill(0),
+                // The last ship sank two turns after the second.
+                Achievement::DoubleKill(0),
+                Achievement::Flawless(0),
+            ]
+        );
+    }
+
+    #[test]
+    fn comeback_from_last_ship() {
+        let earned = play(&[
+            (1, 3, 0),
+            (0, 0, 0),
+            (1, 3, 1),
+            (0, 3, 0),
+            (1, 3, 2),
+            (0, 3, 1),
+            (1, 3, 3),
+            // Three turns after its first sink, so not a double kill.
+            (0, 2, 0),
+            (1, 0, 0),
+            (0, 3, 2),
+            (1, 0, 2),
+            (0, 3, 3),
+            (1, 1, 0),
+            (0, 1, 0),
+            (1, 1, 2),
+            (0, 1, 1),
+            (1, 1, 1),
+            (0, 0, 1),
+            (1, 0, 1),
+            (0, 2, 1),
+            (1, 2, 0),
+        ]);
+        assert_eq!(
+            earned,
+            [Achievement::FirstBlood(1), Achievement::Comeback(0)]
+        );
+    }
+
+    #[test]
+    fn no_sinks_earn_nothing() {
+        let earned = play(&[(1, 0, 2), (0, 3, 3), (1, 3, 3), (0, 1, 2)]);
+        assert!(earned.is_empty(), "{:?}", earned);
+    }
+
+    #[test]
+    fn record_returns_new_achievements() {
+        let mut game = duel();
+        let mut achievements = Achievements::new(&game);
+        let (outcome, events) = game.shoot_with_events(1, Coordinate::new(0, 0)).unwrap();
+        let earned = achievements.record(&0, &1, &outcome, &events);
+        assert_eq!(earned, [Achievement::FirstBlood(0)]);
+        assert!(earned[0].is_first_blood());
+        assert_eq!(earned[0].player(), &0);
+        let (outcome, events) = game.shoot_with_events(0, Coordinate::new(3, 3)).unwrap();
+        assert!(achievements.record(&1, &0, &outcome, &events).is_empty());
+        assert_eq!(achievements.earned(), [Achievement::FirstBlood(0)]);
+    }
+}
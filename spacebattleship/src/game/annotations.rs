@@ -0,0 +1,121 @@
+// Copyright 2020 Zachary Stewart
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Notes players make on cells of their opponents' boards. Notes are kept alongside the
+//! game for the player's convenience, but game logic never reads them.
+use std::{borrow::Borrow, collections::HashMap, fmt, hash::Hash};
+
+/// A note a player made on a single cell.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Note {
+    /// The player suspects a ship is in this cell.
+    Suspect,
+    /// The player wants to avoid shooting this cell.
+    Avoid,
+    /// Free-form text.
+    Text(String),
+}
+
+impl Note {
+    /// Returns true if this is [`Note::Suspect`].
+    pub fn is_suspect(&self) -> bool {
+        matches!(self, Note::Suspect)
+    }
+
+    /// Returns true if this is [`Note::Avoid`].
+    pub fn is_avoid(&self) -> bool {
+        matches!(self, Note::Avoid)
+    }
+
+    /// Returns true if this is [`Note::Text`].
+    pub fn is_text(&self) -> bool {
+        matches!(self, Note::Text(_))
+    }
+}
+
+impl fmt::Display for Note {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Note::Suspect => f.pad("suspect"),
+            Note::Avoid => f.pad("avoid"),
+            Note::Text(text) => f.pad(text),
+        }
+    }
+}
+
+/// One player's notes on the cells of one opponent's board, at most one per cell.
+#[derive(Debug, Clone)]
+pub struct AnnotationMap<C> {
+    /// Note for each annotated cell.
+    notes: HashMap<C, Note>,
+}
+
+impl<C: Eq + Hash> AnnotationMap<C> {
+    /// Create an empty [`AnnotationMap`].
+    pub fn new() -> Self {
+        Self {
+            notes: HashMap::new(),
+        }
+    }
+
+    /// Get the note on the given cell, if any.
+    pub fn get<Q>(&self, coord: &Q) -> Option<&Note>
+    where
+        C: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.notes.get(coord)
+    }
+
+    /// Set the note on the given cell, returning the note it replaced, if any.
+    pub fn set(&mut self, coord: C, note: Note) -> Option<Note> {
+        self.notes.insert(coord, note)
+    }
+
+    /// Remove the note from the given cell, returning it if there was one.
+    pub fn remove<Q>(&mut self, coord: &Q) -> Option<Note>
+    where
+        C: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.notes.remove(coord)
+    }
+
+    /// Iterate the annotated cells and their notes, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&C, &Note)> {
+        self.notes.iter()
+    }
+
+    /// Get the number of annotated cells.
+    pub fn len(&self) -> usize {
+        self.notes.len()
+    }
+
+    /// Returns true if no cells are annotated.
+    pub fn is_empty(&self) -> bool {
+        self.notes.is_empty()
+    }
+
+    /// Remove every note.
+    pub fn clear(&mut self) {
+        self.notes.clear()
+    }
+}
+
+impl<C: Eq + Hash> Default for AnnotationMap<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
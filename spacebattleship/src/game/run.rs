@@ -0,0 +1,233 @@
+// Copyright 2020 Zachary Stewart
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generic driver loop for playing out a [`uniform::Game`][Game].
+use crate::{
+    board::Dimensions,
+    game::uniform::{Game, PlayerId, ShotError, ShotOutcome},
+    ships::ShipId,
+};
+
+/// Supplies moves to [`drive`] and receives notifications about how the game is going.
+/// Only [`choose_move`][MoveProvider::choose_move] is required; the notifications do
+/// nothing by default.
+pub trait MoveProvider<P: PlayerId, I: ShipId, D: Dimensions> {
+    /// Choose the target player and coordinate for the current player's next shot.
+    fn choose_move(&mut self, game: &Game<P, I, D>) -> (P, D::Coordinate);
+
+    /// Called after a shot by `shooter` at `target` is fired successfully. The game has
+    /// already moved on to the next shot.
+    fn shot_fired(
+        &mut self,
+        _game: &Game<P, I, D>,
+        _shooter: &P,
+        _target: &P,
+        _coord: &D::Coordinate,
+        _outcome: &ShotOutcome<I>,
+    ) {
+    }
+
+    /// Called when a move chosen for `player` was rejected by the game.
+    fn move_rejected(
+        &mut self,
+        _game: &Game<P, I, D>,
+        _player: &P,
+        _error: &ShotError<P, D::Coordinate>,
+    ) {
+    }
+
+    /// Number of times in a row a player may retry after an invalid move before they
+    /// forfeit. Defaults to 3.
+    fn max_retries(&self) -> usize {
+        3
+    }
+
+    /// Called after `player` forfeits by running out of retries.
+    fn forfeited(&mut self, _game: &Game<P, I, D>, _player: &P) {}
+}
+
+impl<P, I, D, M> MoveProvider<P, I, D> for &mut M
+where
+    P: PlayerId,
+    I: ShipId,
+    D: Dimensions,
+    M: MoveProvider<P, I, D> + ?Sized,
+{
+    fn choose_move(&mut self, game: &Game<P, I, D>) -> (P, D::Coordinate) {
+        (**self).choose_move(game)
+    }
+
+    fn shot_fired(
+        &mut self,
+        game: &Game<P, I, D>,
+        shooter: &P,
+        target: &P,
+        coord: &D::Coordinate,
+        outcome: &ShotOutcome<I>,
+    ) {
+        (**self).shot_fired(game, shooter, target, coord, outcome)
+    }
+
+    fn move_rejected(
+        &mut self,
+        game: &Game<P, I, D>,
+        player: &P,
+        error: &ShotError<P, D::Coordinate>,
+    ) {
+        (**self).move_rejected(game, player, error)
+    }
+
+    fn max_retries(&self) -> usize {
+        (**self).max_retries()
+    }
+
+    fn forfeited(&mut self, game: &Game<P, I, D>, player: &P) {
+        (**self).forfeited(game, player)
+    }
+}
+
+/// Play the game until there is a winner, getting each move from the provider, and
+/// return the winner. When a move is rejected, the same player is asked again, until
+/// they have made more than [`max_retries`][MoveProvider::max_retries] invalid moves in
+/// a row, at which point they forfeit and all of their ships are sunk.
+///
+/// Panics if the game is paused, since every move would be rejected.
+pub fn drive<P, I, D>(game: &mut Game<P, I, D>, mut provider: impl MoveProvider<P, I, D>) -> P
+where
+    P: PlayerId,
+    I: ShipId,
+    D: Dimensions,
+{
+    assert!(!game.state().is_paused(), "cannot drive a paused game");
+    let mut rejected = 0;
+    while game.winner().is_none() {
+        let shooter = game.current().clone();
+        let (target, coord) = provider.choose_move(game);
+        match game.shoot(target.clone(), coord.clone()) {
+            Ok(outcome) => {
+                rejected = 0;
+                provider.shot_fired(game, &shooter, &target, &coord, &outcome);
+            }
+            Err(err) => {
+                provider.move_rejected(game, &shooter, &err);
+                rejected += 1;
+                if rejected > provider.max_retries() {
+                    rejected = 0;
+                    game.forfeit_current();
+                    provider.forfeited(game, &shooter);
+                }
+            }
+        }
+    }
+    game.winner().unwrap().clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        board::rectangular::RectDimensions,
+        fixtures::{self, UniformGame},
+        game::{
+            simple::{Coordinate, Ship},
+            uniform::CannotShootReason,
+        },
+    };
+    use std::collections::VecDeque;
+
+    /// Provider which plays a fixed list of moves and records what it is told.
+    #[derive(Default)]
+    struct Scripted {
+        moves: VecDeque<(usize, Coordinate)>,
+        /// Shooter, target and coordinate of each fired shot, and whether it won the game.
+        fired: Vec<(usize, usize, Coordinate, bool)>,
+        rejected: Vec<(usize, CannotShootReason)>,
+        forfeited: Vec<usize>,
+    }
+
+    impl Scripted {
+        fn new(moves: impl IntoIterator<Item = (usize, (usize, usize))>) -> Self {
+            Self {
+                moves: moves
+                    .into_iter()
+                    .map(|(target, (x, y))| (target, Coordinate::new(x, y)))
+                    .collect(),
+                ..Self::default()
+            }
+        }
+    }
+
+    impl MoveProvider<usize, Ship, RectDimensions> for Scripted {
+        fn choose_move(&mut self, _game: &UniformGame) -> (usize, Coordinate) {
+            self.moves.pop_front().expect("script ran out of moves")
+        }
+
+        fn shot_fired(
+            &mut self,
+            _game: &UniformGame,
+            shooter: &usize,
+            target: &usize,
+            coord: &Coordinate,
+            outcome: &ShotOutcome<Ship>,
+        ) {
+            self.fired.push((
+                *shooter,
+                *target,
+                *coord,
+                matches!(outcome, ShotOutcome::Victory(_)),
+            ));
+        }
+
+        fn move_rejected(
+            &mut self,
+            _game: &UniformGame,
+            player: &usize,
+            error: &ShotError<usize, Coordinate>,
+        ) {
+            self.rejected.push((*player, error.reason()));
+        }
+
+        fn forfeited(&mut self, _game: &UniformGame, player: &usize) {
+            self.forfeited.push(*player);
+        }
+    }
+
+    #[test]
+    fn retries_after_invalid_moves() {
+        let mut game = fixtures::uniform_near_victory();
+        let mut provider = Scripted::new(vec![(0, (0, 0)), (1, (0, 0)), (1, (1, 8))]);
+        assert_eq!(drive(&mut game, &mut provider), 0);
+        assert!(provider.moves.is_empty());
+        assert_eq!(
+            provider.rejected,
+            [
+                (0, CannotShootReason::SelfShot),
+                (0, CannotShootReason::AlreadyShot),
+            ]
+        );
+        assert_eq!(provider.fired, [(0, 1, Coordinate::new(1, 8), true)]);
+        assert!(provider.forfeited.is_empty());
+    }
+
+    #[test]
+    fn forfeits_after_max_retries() {
+        let mut game = fixtures::uniform_near_victory();
+        let mut provider = Scripted::new(vec![(0, (0, 0)); 4]);
+        assert_eq!(drive(&mut game, &mut provider), 1);
+        assert_eq!(provider.rejected, vec![(0, CannotShootReason::SelfShot); 4]);
+        assert_eq!(provider.forfeited, [0]);
+        assert!(provider.fired.is_empty());
+        assert!(game.get_board(&0).unwrap().defeated());
+    }
+}
@@ -13,15 +13,35 @@
 // limitations under the License.
 
 //! Implementation of the basic game of battleship with two players and five ships on a
-//! 10x10 grid.
-use std::{cmp::Ordering, ops::Deref};
+//! 10x10 grid. Games may also be set up with only some of the five ships.
+use std::{
+    cmp::Ordering,
+    collections::HashSet,
+    iter::Rev,
+    ops::{Deref, Not},
+    slice,
+};
 
+use enumflags2::BitFlags;
+#[cfg(feature = "rng_gen")]
+use rand::Rng;
 use thiserror::Error;
 
-pub use crate::board::rectangular::Coordinate;
+#[cfg(feature = "rng_gen")]
+pub mod ai;
+
+pub use crate::{
+    board::rectangular::Coordinate,
+    game::uniform::{GameState, RepeatShotPolicy},
+};
 use crate::{
-    board::{self, rectangular::RectDimensions, BoardSetup},
-    game::uniform,
+    board::{
+        self,
+        rectangular::{RectDimensions, Wrapping},
+        BoardSetup,
+    },
+    game::{annotations::AnnotationMap, uniform},
+    lexicon::Lexicon,
     ships::{Line, ShapeProjection},
 };
 
@@ -37,6 +57,14 @@ pub enum Player {
     P2,
 }
 
+impl Default for Player {
+    /// The default player is `P1`, since `P1` is always the first player in the turn
+    /// order of the simple game.
+    fn default() -> Self {
+        Player::P1
+    }
+}
+
 impl Player {
     /// Get the oponent of this player.
     pub fn opponent(self) -> Self {
@@ -47,6 +75,15 @@ impl Player {
     }
 }
 
+impl Not for Player {
+    type Output = Self;
+
+    /// Get the opponent of this player, same as [`Player::opponent`].
+    fn not(self) -> Self {
+        self.opponent()
+    }
+}
+
 /// Ship ID for the simple game.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum Ship {
@@ -78,6 +115,7 @@ impl Ship {
     ];
 
     /// Get the length of this ship type.
+    #[allow(clippy::len_without_is_empty)]
     pub fn len(self) -> usize {
         match self {
             Ship::Carrier => 5,
@@ -89,8 +127,94 @@ impl Ship {
     }
 }
 
+/// English [`Lexicon`] for the simple game. Parses the standard ship names and the
+/// abbreviations `cv`, `bb`, `ca`/`cl`, `ss`/`sub`, and `dd`.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct English;
+
+impl Lexicon<Ship> for English {
+    fn ship_name(&self, ship: &Ship) -> String {
+        match ship {
+            Ship::Carrier => "carrier",
+            Ship::Battleship => "battleship",
+            Ship::Cruiser => "cruiser",
+            Ship::Submarine => "submarine",
+            Ship::Destroyer => "destroyer",
+        }
+        .to_owned()
+    }
+
+    fn parse_ship(&self, name: &str) -> Option<Ship> {
+        match name {
+            "cv" | "carrier" => Some(Ship::Carrier),
+            "bb" | "battleship" => Some(Ship::Battleship),
+            "ca" | "cl" | "cruiser" => Some(Ship::Cruiser),
+            "ss" | "sub" | "submarine" => Some(Ship::Submarine),
+            "dd" | "destroyer" => Some(Ship::Destroyer),
+            _ => None,
+        }
+    }
+}
+
+/// Spanish [`Lexicon`] for the simple game. Parses the Spanish ship names, with or
+/// without accents, and the abbreviations `pa`, `ac`, `cr`, `sm`, and `de`.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct Spanish;
+
+impl Lexicon<Ship> for Spanish {
+    fn ship_name(&self, ship: &Ship) -> String {
+        match ship {
+            Ship::Carrier => "portaaviones",
+            Ship::Battleship => "acorazado",
+            Ship::Cruiser => "crucero",
+            Ship::Submarine => "submarino",
+            Ship::Destroyer => "destructor",
+        }
+        .to_owned()
+    }
+
+    fn parse_ship(&self, name: &str) -> Option<Ship> {
+        match name {
+            "pa" | "portaaviones" => Some(Ship::Carrier),
+            "ac" | "acorazado" => Some(Ship::Battleship),
+            "cr" | "crucero" => Some(Ship::Cruiser),
+            "sm" | "submarino" => Some(Ship::Submarine),
+            "de" | "destructor" => Some(Ship::Destroyer),
+            _ => None,
+        }
+    }
+
+    fn miss(&self) -> String {
+        "Agua.".to_owned()
+    }
+
+    fn hit(&self, ship: &Ship) -> String {
+        format!("¡Tocado: {}!", self.ship_name(ship))
+    }
+
+    fn sunk(&self, ship: &Ship) -> String {
+        format!("¡Hundido: {}!", self.ship_name(ship))
+    }
+
+    fn defeated(&self, ship: &Ship) -> String {
+        format!(
+            "¡Hundido: {}! Todos los barcos hundidos.",
+            self.ship_name(ship)
+        )
+    }
+
+    fn victory(&self, ship: &Ship) -> String {
+        format!("¡Hundido: {}! ¡Victoria!", self.ship_name(ship))
+    }
+
+    fn wasted(&self) -> String {
+        "Esa posición ya fue atacada. Disparo perdido.".to_owned()
+    }
+}
+
 /// Reason why a ship could not be placed at a given position.
 #[derive(Debug, Error, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum CannotPlaceReason {
     /// The ship did not fit in the given direction.
     #[error("insufficient space for the ship at the specified position")]
@@ -101,35 +225,147 @@ pub enum CannotPlaceReason {
     /// The space selected overlaps a ship that was already placed.
     #[error("the specified position was already occupied")]
     AlreadyOccupied,
+    /// The cells given do not form a straight line of the ship's length.
+    #[error("the specified cells do not form a straight line of the ship's length")]
+    InvalidCells,
+    /// The ship is not part of the fleet this game was set up with.
+    #[error("the specified ship is not part of the fleet")]
+    NotInFleet,
 }
 
-/// Placement orientation of a ship.
+impl CannotPlaceReason {
+    /// Returns true if this is [`CannotPlaceReason::InsufficientSpace`].
+    pub fn is_insufficient_space(&self) -> bool {
+        matches!(self, CannotPlaceReason::InsufficientSpace)
+    }
+
+    /// Returns true if this is [`CannotPlaceReason::AlreadyPlaced`].
+    pub fn is_already_placed(&self) -> bool {
+        matches!(self, CannotPlaceReason::AlreadyPlaced)
+    }
+
+    /// Returns true if this is [`CannotPlaceReason::AlreadyOccupied`].
+    pub fn is_already_occupied(&self) -> bool {
+        matches!(self, CannotPlaceReason::AlreadyOccupied)
+    }
+
+    /// Returns true if this is [`CannotPlaceReason::InvalidCells`].
+    pub fn is_invalid_cells(&self) -> bool {
+        matches!(self, CannotPlaceReason::InvalidCells)
+    }
+
+    /// Returns true if this is [`CannotPlaceReason::NotInFleet`].
+    pub fn is_not_in_fleet(&self) -> bool {
+        matches!(self, CannotPlaceReason::NotInFleet)
+    }
+}
+
+/// Reason why a game could not be set up with a given fleet.
+#[derive(Debug, Error, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum FleetError {
+    /// The fleet has no ships.
+    #[error("the fleet has no ships")]
+    Empty,
+    /// The fleet lists the given ship more than once. Ships are identified by their type
+    /// in the simple game, so each type can appear at most once. Use the
+    /// [`uniform`] game with a custom ship ID to have several ships of the same type.
+    #[error("the fleet has more than one {0:?}")]
+    Duplicate(Ship),
+}
+
+impl FleetError {
+    /// Returns true if this is [`FleetError::Empty`].
+    pub fn is_empty(&self) -> bool {
+        matches!(self, FleetError::Empty)
+    }
+
+    /// Returns true if this is [`FleetError::Duplicate`].
+    pub fn is_duplicate(&self) -> bool {
+        matches!(self, FleetError::Duplicate(_))
+    }
+}
+
+/// Placement orientation of a ship: the direction the ship extends from its start
+/// coordinate. Orientations are named as the board is displayed, with row 0 at the top
+/// and column 0 on the left.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum Orientation {
+    /// Toward row 0 (decreasing `y`).
     Up,
+    /// Away from row 0 (increasing `y`).
     Down,
+    /// Toward column 0 (decreasing `x`).
     Left,
+    /// Away from column 0 (increasing `x`).
     Right,
 }
 
 impl Orientation {
-    /// Check if the given projection is pointed along this orientation.
-    fn check_dir(self, proj: &ShapeProjection<Coordinate>) -> bool {
-        if proj.len() < 2 {
-            // None of the current ships should have len 1, but support it here anyway.
-            true
-        } else {
-            let dx = proj[0].x.cmp(&proj[1].x);
-            let dy = proj[0].y.cmp(&proj[1].y);
-            match (self, dx, dy) {
-                (Orientation::Up, Ordering::Equal, Ordering::Greater) => true,
-                (Orientation::Down, Ordering::Equal, Ordering::Less) => true,
-                (Orientation::Left, Ordering::Greater, Ordering::Equal) => true,
-                (Orientation::Right, Ordering::Less, Ordering::Equal) => true,
-                _ => false,
-            }
+    /// Get a slice containing a list of all orientations.
+    pub const ALL: &'static [Orientation] = &[
+        Orientation::Up,
+        Orientation::Down,
+        Orientation::Left,
+        Orientation::Right,
+    ];
+
+    /// Get the unit step `(dx, dy)` for this orientation, using the same axes as the
+    /// board: Up is `(0, -1)` and Right is `(1, 0)`.
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Orientation::Up => (0, -1),
+            Orientation::Down => (0, 1),
+            Orientation::Left => (-1, 0),
+            Orientation::Right => (1, 0),
         }
     }
+
+    /// Build the projection of a ship of the given length which starts at `start` and
+    /// extends in this direction. The projection is computed directly with
+    /// [`Line::project_in_direction`] rather than searched for, so it is unique and
+    /// doesn't depend on the board's contents. On axes where the board wraps, the ship
+    /// continues from the opposite edge. Returns `None` if the ship would leave the board,
+    /// or would wrap onto itself.
+    fn project(
+        self,
+        start: Coordinate,
+        len: usize,
+        dim: &RectDimensions,
+    ) -> Option<ShapeProjection<Coordinate>> {
+        let (dx, dy) = self.delta();
+        let axis = |pos: usize, delta: isize, size: usize, wrap: bool| {
+            let pos = pos as isize + delta;
+            if wrap {
+                Some(pos.rem_euclid(size as isize) as usize)
+            } else if pos < 0 {
+                None
+            } else {
+                Some(pos as usize)
+            }
+        };
+        let next = match (
+            axis(start.x, dx, dim.width(), dim.wrap_x()),
+            axis(start.y, dy, dim.height(), dim.wrap_y()),
+        ) {
+            (Some(x), Some(y)) => Coordinate::new(x, y),
+            // The first step leaves the board. That only matters for ships longer than
+            // one cell, which `start` isn't a neighbor of, so they are still rejected.
+            _ => start,
+        };
+        Line::new(len).project_in_direction(start, next, dim)
+    }
+}
+
+/// Source of randomness for placing ships randomly. When the `rng_gen` feature is
+/// enabled, this is implemented for every [`rand::Rng`], but it can also be implemented
+/// directly to use a different RNG or a deterministic sequence.
+pub trait RandomSource {
+    /// Pick a coordinate within the given dimensions.
+    fn gen_coord(&mut self, dim: &RectDimensions) -> Coordinate;
+
+    /// Pick an orientation.
+    fn gen_orientation(&mut self) -> Orientation;
 }
 
 /// Represents a placement of a ship. Allows extracting the orientation and start, as well
@@ -146,8 +382,17 @@ impl Placement {
             // None of the current ships are less than 2 len, but we can handle it anyway.
             Orientation::Up
         } else {
-            let dx = self[0].x.cmp(&self[1].x);
-            let dy = self[0].y.cmp(&self[1].y);
+            // Adjacent cells are one apart, unless the ship crosses the seam of a wrapping
+            // board, where the step jumps across the whole board the other way.
+            let step = |from: usize, to: usize| match to as isize - from as isize {
+                0 => Ordering::Equal,
+                1 => Ordering::Less,
+                -1 => Ordering::Greater,
+                jump if jump < 0 => Ordering::Less,
+                _ => Ordering::Greater,
+            };
+            let dx = step(self[0].x, self[1].x);
+            let dy = step(self[0].y, self[1].y);
             match (dx, dy) {
                 (Ordering::Equal, Ordering::Greater) => Orientation::Up,
                 (Ordering::Equal, Ordering::Less) => Orientation::Down,
@@ -160,6 +405,12 @@ impl Placement {
         }
     }
 
+    /// Get the unit step `(dx, dy)` from each cell of this placement to the next. See
+    /// [`Orientation::delta`].
+    pub fn direction_delta(&self) -> (isize, isize) {
+        self.orientation().delta()
+    }
+
     /// Get the coordinate where this placement starts.
     pub fn start(&self) -> &Coordinate {
         // This will panic if len is 0. That's OK because this type has no public
@@ -167,6 +418,30 @@ impl Placement {
         // 0 length.
         &self[0]
     }
+
+    /// Get the coordinate where this placement ends, which is its last cell.
+    pub fn end(&self) -> &Coordinate {
+        // Placements are never empty, see start.
+        &self[self.len() - 1]
+    }
+
+    /// Get the coordinate of the ship's stern. Ships point in their orientation, so the
+    /// bow is the [`start`][Placement::start] and the stern is the last cell, the same as
+    /// [`end`][Placement::end].
+    pub fn stern(&self) -> &Coordinate {
+        self.end()
+    }
+
+    /// Iterate the cells of this placement from the bow to the stern, which is the order
+    /// the placement is stored in.
+    pub fn iter_bow_to_stern(&self) -> slice::Iter<'_, Coordinate> {
+        self.iter()
+    }
+
+    /// Iterate the cells of this placement from the stern to the bow.
+    pub fn iter_stern_to_bow(&self) -> Rev<slice::Iter<'_, Coordinate>> {
+        self.iter().rev()
+    }
 }
 
 impl Deref for Placement {
@@ -183,27 +458,49 @@ pub struct GameSetup(uniform::GameSetup<Player, Ship, RectDimensions, Line>);
 impl GameSetup {
     /// Create a [`GameSetup`] for the game, including two players with one of each ship.
     pub fn new() -> Self {
+        Self::with_ships(Ship::ALL, RectDimensions::new(10, 10))
+    }
+
+    /// Create a [`GameSetup`] like [`new`][GameSetup::new], but where both boards wrap
+    /// along the given axes, so ships can be placed across the seam.
+    pub fn new_wrapping<B: Into<BitFlags<Wrapping>>>(wrapping: B) -> Self {
+        Self::with_ships(Ship::ALL, RectDimensions::new_wrapping(10, 10, wrapping))
+    }
+
+    /// Create a [`GameSetup`] for the game where both players have one of each of the
+    /// given ships. Ship types which aren't listed are left out of the game.
+    ///
+    /// Each type of ship can appear at most once. The simple game identifies ships by
+    /// their type everywhere, from [`place_ship`][GameSetup::place_ship] to the ship named
+    /// in a [`ShotOutcome`], so two ships of one type couldn't be told apart. For a fleet
+    /// with repeated types, use [`uniform::GameSetup`] with a ship ID that includes an
+    /// instance number, such as `(Ship, u8)`.
+    pub fn new_with_fleet(fleet: &[Ship]) -> Result<Self, FleetError> {
+        for (i, ship) in fleet.iter().enumerate() {
+            if fleet[..i].contains(ship) {
+                return Err(FleetError::Duplicate(*ship));
+            }
+        }
+        if fleet.is_empty() {
+            return Err(FleetError::Empty);
+        }
+        Ok(Self::with_ships(fleet, RectDimensions::new(10, 10)))
+    }
+
+    /// Create a [`GameSetup`] where both players have one of each of the given ships, on
+    /// boards with the given dimensions.
+    fn with_ships(ships: &[Ship], dim: RectDimensions) -> Self {
         let mut setup = uniform::GameSetup::new();
-        Self::add_ships(
-            setup
-                .add_player(Player::P1, RectDimensions::new(10, 10))
-                .unwrap(),
-        );
-        Self::add_ships(
-            setup
-                .add_player(Player::P2, RectDimensions::new(10, 10))
-                .unwrap(),
-        );
+        Self::add_ships(ships, setup.add_player(Player::P1, dim).unwrap());
+        Self::add_ships(ships, setup.add_player(Player::P2, dim).unwrap());
         GameSetup(setup)
     }
 
     /// Add the initial ships for the player.
-    fn add_ships(board: &mut BoardSetup<Ship, RectDimensions, Line>) {
-        Self::add_ship(Ship::Carrier, board);
-        Self::add_ship(Ship::Battleship, board);
-        Self::add_ship(Ship::Cruiser, board);
-        Self::add_ship(Ship::Submarine, board);
-        Self::add_ship(Ship::Destroyer, board);
+    fn add_ships(ships: &[Ship], board: &mut BoardSetup<Ship, RectDimensions, Line>) {
+        for &ship in ships {
+            Self::add_ship(ship, board);
+        }
     }
 
     /// Add the given ship to the board.
@@ -213,6 +510,7 @@ impl GameSetup {
 
     /// Tries to start the game. If all players are ready, returns a [`Game`], otherwise
     /// returns self.
+    #[allow(clippy::result_large_err)]
     pub fn start(self) -> Result<Game, Self> {
         match self.0.start() {
             Ok(game) => Ok(Game(game)),
@@ -220,6 +518,35 @@ impl GameSetup {
         }
     }
 
+    /// Get a reference to the underlying [`uniform::GameSetup`], for features that the
+    /// simple API doesn't expose. There is no mutable access, since changing the players
+    /// or fleets would break the assumptions of the simple wrapper.
+    pub fn as_uniform(&self) -> &uniform::GameSetup<Player, Ship, RectDimensions, Line> {
+        &self.0
+    }
+
+    /// Unwrap the underlying [`uniform::GameSetup`], to continue setup with the full
+    /// uniform API. The setup can't be converted back.
+    pub fn into_uniform(self) -> uniform::GameSetup<Player, Ship, RectDimensions, Line> {
+        self.0
+    }
+
+    /// Get the dimensions of the boards, which are the same for both players.
+    pub fn dimensions(&self) -> &RectDimensions {
+        self.0.get_board(&Player::P1).unwrap().dimensions()
+    }
+
+    /// Get the policy for shots at cells which were already shot.
+    pub fn repeat_shot_policy(&self) -> RepeatShotPolicy {
+        self.0.repeat_shot_policy()
+    }
+
+    /// Set the policy for shots at cells which were already shot. Defaults to
+    /// [`RepeatShotPolicy::Reject`].
+    pub fn set_repeat_shot_policy(&mut self, policy: RepeatShotPolicy) {
+        self.0.set_repeat_shot_policy(policy)
+    }
+
     /// Return true if both players are ready to start the game.
     pub fn ready(&self) -> bool {
         self.0.ready()
@@ -253,13 +580,13 @@ impl GameSetup {
             })
     }
 
-    /// Get the the coordinates where the given ship is placed, if any.
+    /// Get the the coordinates where the given ship is placed, if any. Returns `None` if
+    /// the ship isn't part of the fleet.
     pub fn get_placement(&self, player: Player, ship: Ship) -> Option<&Placement> {
         self.0
             .get_board(&player)
             .unwrap()
-            .get_ship(ship)
-            .unwrap()
+            .get_ship(&ship)?
             .placement()
             .map(|v| Placement::from_coords(v))
     }
@@ -273,18 +600,68 @@ impl GameSetup {
         start: Coordinate,
         dir: Orientation,
     ) -> Result<(), CannotPlaceReason> {
-        let board = self.0.get_board(&player).unwrap();
-        let ship = board.get_ship(ship).unwrap();
-        let proj = ship
-            .get_placements(start)
-            .find(|proj| dir.check_dir(proj))
+        self.resolve_placement(player, ship, start, dir).map(|_| ())
+    }
+
+    /// Get the cells the specified ship would occupy if placed at the specified position,
+    /// in order from `start`, without placing it. Fails for the same reasons as
+    /// [`place_ship`][GameSetup::place_ship]. The cells can be shown to the player and
+    /// then committed with [`place_ship_cells`][GameSetup::place_ship_cells].
+    pub fn resolve_placement(
+        &self,
+        player: Player,
+        ship: Ship,
+        start: Coordinate,
+        dir: Orientation,
+    ) -> Result<Vec<Coordinate>, CannotPlaceReason> {
+        let proj = dir
+            .project(start, ship.len(), self.dimensions())
             .ok_or(CannotPlaceReason::InsufficientSpace)?;
-        ship.check_placement(&proj).map_err(|err| match err {
-            board::CannotPlaceReason::AlreadyOccupied => CannotPlaceReason::AlreadyOccupied,
-            board::CannotPlaceReason::AlreadyPlaced => CannotPlaceReason::AlreadyPlaced,
-            // We will never provide an invalid projection.
-            board::CannotPlaceReason::InvalidProjection => unreachable!(),
-        })
+        let board = self.0.get_board(&player).unwrap();
+        let ship = board.get_ship(&ship).ok_or(CannotPlaceReason::NotInFleet)?;
+        ship.check_placement(&proj)
+            .map(|()| proj)
+            .map_err(Self::resolved_place_reason)
+    }
+
+    /// Suggest up to `limit` valid positions for the specified ship, closest first, for
+    /// when placing it at the requested position failed. Distance is the number of rows
+    /// plus the number of columns from `near` to the suggested start. A set of cells is
+    /// only suggested once, from whichever end is closer. Returns an empty list if the
+    /// ship doesn't fit anywhere, is already placed, or isn't part of the fleet.
+    pub fn suggest_placements(
+        &self,
+        player: Player,
+        ship: Ship,
+        near: Coordinate,
+        limit: usize,
+    ) -> Vec<(Coordinate, Orientation)> {
+        let mut candidates: Vec<_> = self
+            .dimensions()
+            .iter_coordinates()
+            .flatten()
+            .flat_map(|start| Orientation::ALL.iter().map(move |&dir| (start, dir)))
+            .filter_map(|(start, dir)| {
+                let cells = self.resolve_placement(player, ship, start, dir).ok()?;
+                let distance = start.x.abs_diff(near.x) + start.y.abs_diff(near.y);
+                Some((distance, start, dir, cells))
+            })
+            .collect();
+        // Stable, so ties stay in row-major order.
+        candidates.sort_by_key(|&(distance, ..)| distance);
+        let mut seen = HashSet::new();
+        candidates
+            .into_iter()
+            .filter(|(_, _, _, cells)| {
+                // The same cells seen from either end. Compare every cell rather than the
+                // ends, since on a wrapping board two placements can share both ends.
+                let mut key: Vec<_> = cells.iter().map(|c| (c.y, c.x)).collect();
+                key.sort_unstable();
+                seen.insert(key)
+            })
+            .take(limit)
+            .map(|(_, start, dir, _)| (start, dir))
+            .collect()
     }
 
     /// Try to place the specified ship at the specified position, returning an
@@ -296,29 +673,82 @@ impl GameSetup {
         start: Coordinate,
         dir: Orientation,
     ) -> Result<(), CannotPlaceReason> {
-        let board = self.0.get_board_mut(&player).unwrap();
-        let mut ship = board.get_ship_mut(ship).unwrap();
-        let proj = ship
-            .get_placements(start)
-            .find(|proj| dir.check_dir(proj))
+        let proj = dir
+            .project(start, ship.len(), self.dimensions())
             .ok_or(CannotPlaceReason::InsufficientSpace)?;
-        ship.place(proj).map_err(|err| match err.reason() {
+        let board = self.0.get_board_mut(&player).unwrap();
+        let mut ship = board
+            .get_ship_mut(&ship)
+            .ok_or(CannotPlaceReason::NotInFleet)?;
+        ship.place(proj)
+            .map_err(|err| Self::resolved_place_reason(err.reason()))
+    }
+
+    /// Convert the reason a projection built by [`Orientation::project`] couldn't be
+    /// placed.
+    fn resolved_place_reason(reason: board::CannotPlaceReason) -> CannotPlaceReason {
+        match reason {
             board::CannotPlaceReason::AlreadyOccupied => CannotPlaceReason::AlreadyOccupied,
             board::CannotPlaceReason::AlreadyPlaced => CannotPlaceReason::AlreadyPlaced,
-            // We will never provide an invalid projection.
+            // Projections built from an orientation are always straight lines of the
+            // ship's length.
             board::CannotPlaceReason::InvalidProjection => unreachable!(),
+            // The simple game never locks boards.
+            board::CannotPlaceReason::Locked => unreachable!(),
+        }
+    }
+
+    /// Try to place the specified ship on exactly the given cells, in order from one end
+    /// of the ship to the other. The cells must form a straight, contiguous line with the
+    /// same length as the ship.
+    pub fn place_ship_cells(
+        &mut self,
+        player: Player,
+        ship: Ship,
+        cells: Vec<Coordinate>,
+    ) -> Result<(), CannotPlaceReason> {
+        let board = self.0.get_board_mut(&player).unwrap();
+        let mut ship = board
+            .get_ship_mut(&ship)
+            .ok_or(CannotPlaceReason::NotInFleet)?;
+        ship.place(cells).map_err(|err| match err.reason() {
+            board::CannotPlaceReason::AlreadyOccupied => CannotPlaceReason::AlreadyOccupied,
+            board::CannotPlaceReason::AlreadyPlaced => CannotPlaceReason::AlreadyPlaced,
+            board::CannotPlaceReason::InvalidProjection => CannotPlaceReason::InvalidCells,
+            // The simple game never locks boards.
+            board::CannotPlaceReason::Locked => unreachable!(),
         })
     }
 
+    /// Place all of the specified player's un-placed ships at random positions, retrying
+    /// each ship until it fits. Ships which are already placed are left where they are.
+    /// The source must eventually produce a position where each ship fits, or this will
+    /// not return. Ships are placed in the order of [`Ship::ALL`], so a seeded source
+    /// always produces the same layout.
+    pub fn randomize_placements(&mut self, player: Player, source: &mut impl RandomSource) {
+        let dim = *self.dimensions();
+        let mut pending: Vec<_> = self.get_pending_ships(player).collect();
+        // The fleet is stored in a HashMap, whose iteration order changes between runs.
+        pending.sort_by_key(|ship| Ship::ALL.iter().position(|s| s == ship));
+        for ship in pending {
+            loop {
+                let start = source.gen_coord(&dim);
+                let dir = source.gen_orientation();
+                if self.place_ship(player, ship, start, dir).is_ok() {
+                    break;
+                }
+            }
+        }
+    }
+
     /// Clear the placement of the specified ship. Return true if the ship was previously
-    /// placed.
+    /// placed, and false if it wasn't placed or isn't part of the fleet.
     pub fn unplace_ship(&mut self, player: Player, ship: Ship) -> bool {
         self.0
             .get_board_mut(&player)
             .unwrap()
-            .get_ship_mut(ship)
-            .unwrap()
-            .unplace()
+            .get_ship_mut(&ship)
+            .and_then(|mut ship| ship.unplace().ok().flatten())
             .is_some()
     }
 
@@ -336,17 +766,29 @@ impl GameSetup {
     }
 }
 
+impl Default for GameSetup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Reason why a shot at the board failed.
 #[derive(Debug, Error, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum CannotShootReason {
     /// The game is already over
     #[error("the game is already over")]
     AlreadyOver,
 
-    /// The target player is the player whose turn it is.
+    /// The shot was out of turn. No longer returned by [`Game::shoot`], which reports
+    /// [`CannotShootReason::SelfTarget`] when the current player targets themselves.
     #[error("player attempted to shoot out of turn")]
     OutOfTurn,
 
+    /// The target player is the player whose turn it is.
+    #[error("players cannot shoot their own board")]
+    SelfTarget,
+
     /// The specified cell is out of bounds for the grid.
     #[error("the target coordinate is out of bounds")]
     OutOfBounds,
@@ -354,10 +796,57 @@ pub enum CannotShootReason {
     /// The specified cell has already been shot.
     #[error("the target cell was already shot")]
     AlreadyShot,
+
+    /// The target player is not part of the game. Both players are always present, so
+    /// this is reported instead of panicking if the underlying game ever disagrees.
+    #[error("the target player is not part of the game")]
+    UnknownPlayer,
+
+    /// The game is paused.
+    #[error("the game is paused")]
+    Paused,
+}
+
+impl CannotShootReason {
+    /// Returns true if this is [`CannotShootReason::AlreadyOver`].
+    pub fn is_already_over(&self) -> bool {
+        matches!(self, CannotShootReason::AlreadyOver)
+    }
+
+    /// Returns true if this is [`CannotShootReason::OutOfTurn`].
+    pub fn is_out_of_turn(&self) -> bool {
+        matches!(self, CannotShootReason::OutOfTurn)
+    }
+
+    /// Returns true if this is [`CannotShootReason::SelfTarget`].
+    pub fn is_self_target(&self) -> bool {
+        matches!(self, CannotShootReason::SelfTarget)
+    }
+
+    /// Returns true if this is [`CannotShootReason::OutOfBounds`].
+    pub fn is_out_of_bounds(&self) -> bool {
+        matches!(self, CannotShootReason::OutOfBounds)
+    }
+
+    /// Returns true if this is [`CannotShootReason::AlreadyShot`].
+    pub fn is_already_shot(&self) -> bool {
+        matches!(self, CannotShootReason::AlreadyShot)
+    }
+
+    /// Returns true if this is [`CannotShootReason::UnknownPlayer`].
+    pub fn is_unknown_player(&self) -> bool {
+        matches!(self, CannotShootReason::UnknownPlayer)
+    }
+
+    /// Returns true if this is [`CannotShootReason::Paused`].
+    pub fn is_paused(&self) -> bool {
+        matches!(self, CannotShootReason::Paused)
+    }
 }
 
 /// Outcome of a successfully-fired shot.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum ShotOutcome {
     /// Nothing was hit.
     Miss,
@@ -367,6 +856,76 @@ pub enum ShotOutcome {
     Sunk(Ship),
     /// The given ship was hit and sunk, and the target player has no remaining ships.
     Victory(Ship),
+    /// The cell was already shot, so the shot was wasted. Only produced under
+    /// [`RepeatShotPolicy::Waste`].
+    Wasted,
+}
+
+impl ShotOutcome {
+    /// Returns true if this is [`ShotOutcome::Miss`].
+    pub fn is_miss(&self) -> bool {
+        matches!(self, ShotOutcome::Miss)
+    }
+
+    /// Returns true if this is [`ShotOutcome::Hit`].
+    pub fn is_hit(&self) -> bool {
+        matches!(self, ShotOutcome::Hit(_))
+    }
+
+    /// Returns true if this is [`ShotOutcome::Sunk`].
+    pub fn is_sunk(&self) -> bool {
+        matches!(self, ShotOutcome::Sunk(_))
+    }
+
+    /// Returns true if this is [`ShotOutcome::Victory`].
+    pub fn is_victory(&self) -> bool {
+        matches!(self, ShotOutcome::Victory(_))
+    }
+
+    /// Returns true if this is [`ShotOutcome::Wasted`].
+    pub fn is_wasted(&self) -> bool {
+        matches!(self, ShotOutcome::Wasted)
+    }
+
+    /// Describe this outcome as a human-readable string using the given [`Lexicon`],
+    /// such as [`English`] or [`Spanish`].
+    pub fn describe_in(&self, lexicon: &(impl Lexicon<Ship> + ?Sized)) -> String {
+        match self {
+            ShotOutcome::Miss => lexicon.miss(),
+            ShotOutcome::Hit(ship) => lexicon.hit(ship),
+            ShotOutcome::Sunk(ship) => lexicon.sunk(ship),
+            ShotOutcome::Victory(ship) => lexicon.victory(ship),
+            ShotOutcome::Wasted => lexicon.wasted(),
+        }
+    }
+}
+
+/// Mid-game position to start a [`Game`] from, such as a puzzle where the player must
+/// finish off their opponent.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Scenario {
+    /// Placement of every ship for both players.
+    pub placements: Vec<(Player, Ship, Coordinate, Orientation)>,
+    /// Cells which have already been shot on each player's board, in the order they were
+    /// shot.
+    pub damage: Vec<(Player, Coordinate)>,
+    /// Player whose turn it is.
+    pub current: Player,
+}
+
+/// Error returned when a [`Scenario`] can't be loaded.
+#[derive(Debug, Error, Copy, Clone, Eq, PartialEq)]
+pub enum ScenarioError {
+    /// One of the ships could not be placed.
+    #[error("could not place {0:?}'s {1:?}: {2}")]
+    Placement(Player, Ship, CannotPlaceReason),
+    /// The player has ships which were not placed.
+    #[error("player {0:?} has ships which were not placed")]
+    Unplaced(Player),
+    /// One of the cells could not be shot. [`CannotShootReason::AlreadyOver`] means the
+    /// damage would sink all of the player's ships.
+    #[error("could not damage {0:?}'s board at {1:?}: {2}")]
+    Damage(Player, Coordinate, CannotShootReason),
 }
 
 /// Simplified game that uses a fixed set of ships and players.
@@ -378,6 +937,83 @@ impl Game {
         *self.0.current()
     }
 
+    /// Start a game from the given [`Scenario`]. Every ship for both players must be
+    /// placed, and the damage must not sink all of either player's ships.
+    pub fn from_scenario(scenario: &Scenario) -> Result<Game, ScenarioError> {
+        let mut setup = GameSetup::new();
+        for &(player, ship, start, dir) in &scenario.placements {
+            setup
+                .place_ship(player, ship, start, dir)
+                .map_err(|reason| ScenarioError::Placement(player, ship, reason))?;
+        }
+        let mut game = match setup.start() {
+            Ok(game) => game,
+            Err(setup) if !setup.is_player_ready(Player::P1) => {
+                return Err(ScenarioError::Unplaced(Player::P1))
+            }
+            Err(_) => return Err(ScenarioError::Unplaced(Player::P2)),
+        };
+        for &(player, coord) in &scenario.damage {
+            let board = game.0.get_board_mut(&player).unwrap();
+            board.apply_damage(Some(coord)).map_err(|err| {
+                let reason = match err.reason() {
+                    board::CannotShootReason::AlreadyDefeated => CannotShootReason::AlreadyOver,
+                    board::CannotShootReason::OutOfBounds => CannotShootReason::OutOfBounds,
+                    board::CannotShootReason::AlreadyShot => CannotShootReason::AlreadyShot,
+                };
+                ScenarioError::Damage(player, coord, reason)
+            })?;
+        }
+        // Neither player can be defeated, so setting the current player can't fail.
+        game.0.set_current(&scenario.current).unwrap();
+        Ok(game)
+    }
+
+    /// Get a reference to the underlying [`uniform::Game`], for features that the simple
+    /// API doesn't expose. There is no mutable access, since changes at the uniform level
+    /// could break the assumptions of the simple wrapper.
+    pub fn as_uniform(&self) -> &uniform::Game<Player, Ship, RectDimensions> {
+        &self.0
+    }
+
+    /// Unwrap the underlying [`uniform::Game`], to continue play with the full uniform
+    /// API. The game can't be converted back.
+    pub fn into_uniform(self) -> uniform::Game<Player, Ship, RectDimensions> {
+        self.0
+    }
+
+    /// Get the current stage of play.
+    pub fn state(&self) -> GameState {
+        self.0.state()
+    }
+
+    /// Pause the game, so that shots are refused with [`CannotShootReason::Paused`] until
+    /// it is resumed. Returns true if the game was playing and is now paused.
+    pub fn pause(&mut self) -> bool {
+        self.0.pause()
+    }
+
+    /// Resume a paused game. Returns true if the game was paused and is now playing.
+    pub fn resume(&mut self) -> bool {
+        self.0.resume()
+    }
+
+    /// Get the player's notes on their opponent's board.
+    pub fn annotations(&self, player: Player) -> &AnnotationMap<Coordinate> {
+        self.0.annotations(&player, &!player).unwrap()
+    }
+
+    /// Mutably get the player's notes on their opponent's board, to add or remove notes.
+    pub fn annotations_mut(&mut self, player: Player) -> &mut AnnotationMap<Coordinate> {
+        self.0.annotations_mut(&player, &!player).unwrap()
+    }
+
+    /// Clear all shots from both boards and all notes, and give the first turn back to
+    /// [`Player::P1`], keeping the ship placements. See [`uniform::Game::reset`].
+    pub fn reset(&mut self) {
+        self.0.reset()
+    }
+
     /// Get the status of the game. Returns `None` if the game is in progress, otherwise
     /// returns the winner.
     pub fn winner(&self) -> Option<Player> {
@@ -402,17 +1038,33 @@ impl Game {
         self.0.get_board(&player).unwrap().iter_ships()
     }
 
+    /// Get an iterator over the specified player's ships which have been hit but not
+    /// sunk, along with the coordinates of their hit cells.
+    pub fn damaged_ships(
+        &self,
+        player: Player,
+    ) -> impl Iterator<Item = (ShipRef<'_>, Vec<&Coordinate>)> {
+        self.0.get_board(&player).unwrap().damaged_ships()
+    }
+
     /// Get a reference to the cell with the specified coordinate in the specified
     /// player's board. Return None if the coord is out of bounds.
-    pub fn get_coord(&self, player: Player, coord: Coordinate) -> Option<CellRef> {
+    pub fn get_coord(&self, player: Player, coord: Coordinate) -> Option<CellRef<'_>> {
         self.0.get_board(&player).unwrap().get_coord(coord)
     }
 
-    /// Get a reference to the specified ship from the specified player's board.
-    pub fn get_ship(&self, player: Player, ship: Ship) -> ShipRef {
+    /// Get a reference to the specified ship from the specified player's board. Panics if
+    /// the ship isn't part of the fleet.
+    pub fn get_ship(&self, player: Player, ship: Ship) -> ShipRef<'_> {
         self.0.get_board(&player).unwrap().get_ship(&ship).unwrap()
     }
 
+    /// Iterate the cells of the opponent's board which the player may shoot right now, in
+    /// row-major order. Empty if it isn't the player's turn or the game is paused or over.
+    pub fn legal_targets(&self, player: Player) -> impl '_ + Iterator<Item = Coordinate> {
+        self.0.legal_targets(&player, &!player)
+    }
+
     /// Fire at the specified player on the specified coordinate.
     pub fn shoot(
         &mut self,
@@ -425,28 +1077,47 @@ impl Game {
                 uniform::ShotOutcome::Miss => ShotOutcome::Miss,
                 uniform::ShotOutcome::Hit(ship) => ShotOutcome::Hit(ship),
                 uniform::ShotOutcome::Sunk(ship) => ShotOutcome::Sunk(ship),
-                // There are only two players so if one is defeated, we should go directly to
-                // victory and never hit Defeated.
-                uniform::ShotOutcome::Defeated(_) => unreachable!(),
+                // There are only two players, so defeating the target wins the game.
+                uniform::ShotOutcome::Defeated(ship) => ShotOutcome::Victory(ship),
                 uniform::ShotOutcome::Victory(ship) => ShotOutcome::Victory(ship),
+                uniform::ShotOutcome::Wasted => ShotOutcome::Wasted,
             })
             .map_err(|err| match err.reason() {
                 uniform::CannotShootReason::AlreadyOver => CannotShootReason::AlreadyOver,
-                uniform::CannotShootReason::SelfShot => CannotShootReason::OutOfTurn,
-                // There are always exactly two players, so player will never be unknown.
-                uniform::CannotShootReason::UnknownPlayer => unreachable!(),
-                // Since there are only 2 players, if one is defeated, the reason will be
-                // AlreadyOver not AlreadyDefeated
-                uniform::CannotShootReason::AlreadyDefeated => unreachable!(),
+                uniform::CannotShootReason::SelfShot => CannotShootReason::SelfTarget,
+                uniform::CannotShootReason::UnknownPlayer => CannotShootReason::UnknownPlayer,
+                // There are only two players, so a defeated target means the game is over.
+                uniform::CannotShootReason::AlreadyDefeated => CannotShootReason::AlreadyOver,
                 uniform::CannotShootReason::OutOfBounds => CannotShootReason::OutOfBounds,
                 uniform::CannotShootReason::AlreadyShot => CannotShootReason::AlreadyShot,
+                uniform::CannotShootReason::Paused => CannotShootReason::Paused,
             })
     }
+
+    /// Fire a shot at a cell of the target's board which has not been shot yet, picked
+    /// uniformly at random, returning the cell along with the outcome. Fails for the same
+    /// reasons as [`shoot`][Game::shoot], other than [`CannotShootReason::AlreadyShot`].
+    #[cfg(feature = "rng_gen")]
+    pub fn shoot_random<R: Rng + ?Sized>(
+        &mut self,
+        target: Player,
+        rng: &mut R,
+    ) -> Result<(Coordinate, ShotOutcome), CannotShootReason> {
+        // Once every cell is shot, every ship is sunk and the game is over.
+        let coord = self
+            .0
+            .get_board(&target)
+            .unwrap()
+            .sample_unshot(rng)
+            .ok_or(CannotShootReason::AlreadyOver)?;
+        self.shoot(target, coord).map(|outcome| (coord, outcome))
+    }
 }
 
 #[cfg(feature = "rng_gen")]
 mod rand_impl {
-    use super::{Orientation, Player};
+    use super::{Coordinate, Orientation, Player, RandomSource, RectDimensions};
+    use crate::board::Dimensions;
     use once_cell::sync::Lazy;
     use rand::{
         distributions::{Distribution, Standard, Uniform},
@@ -458,7 +1129,7 @@ mod rand_impl {
 
     impl Distribution<Player> for Standard {
         fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Player {
-            match rng.sample(&*PLAYER_SAMPLER) {
+            match rng.sample(*PLAYER_SAMPLER) {
                 0 => Player::P1,
                 _ => Player::P2,
             }
@@ -470,7 +1141,7 @@ mod rand_impl {
 
     impl Distribution<Orientation> for Standard {
         fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Orientation {
-            match rng.sample(&*ORIENTATION_SAMPLER) {
+            match rng.sample(*ORIENTATION_SAMPLER) {
                 0 => Orientation::Up,
                 1 => Orientation::Down,
                 2 => Orientation::Left,
@@ -478,4 +1149,828 @@ mod rand_impl {
             }
         }
     }
+
+    impl<R: Rng + ?Sized> RandomSource for R {
+        fn gen_coord(&mut self, dim: &RectDimensions) -> Coordinate {
+            dim.sample_coordinate(self)
+        }
+
+        fn gen_orientation(&mut self) -> Orientation {
+            self.gen()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    #[test]
+    fn default_setup_is_not_ready() {
+        let setup = GameSetup::default();
+        assert!(!setup.ready());
+        assert!(!setup.is_player_ready(Player::P1));
+        assert!(!setup.is_player_ready(Player::P2));
+    }
+
+    #[test]
+    fn default_player_moves_first() {
+        assert_eq!(Player::default(), Player::P1);
+        let mut setup = GameSetup::default();
+        for &player in &[Player::P1, Player::P2] {
+            for (y, &ship) in Ship::ALL.iter().enumerate() {
+                setup
+                    .place_ship(player, ship, Coordinate::new(0, y), Orientation::Right)
+                    .unwrap();
+            }
+        }
+        let game = setup.start().ok().unwrap();
+        assert_eq!(game.current(), Player::default());
+    }
+
+    #[test]
+    fn shoot_own_board() {
+        let mut game = fixtures::simple_mid_game();
+        assert_eq!(game.current(), Player::P1);
+        assert_eq!(
+            game.shoot(Player::P1, Coordinate::new(5, 5)),
+            Err(CannotShootReason::SelfTarget)
+        );
+        // The rejected shot doesn't use up the turn or mark the cell.
+        assert_eq!(game.current(), Player::P1);
+        assert!(!game
+            .get_coord(Player::P1, Coordinate::new(5, 5))
+            .unwrap()
+            .hit());
+    }
+
+    #[test]
+    fn place_ship_cells_valid() {
+        let mut setup = GameSetup::new();
+        let cells = vec![
+            Coordinate::new(4, 6),
+            Coordinate::new(4, 5),
+            Coordinate::new(4, 4),
+        ];
+        assert_eq!(
+            setup.place_ship_cells(Player::P1, Ship::Cruiser, cells),
+            Ok(())
+        );
+        let placement = setup.get_placement(Player::P1, Ship::Cruiser).unwrap();
+        assert_eq!(placement.start(), &Coordinate::new(4, 6));
+        assert_eq!(placement.orientation(), Orientation::Up);
+        assert_eq!(placement.end(), &Coordinate::new(4, 4));
+    }
+
+    #[test]
+    fn place_ship_cells_wrong_length() {
+        let mut setup = GameSetup::new();
+        let cells = vec![Coordinate::new(0, 0), Coordinate::new(1, 0)];
+        assert_eq!(
+            setup.place_ship_cells(Player::P1, Ship::Cruiser, cells),
+            Err(CannotPlaceReason::InvalidCells)
+        );
+        assert!(setup.get_placement(Player::P1, Ship::Cruiser).is_none());
+    }
+
+    #[test]
+    fn place_ship_cells_not_contiguous() {
+        let mut setup = GameSetup::new();
+        let cells = vec![
+            Coordinate::new(0, 0),
+            Coordinate::new(1, 0),
+            Coordinate::new(3, 0),
+        ];
+        assert_eq!(
+            setup.place_ship_cells(Player::P1, Ship::Cruiser, cells),
+            Err(CannotPlaceReason::InvalidCells)
+        );
+        assert!(setup.get_placement(Player::P1, Ship::Cruiser).is_none());
+    }
+
+    #[test]
+    fn shoot_after_victory() {
+        let mut game = fixtures::simple_near_victory();
+        assert_eq!(
+            game.shoot(Player::P2, Coordinate::new(1, 8)),
+            Ok(ShotOutcome::Victory(Ship::Destroyer))
+        );
+        assert_eq!(game.winner(), Some(Player::P1));
+        for &target in &[Player::P1, Player::P2] {
+            assert_eq!(
+                game.shoot(target, Coordinate::new(5, 5)),
+                Err(CannotShootReason::AlreadyOver)
+            );
+        }
+        #[cfg(feature = "rng_gen")]
+        assert_eq!(
+            game.shoot_random(Player::P1, &mut rand::thread_rng()),
+            Err(CannotShootReason::AlreadyOver)
+        );
+    }
+
+    /// [`RandomSource`] which plays back a fixed list of positions.
+    struct Scripted(
+        std::vec::IntoIter<(Coordinate, Orientation)>,
+        Option<Orientation>,
+    );
+
+    impl RandomSource for Scripted {
+        fn gen_coord(&mut self, _dim: &RectDimensions) -> Coordinate {
+            let (coord, dir) = self.0.next().expect("script ran out");
+            self.1 = Some(dir);
+            coord
+        }
+
+        fn gen_orientation(&mut self) -> Orientation {
+            self.1.take().unwrap()
+        }
+    }
+
+    #[test]
+    fn randomize_with_scripted_source() {
+        let mut setup = GameSetup::new();
+        setup
+            .place_ship(
+                Player::P1,
+                Ship::Destroyer,
+                Coordinate::new(5, 9),
+                Orientation::Left,
+            )
+            .unwrap();
+        // The first position doesn't fit any ship, so it is retried.
+        let script: Vec<_> = Some((Coordinate::new(9, 9), Orientation::Right))
+            .into_iter()
+            .chain((0..4).map(|row| (Coordinate::new(0, row * 2), Orientation::Right)))
+            .collect();
+        let mut source = Scripted(script.into_iter(), None);
+        setup.randomize_placements(Player::P1, &mut source);
+        assert_eq!(source.0.len(), 0);
+        assert!(setup.is_player_ready(Player::P1));
+
+        let destroyer = setup.get_placement(Player::P1, Ship::Destroyer).unwrap();
+        assert_eq!(destroyer.start(), &Coordinate::new(5, 9));
+        let mut rows: Vec<_> = Ship::ALL[..4]
+            .iter()
+            .map(|&ship| {
+                let placement = setup.get_placement(Player::P1, ship).unwrap();
+                assert_eq!(placement.start().x, 0);
+                assert_eq!(placement.orientation(), Orientation::Right);
+                placement.start().y
+            })
+            .collect();
+        rows.sort_unstable();
+        assert_eq!(rows, [0, 2, 4, 6]);
+    }
+
+    #[test]
+    fn orientation_delta() {
+        assert_eq!(Orientation::Up.delta(), (0, -1));
+        assert_eq!(Orientation::Down.delta(), (0, 1));
+        assert_eq!(Orientation::Left.delta(), (-1, 0));
+        assert_eq!(Orientation::Right.delta(), (1, 0));
+    }
+
+    #[test]
+    fn placement_direction_delta() {
+        for &dir in Orientation::ALL {
+            let mut setup = GameSetup::new();
+            setup
+                .place_ship(Player::P1, Ship::Cruiser, Coordinate::new(5, 5), dir)
+                .unwrap();
+            let placement = setup.get_placement(Player::P1, Ship::Cruiser).unwrap();
+            let (dx, dy) = placement.direction_delta();
+            assert_eq!((dx, dy), dir.delta());
+            for step in placement.windows(2) {
+                let next = Coordinate::new(
+                    (step[0].x as isize + dx) as usize,
+                    (step[0].y as isize + dy) as usize,
+                );
+                assert_eq!(step[1], next);
+            }
+        }
+    }
+
+    #[test]
+    fn not_player_is_opponent() {
+        assert_eq!(!Player::P1, Player::P2);
+        assert_eq!(!Player::P2, Player::P1);
+        assert_eq!(!!Player::P1, Player::P1);
+    }
+
+    #[test]
+    fn bow_and_stern_follow_orientation() {
+        for &(dir, stern) in &[
+            (Orientation::Up, (5, 2)),
+            (Orientation::Down, (5, 8)),
+            (Orientation::Left, (2, 5)),
+            (Orientation::Right, (8, 5)),
+        ] {
+            let mut setup = GameSetup::new();
+            setup
+                .place_ship(Player::P1, Ship::Battleship, Coordinate::new(5, 5), dir)
+                .unwrap();
+            let placement = setup.get_placement(Player::P1, Ship::Battleship).unwrap();
+            let bow = Coordinate::new(5, 5);
+            let stern = Coordinate::new(stern.0, stern.1);
+            assert_eq!(placement.start(), &bow);
+            assert_eq!(placement.stern(), &stern);
+            assert_eq!(placement.orientation(), dir);
+
+            let forward: Vec<_> = placement.iter_bow_to_stern().copied().collect();
+            let mut backward: Vec<_> = placement.iter_stern_to_bow().copied().collect();
+            assert_eq!(forward.len(), 4);
+            assert_eq!(forward[0], bow);
+            assert_eq!(forward[3], stern);
+            backward.reverse();
+            assert_eq!(forward, backward);
+        }
+    }
+
+    #[test]
+    fn custom_fleet_game() {
+        let fleet = [Ship::Destroyer, Ship::Carrier];
+        let mut setup = GameSetup::new_with_fleet(&fleet).unwrap();
+        for &player in &[Player::P1, Player::P2] {
+            assert_eq!(setup.get_pending_ships(player).count(), 2);
+            for (y, &ship) in fleet.iter().enumerate() {
+                setup
+                    .place_ship(player, ship, Coordinate::new(0, y), Orientation::Right)
+                    .unwrap();
+            }
+        }
+        let mut game = setup.start().ok().unwrap();
+        let mut last = None;
+        let targets = (0..2).map(|x| (x, 0)).chain((0..5).map(|x| (x, 1)));
+        for (miss, (x, y)) in targets.enumerate() {
+            last = Some(game.shoot(Player::P2, Coordinate::new(x, y)).unwrap());
+            if game.winner().is_none() {
+                game.shoot(Player::P1, Coordinate::new(miss, 9)).unwrap();
+            }
+        }
+        assert_eq!(last, Some(ShotOutcome::Victory(Ship::Carrier)));
+        assert_eq!(game.winner(), Some(Player::P1));
+    }
+
+    #[test]
+    fn custom_fleet_errors() {
+        assert_eq!(
+            GameSetup::new_with_fleet(&[]).err(),
+            Some(FleetError::Empty)
+        );
+        assert_eq!(
+            GameSetup::new_with_fleet(&[Ship::Carrier, Ship::Destroyer, Ship::Carrier]).err(),
+            Some(FleetError::Duplicate(Ship::Carrier))
+        );
+    }
+
+    #[test]
+    fn repeated_ship_types_in_uniform_game() {
+        // Two carriers, told apart by an instance number in the ship ID.
+        let mut setup = uniform::GameSetup::<Player, (Ship, u8), RectDimensions, Line>::new();
+        for &player in &[Player::P1, Player::P2] {
+            let board = setup
+                .add_player(player, RectDimensions::new(10, 10))
+                .unwrap();
+            for n in 0..2 {
+                let cells = (0..5).map(|x| Coordinate::new(x, n as usize)).collect();
+                board
+                    .add_ship((Ship::Carrier, n), Ship::Carrier.get_shape())
+                    .unwrap()
+                    .place(cells)
+                    .unwrap();
+            }
+        }
+        let mut game = setup.start().ok().unwrap();
+        for x in 0..4 {
+            game.shoot(Player::P2, Coordinate::new(x, 1)).unwrap();
+            game.shoot(Player::P1, Coordinate::new(x, 9)).unwrap();
+        }
+        assert!(matches!(
+            game.shoot(Player::P2, Coordinate::new(4, 1)),
+            Ok(uniform::ShotOutcome::Sunk((Ship::Carrier, 1)))
+        ));
+        let board = game.get_board(&Player::P2).unwrap();
+        assert!(!board.get_ship(&(Ship::Carrier, 0)).unwrap().sunk());
+    }
+
+    #[test]
+    fn lexicon_names_round_trip() {
+        let lexicons: [&dyn Lexicon<Ship>; 2] = [&English, &Spanish];
+        for lexicon in &lexicons {
+            for &ship in Ship::ALL {
+                assert_eq!(lexicon.parse_ship(&lexicon.ship_name(&ship)), Some(ship));
+            }
+            assert_eq!(lexicon.parse_ship("rowboat"), None);
+        }
+        for &(alias, ship) in &[
+            ("cv", Ship::Carrier),
+            ("bb", Ship::Battleship),
+            ("ca", Ship::Cruiser),
+            ("cl", Ship::Cruiser),
+            ("ss", Ship::Submarine),
+            ("sub", Ship::Submarine),
+            ("dd", Ship::Destroyer),
+        ] {
+            assert_eq!(English.parse_ship(alias), Some(ship));
+        }
+        for &(alias, ship) in &[
+            ("pa", Ship::Carrier),
+            ("ac", Ship::Battleship),
+            ("cr", Ship::Cruiser),
+            ("sm", Ship::Submarine),
+            ("de", Ship::Destroyer),
+        ] {
+            assert_eq!(Spanish.parse_ship(alias), Some(ship));
+        }
+        // Each lexicon only parses its own language.
+        assert_eq!(English.parse_ship("crucero"), None);
+        assert_eq!(Spanish.parse_ship("cruiser"), None);
+    }
+
+    #[test]
+    fn lexicon_renders_outcomes() {
+        let outcomes = [
+            ShotOutcome::Miss,
+            ShotOutcome::Hit(Ship::Cruiser),
+            ShotOutcome::Sunk(Ship::Submarine),
+            ShotOutcome::Victory(Ship::Destroyer),
+            ShotOutcome::Wasted,
+        ];
+        let english: Vec<_> = outcomes.iter().map(|o| o.describe_in(&English)).collect();
+        assert_eq!(
+            english,
+            [
+                "Miss.",
+                "Hit cruiser!",
+                "Sunk submarine!",
+                "Sunk destroyer! Victory!",
+                "Already shot there. Shot wasted.",
+            ]
+        );
+        let spanish: Vec<_> = outcomes.iter().map(|o| o.describe_in(&Spanish)).collect();
+        assert_eq!(
+            spanish,
+            [
+                "Agua.",
+                "¡Tocado: crucero!",
+                "¡Hundido: submarino!",
+                "¡Hundido: destructor! ¡Victoria!",
+                "Esa posición ya fue atacada. Disparo perdido.",
+            ]
+        );
+        assert_eq!(
+            Spanish.defeated(&Ship::Carrier),
+            "¡Hundido: portaaviones! Todos los barcos hundidos."
+        );
+        assert_eq!(
+            English.defeated(&Ship::Carrier),
+            "Sunk carrier! All ships sunk."
+        );
+    }
+
+    /// Scenario with both fleets in the fixture layout and no damage.
+    fn fixture_scenario(current: Player) -> Scenario {
+        let mut placements = vec![];
+        for &player in &[Player::P1, Player::P2] {
+            for (y, &ship) in Ship::ALL.iter().enumerate() {
+                placements.push((player, ship, Coordinate::new(0, 2 * y), Orientation::Right));
+            }
+        }
+        Scenario {
+            placements,
+            damage: vec![],
+            current,
+        }
+    }
+
+    #[test]
+    fn half_sunk_scenario() {
+        let mut scenario = fixture_scenario(Player::P2);
+        // P2 has lost the Carrier, and half of the Battleship.
+        scenario
+            .damage
+            .extend((0..5).map(|x| (Player::P2, Coordinate::new(x, 0))));
+        scenario
+            .damage
+            .extend((0..2).map(|x| (Player::P2, Coordinate::new(x, 2))));
+        scenario.damage.push((Player::P2, Coordinate::new(9, 9)));
+        // P1's Destroyer has been hit once.
+        scenario.damage.push((Player::P1, Coordinate::new(0, 8)));
+        let mut game = Game::from_scenario(&scenario).unwrap();
+
+        assert_eq!(game.current(), Player::P2);
+        assert_eq!(game.winner(), None);
+        assert!(game.get_ship(Player::P2, Ship::Carrier).sunk());
+        let battleship = game.get_ship(Player::P2, Ship::Battleship);
+        assert!(!battleship.sunk());
+        assert_eq!(battleship.hit_coords().count(), 2);
+        assert!(game
+            .get_coord(Player::P2, Coordinate::new(9, 9))
+            .unwrap()
+            .hit());
+        assert_eq!(
+            game.get_ship(Player::P1, Ship::Destroyer)
+                .hit_coords()
+                .count(),
+            1
+        );
+
+        // Play carries on from the scenario.
+        assert_eq!(
+            game.shoot(Player::P1, Coordinate::new(1, 8)),
+            Ok(ShotOutcome::Sunk(Ship::Destroyer))
+        );
+        assert_eq!(
+            game.shoot(Player::P2, Coordinate::new(2, 2)),
+            Ok(ShotOutcome::Hit(Ship::Battleship))
+        );
+        assert_eq!(
+            game.shoot(Player::P1, Coordinate::new(0, 8)),
+            Err(CannotShootReason::AlreadyShot)
+        );
+    }
+
+    #[test]
+    fn invalid_scenarios() {
+        let mut scenario = fixture_scenario(Player::P1);
+        scenario.placements.pop();
+        assert_eq!(
+            Game::from_scenario(&scenario).err(),
+            Some(ScenarioError::Unplaced(Player::P2))
+        );
+
+        let mut scenario = fixture_scenario(Player::P1);
+        scenario.placements[1].2 = Coordinate::new(0, 0);
+        assert_eq!(
+            Game::from_scenario(&scenario).err(),
+            Some(ScenarioError::Placement(
+                Player::P1,
+                Ship::Battleship,
+                CannotPlaceReason::AlreadyOccupied
+            ))
+        );
+
+        let mut scenario = fixture_scenario(Player::P1);
+        let repeated = Coordinate::new(4, 4);
+        scenario.damage = vec![(Player::P1, repeated), (Player::P1, repeated)];
+        assert_eq!(
+            Game::from_scenario(&scenario).err(),
+            Some(ScenarioError::Damage(
+                Player::P1,
+                repeated,
+                CannotShootReason::AlreadyShot
+            ))
+        );
+
+        // Damage may not sink a player's whole fleet, so the last cell is refused.
+        let mut scenario = fixture_scenario(Player::P1);
+        for (y, &ship) in Ship::ALL.iter().enumerate() {
+            for x in 0..ship.len() {
+                scenario
+                    .damage
+                    .push((Player::P2, Coordinate::new(x, 2 * y)));
+            }
+        }
+        assert_eq!(
+            Game::from_scenario(&scenario).err(),
+            Some(ScenarioError::Damage(
+                Player::P2,
+                Coordinate::new(1, 8),
+                CannotShootReason::AlreadyOver
+            ))
+        );
+    }
+
+    #[test]
+    fn check_placement_agrees_with_place_ship() {
+        let mut setup = GameSetup::new();
+        // Crowd the board with every ship but the Destroyer, one per row from the top.
+        for (y, &ship) in Ship::ALL[..4].iter().enumerate() {
+            setup
+                .place_ship(Player::P1, ship, Coordinate::new(y, y), Orientation::Right)
+                .unwrap();
+        }
+        let (mut fits, mut blocked) = (0, 0);
+        for coord in setup.dimensions().iter_coordinates().flatten() {
+            for &dir in Orientation::ALL {
+                let checked = setup.check_placement(Player::P1, Ship::Destroyer, coord, dir);
+                let placed = setup.place_ship(Player::P1, Ship::Destroyer, coord, dir);
+                assert_eq!(checked, placed, "{:?} {:?}", coord, dir);
+                if placed.is_ok() {
+                    fits += 1;
+                    assert!(setup.unplace_ship(Player::P1, Ship::Destroyer));
+                } else {
+                    blocked += 1;
+                }
+            }
+        }
+        assert!(fits > 0 && blocked > 0);
+        for &dir in Orientation::ALL {
+            let start = Coordinate::new(5, 5);
+            assert_eq!(
+                setup.check_placement(Player::P1, Ship::Carrier, start, dir),
+                Err(CannotPlaceReason::AlreadyPlaced)
+            );
+            assert_eq!(
+                setup.place_ship(Player::P1, Ship::Carrier, start, dir),
+                Err(CannotPlaceReason::AlreadyPlaced)
+            );
+        }
+    }
+
+    #[test]
+    fn resolved_cells_match_placement() {
+        for &dir in Orientation::ALL {
+            for &start in &[
+                Coordinate::new(4, 4),
+                Coordinate::new(0, 9),
+                Coordinate::new(9, 0),
+            ] {
+                let mut setup = GameSetup::new();
+                let resolved = setup.resolve_placement(Player::P2, Ship::Cruiser, start, dir);
+                let placed = setup.place_ship(Player::P2, Ship::Cruiser, start, dir);
+                match resolved {
+                    Ok(cells) => {
+                        assert_eq!(placed, Ok(()));
+                        assert_eq!(cells.len(), 3);
+                        assert_eq!(cells[0], start);
+                        let placement = setup.get_placement(Player::P2, Ship::Cruiser).unwrap();
+                        assert_eq!(&cells[..], &placement[..], "{:?} {:?}", start, dir);
+                        assert_eq!(placement.orientation(), dir);
+                    }
+                    Err(reason) => assert_eq!(placed, Err(reason)),
+                }
+            }
+        }
+
+        // Committing the resolved cells places the ship in the same place.
+        let mut setup = GameSetup::new();
+        let start = Coordinate::new(2, 3);
+        let cells = setup
+            .resolve_placement(Player::P1, Ship::Battleship, start, Orientation::Down)
+            .unwrap();
+        setup
+            .place_ship_cells(Player::P1, Ship::Battleship, cells.clone())
+            .unwrap();
+        let placement = setup.get_placement(Player::P1, Ship::Battleship).unwrap();
+        assert_eq!(&placement[..], &cells[..]);
+        assert_eq!(placement.orientation(), Orientation::Down);
+        assert_eq!(placement.start(), &start);
+    }
+
+    #[test]
+    fn suggestions_ordered_by_distance_and_valid() {
+        let mut setup = fixtures::simple_setup();
+        assert!(setup.unplace_ship(Player::P1, Ship::Carrier));
+        let near = Coordinate::new(1, 2);
+        let distance = |start: &Coordinate| start.x.abs_diff(near.x) + start.y.abs_diff(near.y);
+
+        let all = setup.suggest_placements(Player::P1, Ship::Carrier, near, usize::MAX);
+        let mut cell_sets = HashSet::new();
+        for pair in all.windows(2) {
+            assert!(distance(&pair[0].0) <= distance(&pair[1].0), "{:?}", pair);
+        }
+        for &(start, dir) in &all {
+            assert_eq!(
+                setup.check_placement(Player::P1, Ship::Carrier, start, dir),
+                Ok(())
+            );
+            let mut cells = setup
+                .resolve_placement(Player::P1, Ship::Carrier, start, dir)
+                .unwrap();
+            cells.sort_by_key(|c| (c.y, c.x));
+            assert!(
+                cell_sets.insert(cells),
+                "{:?} {:?} suggested twice",
+                start,
+                dir
+            );
+        }
+
+        // Every distinct valid placement is suggested, and the closest comes first.
+        let mut valid = HashSet::new();
+        let mut closest = usize::MAX;
+        for start in setup.dimensions().iter_coordinates().flatten() {
+            for &dir in Orientation::ALL {
+                if let Ok(mut cells) =
+                    setup.resolve_placement(Player::P1, Ship::Carrier, start, dir)
+                {
+                    cells.sort_by_key(|c| (c.y, c.x));
+                    valid.insert(cells);
+                    closest = closest.min(distance(&start));
+                }
+            }
+        }
+        assert_eq!(cell_sets, valid);
+        assert_eq!(distance(&all[0].0), closest);
+
+        assert_eq!(
+            setup.suggest_placements(Player::P1, Ship::Carrier, near, 3),
+            all[..3]
+        );
+        assert!(setup
+            .suggest_placements(Player::P1, Ship::Carrier, near, 0)
+            .is_empty());
+        assert!(setup
+            .suggest_placements(Player::P2, Ship::Carrier, near, 3)
+            .is_empty());
+    }
+
+    #[test]
+    fn ship_placed_across_seam() {
+        let mut setup = GameSetup::new_wrapping(Wrapping::Horizontal);
+        let start = Coordinate::new(8, 3);
+        setup
+            .place_ship(Player::P1, Ship::Carrier, start, Orientation::Right)
+            .unwrap();
+        let placement = setup.get_placement(Player::P1, Ship::Carrier).unwrap();
+        let expected: Vec<_> = [8, 9, 0, 1, 2]
+            .iter()
+            .map(|&x| Coordinate::new(x, 3))
+            .collect();
+        assert_eq!(&placement[..], &expected[..]);
+        assert_eq!(placement.start(), &start);
+        assert_eq!(placement.end(), &Coordinate::new(2, 3));
+        assert_eq!(placement.orientation(), Orientation::Right);
+
+        // The seam only runs left to right, and a plain board has no seam at all.
+        assert_eq!(
+            setup.place_ship(
+                Player::P1,
+                Ship::Battleship,
+                Coordinate::new(5, 1),
+                Orientation::Up
+            ),
+            Err(CannotPlaceReason::InsufficientSpace)
+        );
+        assert_eq!(
+            GameSetup::new().place_ship(Player::P1, Ship::Carrier, start, Orientation::Right),
+            Err(CannotPlaceReason::InsufficientSpace)
+        );
+    }
+
+    #[test]
+    fn directed_projection_matches_filtered_projections() {
+        use crate::ships::ShipShape;
+
+        /// The old approach: build every projection from the start and keep the one whose
+        /// first step goes in the right direction, allowing for wrapping.
+        fn filtered(
+            dir: Orientation,
+            start: Coordinate,
+            len: usize,
+            dim: &RectDimensions,
+        ) -> Option<ShapeProjection<Coordinate>> {
+            let (dx, dy) = dir.delta();
+            let steps = |from: usize, to: usize, delta: isize, size: usize| {
+                (to as isize - from as isize).rem_euclid(size as isize)
+                    == delta.rem_euclid(size as isize)
+            };
+            Line::new(len).project(start, dim).find(|proj| {
+                proj.len() == 1
+                    || steps(proj[0].x, proj[1].x, dx, dim.width())
+                        && steps(proj[0].y, proj[1].y, dy, dim.height())
+            })
+        }
+
+        let dims = [
+            RectDimensions::new(10, 10),
+            RectDimensions::new(4, 6),
+            RectDimensions::new_wrapping(10, 10, Wrapping::Horizontal),
+            RectDimensions::new_wrapping(4, 6, Wrapping::Horizontal | Wrapping::Vertical),
+        ];
+        let mut found = 0;
+        for dim in &dims {
+            // Every cell on the edges, plus one in the middle.
+            let (w, h) = (dim.width(), dim.height());
+            let starts = dim
+                .iter_coordinates()
+                .flatten()
+                .filter(|c| c.x == 0 || c.y == 0 || c.x == w - 1 || c.y == h - 1)
+                .chain(Some(Coordinate::new(w / 2, h / 2)));
+            for start in starts {
+                for &dir in Orientation::ALL {
+                    for len in 1..=6 {
+                        let directed = dir.project(start, len, dim);
+                        assert_eq!(
+                            directed,
+                            filtered(dir, start, len, dim),
+                            "{:?} {:?} len {} on {:?}",
+                            start,
+                            dir,
+                            len,
+                            dim
+                        );
+                        found += directed.is_some() as usize;
+                    }
+                }
+            }
+        }
+        assert!(found > 0);
+    }
+
+    #[test]
+    fn place_reason_predicates() {
+        let mut setup = GameSetup::new_with_fleet(&[Ship::Carrier, Ship::Destroyer]).unwrap();
+        let mut place = |ship, x, y, dir| {
+            setup
+                .place_ship(Player::P1, ship, Coordinate::new(x, y), dir)
+                .unwrap_err()
+        };
+        let insufficient = place(Ship::Carrier, 8, 0, Orientation::Right);
+        let not_in_fleet = place(Ship::Cruiser, 0, 5, Orientation::Right);
+        setup
+            .place_ship(
+                Player::P1,
+                Ship::Carrier,
+                Coordinate::new(0, 0),
+                Orientation::Right,
+            )
+            .unwrap();
+        let already_placed = setup
+            .place_ship(
+                Player::P1,
+                Ship::Carrier,
+                Coordinate::new(0, 5),
+                Orientation::Right,
+            )
+            .unwrap_err();
+        let already_occupied = setup
+            .place_ship(
+                Player::P1,
+                Ship::Destroyer,
+                Coordinate::new(2, 0),
+                Orientation::Down,
+            )
+            .unwrap_err();
+        let invalid_cells = setup
+            .place_ship_cells(
+                Player::P1,
+                Ship::Destroyer,
+                vec![Coordinate::new(5, 5), Coordinate::new(6, 6)],
+            )
+            .unwrap_err();
+
+        let reasons = [
+            insufficient,
+            already_placed,
+            already_occupied,
+            invalid_cells,
+            not_in_fleet,
+        ];
+        for (i, reason) in reasons.iter().enumerate() {
+            let flags = [
+                reason.is_insufficient_space(),
+                reason.is_already_placed(),
+                reason.is_already_occupied(),
+                reason.is_invalid_cells(),
+                reason.is_not_in_fleet(),
+            ];
+            for (j, &flag) in flags.iter().enumerate() {
+                assert_eq!(flag, i == j, "{:?} predicate {}", reason, j);
+            }
+        }
+    }
+
+    #[test]
+    fn uniform_escape_hatch() {
+        let setup = fixtures::simple_setup();
+        let order: Vec<_> = setup
+            .as_uniform()
+            .iter_boards()
+            .map(|(pid, _)| *pid)
+            .collect();
+        assert_eq!(order, [Player::P1, Player::P2]);
+
+        let mut game = fixtures::simple_mid_game();
+        // Read the shots fired so far through the uniform game, then keep playing through
+        // the simple API and see the uniform view follow along.
+        let shots_at = |game: &Game, player| {
+            game.as_uniform()
+                .get_board(&player)
+                .unwrap()
+                .target_legality()
+                .filter(|(_, status)| status.is_already_shot())
+                .count()
+        };
+        assert_eq!(
+            (shots_at(&game, Player::P1), shots_at(&game, Player::P2)),
+            (3, 3)
+        );
+        assert_eq!(game.as_uniform().total_ships_sunk(), 1);
+        game.shoot(Player::P2, Coordinate::new(1, 0)).unwrap();
+        assert_eq!(shots_at(&game, Player::P2), 4);
+        assert_eq!(*game.as_uniform().current(), Player::P2);
+
+        // After unwrapping, play continues with the uniform API from the same position.
+        let mut game = game.into_uniform();
+        game.shoot(Player::P1, Coordinate::new(9, 5)).unwrap();
+        assert_eq!(*game.current(), Player::P1);
+        assert_eq!(game.total_ships_sunk(), 1);
+        game.forfeit_current();
+        assert_eq!(game.winner(), Some(&Player::P2));
+        assert!(game.get_board(&Player::P1).unwrap().defeated());
+    }
 }
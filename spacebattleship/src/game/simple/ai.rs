@@ -0,0 +1,131 @@
+// Copyright 2020 Zachary Stewart
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A computer opponent for the simple game.
+//!
+//! The bot only looks at the opponent's board through the shot state every player can
+//! see, so it never knows where the ships are before it hits them.
+
+use rand::Rng;
+
+use super::{CannotShootReason, Coordinate, Game, GameSetup, Player, ShotOutcome};
+
+/// How hard the bot tries to win.
+///
+/// Only [`Difficulty::Random`] exists so far. Hunt-target play (shooting around hits
+/// until a ship sinks) and probability-density play are planned as further levels.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum Difficulty {
+    /// Shoot cells which haven't been shot yet, chosen uniformly at random.
+    Random,
+}
+
+/// A computer player for the simple game.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Bot {
+    difficulty: Difficulty,
+}
+
+impl Bot {
+    /// Create a bot which plays at the given difficulty.
+    pub fn new(difficulty: Difficulty) -> Self {
+        Self { difficulty }
+    }
+
+    /// Get the difficulty this bot plays at.
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    /// Place all of the player's un-placed ships at random positions.
+    pub fn place_ships<R: Rng>(&self, setup: &mut GameSetup, player: Player, rng: &mut R) {
+        setup.randomize_placements(player, rng);
+    }
+
+    /// Take the current player's turn by shooting their opponent, returning the cell
+    /// which was shot along with the outcome. Fails if the game is over or paused.
+    pub fn take_turn<R: Rng>(
+        &self,
+        game: &mut Game,
+        rng: &mut R,
+    ) -> Result<(Coordinate, ShotOutcome), CannotShootReason> {
+        match self.difficulty {
+            Difficulty::Random => game.shoot_random(game.current().opponent(), rng),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    /// Play a whole game between two bots, returning the winner and the number of shots
+    /// each player fired.
+    fn play_out(bot: Bot, seed: u64) -> (Player, [usize; 2]) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut setup = GameSetup::new();
+        bot.place_ships(&mut setup, Player::P1, &mut rng);
+        bot.place_ships(&mut setup, Player::P2, &mut rng);
+        let mut game = setup.start().ok().unwrap();
+        let mut shots = [0; 2];
+        loop {
+            let player = game.current();
+            let (coord, outcome) = bot.take_turn(&mut game, &mut rng).unwrap();
+            shots[player as usize] += 1;
+            assert!(game.get_coord(player.opponent(), coord).unwrap().hit());
+            match outcome {
+                ShotOutcome::Victory(_) => return (player, shots),
+                ShotOutcome::Wasted => panic!("bot wasted a shot at {:?}", coord),
+                _ => {}
+            }
+        }
+    }
+
+    #[test]
+    fn random_bot_finishes_games() {
+        let bot = Bot::new(Difficulty::Random);
+        assert_eq!(bot.difficulty(), Difficulty::Random);
+        for seed in 0..20 {
+            let (winner, shots) = play_out(bot, seed);
+            let winner_shots = shots[winner as usize];
+            let loser_shots = shots[winner.opponent() as usize];
+            // Sinking all 17 ship cells takes at least 17 shots and at most every cell.
+            assert!((17..=100).contains(&winner_shots), "{:?}", shots);
+            // Turns alternate, so the loser fired one fewer shot or the same number.
+            assert!(winner_shots - loser_shots <= 1, "{:?}", shots);
+        }
+    }
+
+    #[test]
+    fn seeded_games_repeat() {
+        let bot = Bot::new(Difficulty::Random);
+        assert_eq!(play_out(bot, 1442), play_out(bot, 1442));
+    }
+
+    #[test]
+    fn take_turn_after_victory_fails() {
+        let bot = Bot::new(Difficulty::Random);
+        let mut rng = StdRng::seed_from_u64(1442);
+        let mut game = crate::fixtures::simple_near_victory();
+        while game.winner().is_none() {
+            bot.take_turn(&mut game, &mut rng).unwrap();
+        }
+        assert_eq!(
+            bot.take_turn(&mut game, &mut rng),
+            Err(CannotShootReason::AlreadyOver)
+        );
+    }
+}
@@ -16,18 +16,28 @@
 //! setups.
 use std::{
     borrow::Borrow,
-    collections::{hash_map::Entry, HashMap},
+    collections::{hash_map::Entry, HashMap, HashSet},
     fmt::Debug,
     hash::Hash,
 };
 
 use crate::{
-    board::{Board, BoardSetup, Dimensions, ShotOutcome as BoardShotOutcome},
-    ships::{ShipId, ShipShape},
+    board::{
+        Board, BoardEvents, BoardSetup, CannotShootReason as BoardCannotShootReason, Dimensions,
+        ShotOutcome as BoardShotOutcome,
+    },
+    game::annotations::AnnotationMap,
+    lexicon::{DefaultLexicon, Lexicon},
+    ships::{ShapeProjection, ShipId, ShipShape},
 };
 
-pub use self::errors::{AddPlayerError, CannotShootReason, ShotError};
+pub use self::diff::GameDiff;
+pub use self::errors::{
+    AddPlayerError, CannotShootReason, ConfirmError, FairnessViolation, HandicapError, MirrorError,
+    NegotiationError, SetCurrentError, ShotError, TurnOrderError, ValidateError,
+};
 
+mod diff;
 mod errors;
 
 /// Types used for the ID of a player. IDs are treated as disposable and cheaply
@@ -45,6 +55,33 @@ pub struct GameSetup<P: PlayerId, I: ShipId, D: Dimensions, S: ShipShape<D>> {
 
     /// Records the turn order for players.
     turn_order: Vec<P>,
+
+    /// How shots at already-shot cells are handled.
+    repeat_shot_policy: RepeatShotPolicy,
+
+    /// Whether players may unconfirm their placements after confirming them.
+    allow_unconfirm: bool,
+
+    /// Whether players must confirm their placements before the game can start.
+    require_confirmation: bool,
+
+    /// Number of additional shots each turn for players with a handicap.
+    extra_shots: HashMap<P, u8>,
+
+    /// Fairness rules every player's setup must follow before the game can start.
+    fairness_policy: Option<FairnessPolicy>,
+
+    /// Whether empty cells around a sunk ship are revealed, if ships can't touch.
+    auto_reveal_adjacent_on_sink: bool,
+
+    /// The fleet most recently proposed for every player, along with who proposed it.
+    fleet_proposal: Option<(P, Vec<(I, S)>)>,
+
+    /// Players who have accepted the current fleet proposal.
+    fleet_acceptances: HashSet<P>,
+
+    /// Whether every player accepted the fleet proposal, fixing their rosters.
+    fleets_agreed: bool,
 }
 
 impl<P: PlayerId, I: ShipId, D: Dimensions, S: ShipShape<D>> GameSetup<P, I, D, S> {
@@ -53,27 +90,61 @@ impl<P: PlayerId, I: ShipId, D: Dimensions, S: ShipShape<D>> GameSetup<P, I, D,
         Self {
             boards: HashMap::new(),
             turn_order: Vec::new(),
+            repeat_shot_policy: RepeatShotPolicy::default(),
+            allow_unconfirm: false,
+            require_confirmation: false,
+            extra_shots: HashMap::new(),
+            fairness_policy: None,
+            auto_reveal_adjacent_on_sink: false,
+            fleet_proposal: None,
+            fleet_acceptances: HashSet::new(),
+            fleets_agreed: false,
         }
     }
 
     /// Tries to start the game. If all players are ready, returns a [`Game`] with the
-    /// current setup. If fewer than 2 players have been added, or any player has not
-    /// placed all of their ships, returns `self`.
+    /// current setup. If [`validate`][GameSetup::validate] fails, returns `self`.
+    #[allow(clippy::result_large_err)]
     pub fn start(self) -> Result<Game<P, I, D>, Self> {
         if !self.ready() {
             Err(self)
         } else {
+            let shots_left = self
+                .extra_shots
+                .get(&self.turn_order[0])
+                .copied()
+                .unwrap_or(0);
+            let annotations = self
+                .turn_order
+                .iter()
+                .map(|pid| {
+                    let opponents = self.turn_order.iter().filter(|other| *other != pid);
+                    let maps = opponents.map(|other| (other.clone(), AnnotationMap::new()));
+                    (pid.clone(), maps.collect())
+                })
+                .collect();
+            let auto_reveal = self.auto_reveals();
             Ok(Game {
                 boards: self
                     .boards
                     .into_iter()
                     .map(|(pid, board)| match board.start() {
-                        Ok(board) => (pid, board),
+                        // Validation rejects empty boards, so no player starts defeated.
+                        Ok(board) => {
+                            debug_assert!(!board.defeated());
+                            (pid, board)
+                        }
                         Err(_) => unreachable!(),
                     })
                     .collect(),
                 turn_order: self.turn_order,
                 current: 0,
+                repeat_shot_policy: self.repeat_shot_policy,
+                extra_shots: self.extra_shots,
+                shots_left,
+                paused: false,
+                auto_reveal,
+                annotations,
             })
         }
     }
@@ -93,29 +164,528 @@ impl<P: PlayerId, I: ShipId, D: Dimensions, S: ShipShape<D>> GameSetup<P, I, D,
         }
     }
 
+    /// Like [`add_player`][GameSetup::add_player], but if a player with the same ID and
+    /// equal dimensions was already added, returns their existing board instead of an
+    /// error. This lets a client join the same game more than once. A player with the
+    /// same ID but different dimensions is still rejected; their existing board can be
+    /// inspected with [`get_board`][GameSetup::get_board].
+    pub fn add_player_idempotent(
+        &mut self,
+        pid: P,
+        dim: D,
+    ) -> Result<&mut BoardSetup<I, D, S>, AddPlayerError<P, D>>
+    where
+        D: PartialEq,
+    {
+        match self.boards.entry(pid.clone()) {
+            Entry::Occupied(entry) if *entry.get().dimensions() == dim => Ok(entry.into_mut()),
+            Entry::Occupied(_) => Err(AddPlayerError::new(pid, dim)),
+            Entry::Vacant(entry) => {
+                self.turn_order.push(pid);
+                Ok(entry.insert(BoardSetup::new(dim)))
+            }
+        }
+    }
+
+    /// Get the policy for shots at cells which were already shot.
+    pub fn repeat_shot_policy(&self) -> RepeatShotPolicy {
+        self.repeat_shot_policy
+    }
+
+    /// Set the policy for shots at cells which were already shot.
+    pub fn set_repeat_shot_policy(&mut self, policy: RepeatShotPolicy) {
+        self.repeat_shot_policy = policy;
+    }
+
+    /// Get the order in which players will take turns. Defaults to the order in which
+    /// players were added.
+    pub fn turn_order(&self) -> &[P] {
+        &self.turn_order
+    }
+
+    /// Set the order in which players will take turns. The order must contain every
+    /// player that has been added exactly once.
+    pub fn set_turn_order(&mut self, order: &[P]) -> Result<(), TurnOrderError<P>> {
+        let mut seen = HashSet::with_capacity(order.len());
+        for pid in order {
+            if !self.boards.contains_key(pid) {
+                return Err(TurnOrderError::UnknownPlayer(pid.clone()));
+            }
+            if !seen.insert(pid) {
+                return Err(TurnOrderError::DuplicatePlayer(pid.clone()));
+            }
+        }
+        if let Some(missing) = self.turn_order.iter().find(|pid| !seen.contains(pid)) {
+            return Err(TurnOrderError::MissingPlayer(missing.clone()));
+        }
+        self.turn_order = order.to_vec();
+        Ok(())
+    }
+
+    /// Confirm the specified player's placements, locking their board so that no ships
+    /// can be added, placed, or unplaced. All of the player's ships must be placed.
+    pub fn confirm_placements(&mut self, pid: P) -> Result<(), ConfirmError<P>> {
+        match self.boards.get_mut(&pid) {
+            None => Err(ConfirmError::UnknownPlayer(pid)),
+            Some(board) if !board.ready() => Err(ConfirmError::NotReady(pid)),
+            Some(board) => {
+                board.set_locked(true);
+                Ok(())
+            }
+        }
+    }
+
+    /// Unconfirm the specified player's placements, unlocking their board. Only allowed
+    /// if [`allow_unconfirm`][GameSetup::allow_unconfirm] is set.
+    pub fn unconfirm_placements(&mut self, pid: P) -> Result<(), ConfirmError<P>> {
+        match self.boards.get_mut(&pid) {
+            None => Err(ConfirmError::UnknownPlayer(pid)),
+            Some(_) if !self.allow_unconfirm => Err(ConfirmError::UnconfirmNotAllowed(pid)),
+            Some(board) => {
+                board.set_locked(false);
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns true if the specified player has confirmed their placements. Returns
+    /// false if the player doesn't exist.
+    pub fn is_confirmed<Q>(&self, pid: &Q) -> bool
+    where
+        P: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.boards.get(pid).is_some_and(|board| board.locked())
+    }
+
+    /// Get the number of additional shots the specified player gets each turn. Returns 0
+    /// if the player doesn't exist.
+    pub fn extra_shots<Q>(&self, pid: &Q) -> u8
+    where
+        P: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.extra_shots.get(pid).copied().unwrap_or(0)
+    }
+
+    /// Give the specified player additional shots each turn, as a handicap. With `extra`
+    /// additional shots, the player's turn lasts for `extra + 1` shots. Defaults to 0.
+    pub fn set_extra_shots(&mut self, pid: P, extra: u8) -> Result<(), HandicapError<P>> {
+        if !self.boards.contains_key(&pid) {
+            Err(HandicapError::UnknownPlayer(pid))
+        } else if extra == 0 {
+            self.extra_shots.remove(&pid);
+            Ok(())
+        } else {
+            self.extra_shots.insert(pid, extra);
+            Ok(())
+        }
+    }
+
+    /// Whether players may unconfirm their placements once confirmed.
+    pub fn allow_unconfirm(&self) -> bool {
+        self.allow_unconfirm
+    }
+
+    /// Set whether players may unconfirm their placements once confirmed. Defaults to
+    /// `false`.
+    pub fn set_allow_unconfirm(&mut self, allow: bool) {
+        self.allow_unconfirm = allow;
+    }
+
+    /// Whether players must confirm their placements before the game can start.
+    pub fn require_confirmation(&self) -> bool {
+        self.require_confirmation
+    }
+
+    /// Set whether players must confirm their placements before the game can start,
+    /// rather than just placing all of their ships. Defaults to `false`.
+    pub fn set_require_confirmation(&mut self, require: bool) {
+        self.require_confirmation = require;
+    }
+
+    /// Whether empty cells around a ship are revealed when it sinks. See
+    /// [`set_auto_reveal_adjacent_on_sink`][GameSetup::set_auto_reveal_adjacent_on_sink].
+    pub fn auto_reveal_adjacent_on_sink(&self) -> bool {
+        self.auto_reveal_adjacent_on_sink
+    }
+
+    /// Set whether, when a ship sinks, the un-shot cells around it are marked as shot,
+    /// saving players from shooting water which can't hold a ship. The revealed cells are
+    /// listed in the events from [`Game::shoot_with_events`], and don't use up any shots.
+    /// Defaults to `false`.
+    ///
+    /// Cells next to a ship are only known to be empty if ships can't touch, so this only
+    /// takes effect while the [`fairness_policy`][GameSetup::fairness_policy] has
+    /// [`no_adjacent_ships`][FairnessPolicy::no_adjacent_ships] set. Otherwise it does
+    /// nothing.
+    pub fn set_auto_reveal_adjacent_on_sink(&mut self, reveal: bool) {
+        self.auto_reveal_adjacent_on_sink = reveal;
+    }
+
+    /// Whether the game started from this setup will reveal cells around sunk ships.
+    fn auto_reveals(&self) -> bool {
+        self.auto_reveal_adjacent_on_sink
+            && self
+                .fairness_policy
+                .as_ref()
+                .is_some_and(|policy| policy.no_adjacent_ships)
+    }
+
     /// Checks if at least two players have been added to the game and all players are
-    /// ready
+    /// ready. If confirmation is required, all players must also have confirmed their
+    /// placements. Use [`validate`][GameSetup::validate] to find out why the game isn't
+    /// ready.
     pub fn ready(&self) -> bool {
-        self.boards.len() >= 2 && self.boards.values().all(|board| board.ready())
+        self.validate().is_ok()
+    }
+
+    /// Check whether the game can start, returning the first problem found, checking
+    /// players in turn order. A player with no ships is always rejected, since they would
+    /// be defeated before the first turn.
+    pub fn validate(&self) -> Result<(), ValidateError<P>> {
+        if self.boards.len() < 2 {
+            return Err(ValidateError::TooFewPlayers);
+        }
+        for pid in &self.turn_order {
+            let board = &self.boards[pid];
+            if board.iter_ships().next().is_none() {
+                return Err(ValidateError::NoShips(pid.clone()));
+            }
+            if !board.ready() {
+                return Err(ValidateError::UnplacedShips(pid.clone()));
+            }
+            if self.require_confirmation && !board.locked() {
+                return Err(ValidateError::Unconfirmed(pid.clone()));
+            }
+        }
+        if let Some(policy) = &self.fairness_policy {
+            if let Err(violations) = self.validate_fairness(policy) {
+                let unfair = self
+                    .turn_order
+                    .iter()
+                    .find(|pid| violations.iter().any(|v| v.player() == *pid))
+                    .unwrap();
+                return Err(ValidateError::Unfair(unfair.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the fairness policy players' setups must follow before the game can start, if
+    /// any.
+    pub fn fairness_policy(&self) -> Option<&FairnessPolicy> {
+        self.fairness_policy.as_ref()
+    }
+
+    /// Set the fairness policy players' setups must follow before the game can start.
+    /// While a policy is attached, [`validate`][GameSetup::validate] fails with
+    /// [`ValidateError::Unfair`] for setups which break it.
+    pub fn set_fairness_policy(&mut self, policy: Option<FairnessPolicy>) {
+        self.fairness_policy = policy;
+    }
+
+    /// Check every player's setup against the given policy, returning all of the
+    /// violations found, in turn order. Fleets and dimensions are compared against the
+    /// first player in turn order.
+    pub fn validate_fairness(
+        &self,
+        policy: &FairnessPolicy,
+    ) -> Result<(), Vec<FairnessViolation<P, I>>> {
+        let mut violations = Vec::new();
+        let first = match self.turn_order.first() {
+            Some(first) => &self.boards[first],
+            None => return Ok(()),
+        };
+        for pid in &self.turn_order {
+            let board = &self.boards[pid];
+            if policy.same_fleet {
+                for ship in first.iter_ships() {
+                    let cells = ship.placement().map(|placement| placement.len());
+                    let other = board.get_ship(ship.id());
+                    if other.map(|other| other.placement().map(|p| p.len())) != Some(cells) {
+                        violations.push(FairnessViolation::FleetMismatch {
+                            player: pid.clone(),
+                            ship: ship.id().clone(),
+                        });
+                    }
+                }
+                for ship in board.iter_ships() {
+                    if first.get_ship(ship.id()).is_none() {
+                        violations.push(FairnessViolation::FleetMismatch {
+                            player: pid.clone(),
+                            ship: ship.id().clone(),
+                        });
+                    }
+                }
+            }
+            if policy.no_adjacent_ships {
+                let mut seen = HashSet::new();
+                let dim = board.dimensions();
+                for ship in board.iter_ships() {
+                    let coords = ship.placement().into_iter().flatten();
+                    for neighbor in coords.flat_map(|coord| dim.neighbors(coord.clone())) {
+                        let other = match board.get_coord(&neighbor) {
+                            Some(other) if other != ship.id() => other,
+                            _ => continue,
+                        };
+                        // Each pair is reported once, from whichever ship is reached first.
+                        let pair = (ship.id().clone(), other.clone());
+                        if seen.contains(&(pair.1.clone(), pair.0.clone())) || !seen.insert(pair) {
+                            continue;
+                        }
+                        violations.push(FairnessViolation::AdjacentShips {
+                            player: pid.clone(),
+                            ship: ship.id().clone(),
+                            other: other.clone(),
+                        });
+                    }
+                }
+            }
+            if let Some(max) = policy.max_edge_cells {
+                let dim = board.dimensions();
+                let most = most_neighbors(dim);
+                let count = board
+                    .iter_ships()
+                    .flat_map(|ship| ship.placement().into_iter().flatten())
+                    .filter(|coord| is_edge(dim, coord, most))
+                    .count();
+                if count > max {
+                    violations.push(FairnessViolation::TooManyEdgeCells {
+                        player: pid.clone(),
+                        count,
+                    });
+                }
+            }
+            if policy.same_dimensions && !same_layout(first.dimensions(), board.dimensions()) {
+                violations.push(FairnessViolation::DimensionMismatch {
+                    player: pid.clone(),
+                });
+            }
+        }
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    /// Propose a fleet which every player will use, replacing any earlier proposal as a
+    /// counter-proposal. The proposer accepts their own proposal, and everyone else's
+    /// acceptance of an earlier proposal is withdrawn. Once every player has accepted,
+    /// each board's ships are replaced with the fleet, unplaced, and the boards' rosters
+    /// are locked so ships can no longer be added or removed. Negotiate after every player
+    /// has been added, since players added later aren't part of the agreement.
+    pub fn propose_fleet(&mut self, pid: P, fleet: Vec<(I, S)>) -> Result<(), NegotiationError<P>>
+    where
+        S: Clone,
+    {
+        if self.fleets_agreed {
+            return Err(NegotiationError::AlreadyAgreed);
+        }
+        if !self.boards.contains_key(&pid) {
+            return Err(NegotiationError::UnknownPlayer(pid));
+        }
+        let mut ids = HashSet::with_capacity(fleet.len());
+        if !fleet.iter().all(|(id, _)| ids.insert(id)) {
+            return Err(NegotiationError::DuplicateShip(pid));
+        }
+        self.fleet_acceptances.clear();
+        self.fleet_acceptances.insert(pid.clone());
+        self.fleet_proposal = Some((pid, fleet));
+        self.finish_negotiation();
+        Ok(())
+    }
+
+    /// Accept the current fleet proposal on behalf of the given player. If they were the
+    /// last player to accept, the fleet is applied to every board as described in
+    /// [`propose_fleet`][GameSetup::propose_fleet].
+    pub fn accept_fleet(&mut self, pid: P) -> Result<(), NegotiationError<P>>
+    where
+        S: Clone,
+    {
+        if self.fleets_agreed {
+            return Err(NegotiationError::AlreadyAgreed);
+        }
+        if !self.boards.contains_key(&pid) {
+            return Err(NegotiationError::UnknownPlayer(pid));
+        }
+        if self.fleet_proposal.is_none() {
+            return Err(NegotiationError::NoProposal(pid));
+        }
+        self.fleet_acceptances.insert(pid);
+        self.finish_negotiation();
+        Ok(())
+    }
+
+    /// Get the current fleet proposal and the player who made it, if any. Once fleets are
+    /// agreed, this is the agreed fleet.
+    pub fn fleet_proposal(&self) -> Option<(&P, &[(I, S)])> {
+        self.fleet_proposal
+            .as_ref()
+            .map(|(pid, fleet)| (pid, fleet.as_slice()))
+    }
+
+    /// Returns true if every player has accepted the same fleet proposal.
+    pub fn fleets_agreed(&self) -> bool {
+        self.fleets_agreed
+    }
+
+    /// If every player has accepted the current proposal, apply it to every board and lock
+    /// their rosters.
+    fn finish_negotiation(&mut self)
+    where
+        S: Clone,
+    {
+        let fleet = match &self.fleet_proposal {
+            Some((_, fleet)) => fleet,
+            None => return,
+        };
+        if !self
+            .turn_order
+            .iter()
+            .all(|pid| self.fleet_acceptances.contains(pid))
+        {
+            return;
+        }
+        for board in self.boards.values_mut() {
+            board.replace_roster(fleet.iter().cloned());
+        }
+        self.fleets_agreed = true;
+    }
+
+    /// Copy the source player's ship placements onto each of the target players. Each
+    /// target must have the same dimensions and the same set of ship IDs as the source,
+    /// with each ship having the same shape. Any existing placements on the targets are
+    /// replaced, and ships which are unplaced on the source are left unplaced on the
+    /// targets. Targets listed more than once are only mirrored once, and the source is
+    /// skipped if listed. If any target can't be mirrored, returns an error and leaves all
+    /// boards unchanged.
+    pub fn mirror_placements(&mut self, from: &P, to: &[P]) -> Result<(), MirrorError<P>>
+    where
+        D: PartialEq,
+        S: PartialEq,
+    {
+        let source = match self.boards.get(from) {
+            Some(source) => source,
+            None => return Err(MirrorError::UnknownPlayer(from.clone())),
+        };
+        let placements: Vec<_> = source
+            .iter_ships()
+            .map(|ship| (ship.id().clone(), ship.placement().cloned()))
+            .collect();
+        let mut seen = HashSet::with_capacity(to.len());
+        let targets: Vec<_> = to
+            .iter()
+            .filter(|pid| *pid != from && seen.insert(*pid))
+            .collect();
+        for &pid in &targets {
+            let target = match self.boards.get(pid) {
+                Some(target) => target,
+                None => return Err(MirrorError::UnknownPlayer(pid.clone())),
+            };
+            if target.dimensions() != source.dimensions() {
+                return Err(MirrorError::DimensionMismatch(pid.clone()));
+            }
+            if target.locked() {
+                return Err(MirrorError::Locked(pid.clone()));
+            }
+            if target.iter_ships().count() != placements.len()
+                || source.iter_ships().any(|ship| {
+                    !target
+                        .get_ship(ship.id())
+                        .is_some_and(|other| other.shape() == ship.shape())
+                })
+            {
+                return Err(MirrorError::RosterMismatch(pid.clone()));
+            }
+        }
+
+        let mut previous = Vec::with_capacity(targets.len());
+        for pid in targets {
+            let target = self.boards.get_mut(pid).unwrap();
+            let (old, ok) = replace_placements(target, &placements);
+            previous.push((pid, old));
+            if !ok {
+                // Restoring the old placements always succeeds, since they were valid
+                // before and each board is cleared before they are reapplied.
+                for (pid, old) in previous.into_iter().rev() {
+                    replace_placements(self.boards.get_mut(pid).unwrap(), &old);
+                }
+                return Err(MirrorError::InvalidPlacement(pid.clone()));
+            }
+        }
+        Ok(())
     }
 
     /// Get the board for the player with the specified ID.
-    pub fn get_board<Q: ?Sized>(&self, pid: &Q) -> Option<&BoardSetup<I, D, S>>
+    pub fn get_board<Q>(&self, pid: &Q) -> Option<&BoardSetup<I, D, S>>
     where
         P: Borrow<Q>,
-        Q: Eq + Hash,
+        Q: Eq + Hash + ?Sized,
     {
         self.boards.get(pid)
     }
 
     /// Mutably get the board for the player with the specified ID.
-    pub fn get_board_mut<Q: ?Sized>(&mut self, pid: &Q) -> Option<&mut BoardSetup<I, D, S>>
+    pub fn get_board_mut<Q>(&mut self, pid: &Q) -> Option<&mut BoardSetup<I, D, S>>
     where
         P: Borrow<Q>,
-        Q: Eq + Hash,
+        Q: Eq + Hash + ?Sized,
     {
         self.boards.get_mut(pid)
     }
+
+    /// Iterate the player ids and setup boards in turn order.
+    pub fn iter_boards(&self) -> impl Iterator<Item = (&P, &BoardSetup<I, D, S>)> {
+        self.turn_order
+            .iter()
+            .map(move |pid| (pid, &self.boards[pid]))
+    }
+
+    /// Iterate the player ids and mutable setup boards in turn order.
+    pub fn iter_boards_mut(&mut self) -> impl Iterator<Item = (&P, &mut BoardSetup<I, D, S>)> {
+        let order: HashMap<_, _> = self
+            .turn_order
+            .iter()
+            .enumerate()
+            .map(|(i, pid)| (pid, i))
+            .collect();
+        let mut boards: Vec<_> = self.boards.iter_mut().collect();
+        boards.sort_by_key(|(pid, _)| order[pid]);
+        boards.into_iter()
+    }
+}
+
+/// Placement of each ship on a board, by ship ID.
+type Placements<I, C> = Vec<(I, Option<ShapeProjection<C>>)>;
+
+/// Clear the placements of the given ships on the board, then apply the given
+/// placements. Returns the previous placements, and whether all of the new placements
+/// succeeded. The board must not be locked.
+fn replace_placements<I: ShipId, D: Dimensions, S: ShipShape<D>>(
+    board: &mut BoardSetup<I, D, S>,
+    placements: &Placements<I, D::Coordinate>,
+) -> (Placements<I, D::Coordinate>, bool) {
+    let old = placements
+        .iter()
+        .map(|(id, _)| {
+            let mut ship = board.get_ship_mut(id).unwrap();
+            // Callers check that the board isn't locked.
+            (id.clone(), ship.unplace().unwrap())
+        })
+        .collect();
+    let mut ok = true;
+    for (id, placement) in placements {
+        if let Some(placement) = placement {
+            ok &= board
+                .get_ship_mut(id)
+                .unwrap()
+                .place(placement.clone())
+                .is_ok();
+        }
+    }
+    (old, ok)
 }
 
 impl<P: PlayerId, I: ShipId, D: Dimensions, S: ShipShape<D>> Default for GameSetup<P, I, D, S> {
@@ -124,7 +694,72 @@ impl<P: PlayerId, I: ShipId, D: Dimensions, S: ShipShape<D>> Default for GameSet
     }
 }
 
+/// Rules for fair setups, such as for tournaments, checked by
+/// [`GameSetup::validate_fairness`]. Every rule is off by default.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct FairnessPolicy {
+    /// Every player must have the same ship IDs as the first player in turn order, with
+    /// each ship covering the same number of cells.
+    pub same_fleet: bool,
+    /// None of a player's ships may occupy cells neighboring another of their ships.
+    pub no_adjacent_ships: bool,
+    /// Most ship cells a player may have on the edge of their board, if limited. A cell
+    /// is on the edge if it has fewer neighbors than the best-connected cell of the board,
+    /// so boards which wrap all the way around have no edge.
+    pub max_edge_cells: Option<usize>,
+    /// Every player's board must have the same dimensions as the first player's board:
+    /// the same cells, at the same indexes, with the same neighbors.
+    pub same_dimensions: bool,
+}
+
+/// Get the number of neighbors of the best-connected cell of the dimensions.
+fn most_neighbors<D: Dimensions>(dim: &D) -> usize {
+    (0..dim.total_size())
+        .filter_map(|i| dim.try_un_linearize(i))
+        .map(|cell| dim.neighbors(cell).count())
+        .max()
+        .unwrap_or(0)
+}
+
+/// Check whether the coordinate has fewer neighbors than the best-connected cell of the
+/// dimensions, which has `most` neighbors as found by [`most_neighbors`].
+fn is_edge<D: Dimensions>(dim: &D, coord: &D::Coordinate, most: usize) -> bool {
+    dim.neighbors(coord.clone()).count() < most
+}
+
+/// Check whether two dimensions have the same cells, at the same indexes, with the same
+/// neighbors. Dimensions aren't required to implement `PartialEq`, so this compares
+/// them through the [`Dimensions`] interface.
+fn same_layout<D: Dimensions>(a: &D, b: &D) -> bool {
+    a.total_size() == b.total_size()
+        && (0..a.total_size()).all(|i| match (a.try_un_linearize(i), b.try_un_linearize(i)) {
+            (Some(ca), Some(cb)) => ca == cb && a.neighbors(ca.clone()).eq(b.neighbors(cb.clone())),
+            (None, None) => true,
+            _ => false,
+        })
+}
+
+/// Controls what happens when a player shoots a cell that was already shot.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum RepeatShotPolicy {
+    /// The shot is rejected with [`CannotShootReason::AlreadyShot`] and the player must
+    /// choose another target.
+    Reject,
+    /// The shot is accepted as a [`ShotOutcome::Wasted`] shot and the turn passes to the
+    /// next player.
+    Waste,
+}
+
+impl Default for RepeatShotPolicy {
+    /// Shots at already-shot cells are rejected by default.
+    fn default() -> Self {
+        RepeatShotPolicy::Reject
+    }
+}
+
 /// Result of a shot on a single player's board.
+#[derive(Debug)]
+#[non_exhaustive]
 pub enum ShotOutcome<I> {
     /// The shot did not hit anything.
     Miss,
@@ -138,13 +773,46 @@ pub enum ShotOutcome<I> {
     /// The shot hit the ship with the given ID and all players but the current player are
     /// now defeated. The current player is the winner.
     Victory(I),
+    /// The shot targeted a cell which was already shot, and was wasted. Only produced
+    /// under [`RepeatShotPolicy::Waste`].
+    Wasted,
 }
 
 impl<I> ShotOutcome<I> {
+    /// Returns true if this is [`ShotOutcome::Miss`].
+    pub fn is_miss(&self) -> bool {
+        matches!(self, ShotOutcome::Miss)
+    }
+
+    /// Returns true if this is [`ShotOutcome::Hit`].
+    pub fn is_hit(&self) -> bool {
+        matches!(self, ShotOutcome::Hit(_))
+    }
+
+    /// Returns true if this is [`ShotOutcome::Sunk`].
+    pub fn is_sunk(&self) -> bool {
+        matches!(self, ShotOutcome::Sunk(_))
+    }
+
+    /// Returns true if this is [`ShotOutcome::Defeated`].
+    pub fn is_defeated(&self) -> bool {
+        matches!(self, ShotOutcome::Defeated(_))
+    }
+
+    /// Returns true if this is [`ShotOutcome::Victory`].
+    pub fn is_victory(&self) -> bool {
+        matches!(self, ShotOutcome::Victory(_))
+    }
+
+    /// Returns true if this is [`ShotOutcome::Wasted`].
+    pub fn is_wasted(&self) -> bool {
+        matches!(self, ShotOutcome::Wasted)
+    }
+
     /// Get the id of the ship that was hit.
     pub fn ship(&self) -> Option<&I> {
         match self {
-            ShotOutcome::Miss => None,
+            ShotOutcome::Miss | ShotOutcome::Wasted => None,
             ShotOutcome::Hit(ref id)
             | ShotOutcome::Sunk(ref id)
             | ShotOutcome::Defeated(ref id)
@@ -155,16 +823,55 @@ impl<I> ShotOutcome<I> {
     /// Extract the id of the ship that was hit from this result.
     pub fn into_ship(self) -> Option<I> {
         match self {
-            ShotOutcome::Miss => None,
+            ShotOutcome::Miss | ShotOutcome::Wasted => None,
             ShotOutcome::Hit(id)
             | ShotOutcome::Sunk(id)
             | ShotOutcome::Defeated(id)
             | ShotOutcome::Victory(id) => Some(id),
         }
     }
+
+    /// Describe this outcome as a human-readable string. Ship names are produced by the
+    /// given `namer`, so callers control how ships are referred to.
+    pub fn describe(&self, namer: impl Fn(&I) -> String) -> String {
+        self.describe_in(&DefaultLexicon(namer))
+    }
+
+    /// Describe this outcome as a human-readable string using the given [`Lexicon`].
+    pub fn describe_in(&self, lexicon: &(impl Lexicon<I> + ?Sized)) -> String {
+        match self {
+            ShotOutcome::Miss => lexicon.miss(),
+            ShotOutcome::Hit(id) => lexicon.hit(id),
+            ShotOutcome::Sunk(id) => lexicon.sunk(id),
+            ShotOutcome::Defeated(id) => lexicon.defeated(id),
+            ShotOutcome::Victory(id) => lexicon.victory(id),
+            ShotOutcome::Wasted => lexicon.wasted(),
+        }
+    }
+
+    /// Convert back to the outcome of the shot on the target's board alone. This is the
+    /// reverse of the `From<board::ShotOutcome>` conversion: [`ShotOutcome::Victory`]
+    /// becomes [`Defeated`][BoardShotOutcome::Defeated], since on the target's board the
+    /// winning shot just defeats its player. Returns `None` for [`ShotOutcome::Wasted`],
+    /// which has no board-level equivalent because the board rejected the shot.
+    pub fn downgrade(self) -> Option<BoardShotOutcome<I>> {
+        match self {
+            ShotOutcome::Miss => Some(BoardShotOutcome::Miss),
+            ShotOutcome::Hit(id) => Some(BoardShotOutcome::Hit(id)),
+            ShotOutcome::Sunk(id) => Some(BoardShotOutcome::Sunk(id)),
+            ShotOutcome::Defeated(id) | ShotOutcome::Victory(id) => {
+                Some(BoardShotOutcome::Defeated(id))
+            }
+            ShotOutcome::Wasted => None,
+        }
+    }
 }
 
 impl<I> From<BoardShotOutcome<I>> for ShotOutcome<I> {
+    /// Converts the outcome on a single board to the game outcome, without knowing about
+    /// other players. A board [`Defeated`][BoardShotOutcome::Defeated] always becomes
+    /// [`ShotOutcome::Defeated`], never [`ShotOutcome::Victory`]; the game decides
+    /// whether a defeat is also a victory.
     fn from(shot: BoardShotOutcome<I>) -> Self {
         match shot {
             BoardShotOutcome::Miss => ShotOutcome::Miss,
@@ -175,7 +882,37 @@ impl<I> From<BoardShotOutcome<I>> for ShotOutcome<I> {
     }
 }
 
-/// Handles gameplay.
+/// Stage of play for a [`Game`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum GameState {
+    /// Players are taking turns.
+    Playing,
+    /// The game is paused, and shots are refused until it is resumed.
+    Paused,
+    /// A player has won.
+    Over,
+}
+
+impl GameState {
+    /// Returns true if this is [`GameState::Playing`].
+    pub fn is_playing(&self) -> bool {
+        matches!(self, GameState::Playing)
+    }
+
+    /// Returns true if this is [`GameState::Paused`].
+    pub fn is_paused(&self) -> bool {
+        matches!(self, GameState::Paused)
+    }
+
+    /// Returns true if this is [`GameState::Over`].
+    pub fn is_over(&self) -> bool {
+        matches!(self, GameState::Over)
+    }
+}
+
+/// Handles gameplay. Like [`GameSetup`], the game is `Send` and `Sync` whenever its
+/// player IDs, ship IDs, and dimensions are, so it can be moved to another thread.
 pub struct Game<P: PlayerId, I: ShipId, D: Dimensions> {
     /// Gameplay boards for the players.
     boards: HashMap<P, Board<I, D>>,
@@ -185,6 +922,24 @@ pub struct Game<P: PlayerId, I: ShipId, D: Dimensions> {
 
     /// Counter for the current player turn as an index in `turn_order`.
     current: usize,
+
+    /// How shots at already-shot cells are handled.
+    repeat_shot_policy: RepeatShotPolicy,
+
+    /// Number of additional shots each turn for players with a handicap.
+    extra_shots: HashMap<P, u8>,
+
+    /// Number of additional shots the current player has left this turn.
+    shots_left: u8,
+
+    /// Whether shots are currently refused.
+    paused: bool,
+
+    /// Whether empty cells around sunk ships are revealed.
+    auto_reveal: bool,
+
+    /// Each player's notes on each opponent's board, by player then opponent.
+    annotations: HashMap<P, HashMap<P, AnnotationMap<D::Coordinate>>>,
 }
 
 impl<P: PlayerId, I: ShipId, D: Dimensions> Game<P, I, D> {
@@ -193,61 +948,373 @@ impl<P: PlayerId, I: ShipId, D: Dimensions> Game<P, I, D> {
         &self.turn_order[self.current]
     }
 
-    /// Get the status of the game. Returns `None` if the game is in progress, otherwise
-    /// returns the winner.
-    pub fn winner(&self) -> Option<&P> {
-        let remaining = self
-            .boards
-            .values()
-            .filter(|board| !board.defeated())
-            .count();
-        debug_assert!(remaining > 0);
-        if remaining == 1 {
-            Some(self.current())
+    /// Make it the given player's turn, without changing the turn order. Play continues
+    /// in turn order from that player. Fails if the player is not part of the game or
+    /// has already been defeated.
+    pub fn set_current(&mut self, pid: &P) -> Result<(), SetCurrentError<P>> {
+        let idx = self
+            .turn_order
+            .iter()
+            .position(|p| p == pid)
+            .ok_or_else(|| SetCurrentError::UnknownPlayer(pid.clone()))?;
+        if self.boards[pid].defeated() {
+            return Err(SetCurrentError::Defeated(pid.clone()));
+        }
+        self.current = idx;
+        self.shots_left = self.extra_shots(pid);
+        Ok(())
+    }
+
+    /// Get the number of shots the current player has left this turn, including the
+    /// current shot.
+    pub fn shots_remaining(&self) -> usize {
+        self.shots_left as usize + 1
+    }
+
+    /// Get the number of additional shots the specified player gets each turn. Returns 0
+    /// if the player doesn't exist.
+    pub fn extra_shots<Q>(&self, pid: &Q) -> u8
+    where
+        P: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.extra_shots.get(pid).copied().unwrap_or(0)
+    }
+
+    /// Get the policy for shots at cells which were already shot.
+    pub fn repeat_shot_policy(&self) -> RepeatShotPolicy {
+        self.repeat_shot_policy
+    }
+
+    /// Whether empty cells around a ship are revealed when it sinks. Only true if the
+    /// setup asked for it and didn't allow ships to touch, see
+    /// [`GameSetup::set_auto_reveal_adjacent_on_sink`].
+    pub fn auto_reveal_adjacent_on_sink(&self) -> bool {
+        self.auto_reveal
+    }
+
+    /// Get the current stage of play. A game which is over is [`GameState::Over`] even if
+    /// it was paused.
+    pub fn state(&self) -> GameState {
+        if self.winner().is_some() {
+            GameState::Over
+        } else if self.paused {
+            GameState::Paused
         } else {
-            None
+            GameState::Playing
+        }
+    }
+
+    /// Pause the game, so that shots are refused with [`CannotShootReason::Paused`] until
+    /// it is resumed. Returns true if the game was playing and is now paused.
+    pub fn pause(&mut self) -> bool {
+        let playing = self.state().is_playing();
+        if playing {
+            self.paused = true;
+        }
+        playing
+    }
+
+    /// Resume a paused game. Returns true if the game was paused and is now playing.
+    pub fn resume(&mut self) -> bool {
+        let paused = self.state().is_paused();
+        if paused {
+            self.paused = false;
+        }
+        paused
+    }
+
+    /// Get the status of the game. Returns the winner if exactly one player has not been
+    /// defeated, regardless of whose turn it is, otherwise returns `None`.
+    pub fn winner(&self) -> Option<&P> {
+        let mut remaining = self
+            .turn_order
+            .iter()
+            .filter(|pid| !self.boards[*pid].defeated());
+        match (remaining.next(), remaining.next()) {
+            (Some(winner), None) => Some(winner),
+            _ => None,
         }
     }
 
     /// Get a reference to the board for the specified player.
-    pub fn get_board<Q: ?Sized>(&self, pid: &Q) -> Option<&Board<I, D>>
+    pub fn get_board<Q>(&self, pid: &Q) -> Option<&Board<I, D>>
     where
         P: Borrow<Q>,
-        Q: Eq + Hash,
+        Q: Eq + Hash + ?Sized,
     {
         self.boards.get(pid)
     }
 
-    /// Iterate the player ids and boards in turn-order.
+    /// Get the winning player's board, for example to show their surviving ships. Returns
+    /// `None` while the game is still in progress.
+    pub fn winner_board(&self) -> Option<&Board<I, D>> {
+        self.winner().map(|pid| &self.boards[pid])
+    }
+
+    /// Get a mutable reference to the board for the specified player. Only for use within
+    /// the crate, since shooting through the board would bypass the turn order.
+    pub(crate) fn get_board_mut<Q>(&mut self, pid: &Q) -> Option<&mut Board<I, D>>
+    where
+        P: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.boards.get_mut(pid)
+    }
+
+    /// Get the total number of ships across all players' boards.
+    pub fn total_ships(&self) -> usize {
+        self.boards
+            .values()
+            .map(|board| board.iter_ships().count())
+            .sum()
+    }
+
+    /// Get the total number of sunk ships across all players' boards.
+    pub fn total_ships_sunk(&self) -> usize {
+        self.boards
+            .values()
+            .map(|board| board.iter_ships().filter(|ship| ship.sunk()).count())
+            .sum()
+    }
+
+    /// Iterate the player ids and boards in turn-order. Defeated players are included, so
+    /// their boards can still be inspected.
     pub fn iter_boards(&self) -> impl Iterator<Item = (&P, &Board<I, D>)> {
         self.turn_order
             .iter()
             .map(move |pid| (pid, &self.boards[pid]))
     }
 
+    /// Iterate every occupied cell across all players' boards, yielding the ID of the
+    /// player who owns the board, the ID of the ship in the cell, and the cell's
+    /// coordinate. Boards are visited in turn order.
+    pub fn iter_all_occupied(&self) -> impl Iterator<Item = (&P, &I, &D::Coordinate)> {
+        self.iter_boards().flat_map(|(pid, board)| {
+            board
+                .iter_ships()
+                .flat_map(move |ship| ship.coords().map(move |coord| (pid, ship.id(), coord)))
+        })
+    }
+
+    /// Iterate the cells of the target's board which the shooter may shoot right now, in
+    /// index order: cells that are in bounds and haven't been shot yet. Empty if it isn't
+    /// the shooter's turn, the game is paused or over, the shooter is the target, or the
+    /// target is unknown or already defeated. Cells which were already shot aren't
+    /// included even under [`RepeatShotPolicy::Waste`], since shooting them only wastes
+    /// the shot.
+    pub fn legal_targets<'a>(
+        &'a self,
+        shooter: &P,
+        target: &P,
+    ) -> impl 'a + Iterator<Item = D::Coordinate> {
+        let turn = !self.paused && self.winner().is_none() && self.current() == shooter;
+        self.targets_for(shooter, target, turn)
+    }
+
+    /// Iterate the cells of the target's board which the shooter could shoot on their
+    /// turn, in index order, for planning ahead. Same as
+    /// [`legal_targets`][Game::legal_targets], except that it ignores whose turn it is and
+    /// whether the game is paused.
+    pub fn would_be_legal_targets<'a>(
+        &'a self,
+        shooter: &P,
+        target: &P,
+    ) -> impl 'a + Iterator<Item = D::Coordinate> {
+        let allowed = self.winner().is_none() && self.boards.contains_key(shooter);
+        self.targets_for(shooter, target, allowed)
+    }
+
+    /// Helper for [`legal_targets`][Game::legal_targets] and
+    /// [`would_be_legal_targets`][Game::would_be_legal_targets]. Yields nothing unless
+    /// `allowed` is true.
+    fn targets_for<'a>(
+        &'a self,
+        shooter: &P,
+        target: &P,
+        allowed: bool,
+    ) -> impl 'a + Iterator<Item = D::Coordinate> {
+        let board = match self.boards.get(target) {
+            Some(board) if allowed && shooter != target && !board.defeated() => Some(board),
+            _ => None,
+        };
+        board.into_iter().flat_map(|board| {
+            board
+                .target_legality()
+                .filter(|(_, status)| status.is_available())
+                .map(|(coord, _)| coord)
+        })
+    }
+
+    /// Use up one of the current player's shots. Once they have no shots left, passes the
+    /// turn to the next player in turn order who has not been defeated.
+    fn advance_turn(&mut self) {
+        if self.shots_left > 0 {
+            self.shots_left -= 1;
+            return;
+        }
+        // The game is not over, so at least one other player is still in the game.
+        loop {
+            self.current = (self.current + 1) % self.turn_order.len();
+            if !self.boards[self.current()].defeated() {
+                break;
+            }
+        }
+        self.shots_left = self.extra_shots(self.current());
+    }
+
+    /// Remove the current player from the game by sinking every ship on their board, then
+    /// pass the turn to the next player who has not been defeated. Used by
+    /// [`run::drive`][crate::game::run::drive] when a player runs out of retries.
+    pub(crate) fn forfeit_current(&mut self) {
+        let board = self.boards.get_mut(&self.turn_order[self.current]).unwrap();
+        let coords: Vec<_> = board
+            .iter_ships()
+            .flat_map(|ship| ship.coords().cloned().collect::<Vec<_>>())
+            .collect();
+        for coord in coords {
+            // Cells which were already hit are rejected, which is fine.
+            let _ = board.shoot(coord);
+        }
+        // At least one other player is still in the game, so this always finds one.
+        self.shots_left = 0;
+        self.advance_turn();
+    }
+
+    /// Get the player's notes on the opponent's board. Returns `None` if either player
+    /// isn't part of the game or they are the same player. Notes are never used by the
+    /// game itself.
+    pub fn annotations<Q>(&self, pid: &Q, opponent: &Q) -> Option<&AnnotationMap<D::Coordinate>>
+    where
+        P: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.annotations.get(pid)?.get(opponent)
+    }
+
+    /// Mutably get the player's notes on the opponent's board, to add or remove notes.
+    /// Returns `None` if either player isn't part of the game or they are the same player.
+    pub fn annotations_mut<Q>(
+        &mut self,
+        pid: &Q,
+        opponent: &Q,
+    ) -> Option<&mut AnnotationMap<D::Coordinate>>
+    where
+        P: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.annotations.get_mut(pid)?.get_mut(opponent)
+    }
+
+    /// Return the game to how it was when it started, for practicing against the same
+    /// layout. Every board's shots and every player's notes are cleared, the first
+    /// player in turn order gets the turn with their full handicap, and the game is
+    /// resumed if paused. Placements, the
+    /// turn order, handicaps, and the repeat shot policy are kept.
+    pub fn reset(&mut self) {
+        for board in self.boards.values_mut() {
+            board.reset_damage();
+        }
+        self.current = 0;
+        self.shots_left = self.extra_shots(self.current());
+        self.paused = false;
+        for maps in self.annotations.values_mut() {
+            maps.values_mut().for_each(AnnotationMap::clear);
+        }
+    }
+
     /// Fire a shot at the specified player, returning the result of the shot or
-    /// an error if the shot was invalid.
+    /// an error if the shot was invalid. Shooting a player who has already been defeated
+    /// fails with [`CannotShootReason::AlreadyDefeated`] without using up the turn, even
+    /// under [`RepeatShotPolicy::Waste`]. Once the current player has used all of their
+    /// shots for the turn, the turn passes to the next player in turn order who has not
+    /// been defeated, skipping any number of defeated players in a row.
     pub fn shoot(
         &mut self,
         target: P,
         coord: D::Coordinate,
     ) -> Result<ShotOutcome<I>, ShotError<P, D::Coordinate>> {
+        self.shoot_with_events(target, coord)
+            .map(|(outcome, _)| outcome)
+    }
+
+    /// Fire a shot like [`shoot`][Game::shoot], additionally returning the secondary
+    /// effects of the shot on the target's board: the ship which sank, whether the target
+    /// was defeated, and any cells revealed around the sunk ship. A
+    /// [`ShotOutcome::Wasted`] shot has no effects.
+    #[allow(clippy::type_complexity)]
+    pub fn shoot_with_events(
+        &mut self,
+        target: P,
+        coord: D::Coordinate,
+    ) -> Result<(ShotOutcome<I>, BoardEvents<I, D::Coordinate>), ShotError<P, D::Coordinate>> {
+        #[cfg(feature = "tracing")]
+        let (shooter, traced) = (self.current().clone(), target.clone());
+        let res = self.fire(target, coord);
+        #[cfg(feature = "tracing")]
+        match &res {
+            Ok((outcome, _)) => {
+                tracing::debug!(?shooter, target = ?traced, ?outcome, "game shot")
+            }
+            Err(err) => tracing::debug!(
+                ?shooter,
+                target = ?traced,
+                reason = ?err.reason(),
+                "game shot rejected"
+            ),
+        }
+        res
+    }
+
+    /// Implementation of [`shoot_with_events`][Game::shoot_with_events], without
+    /// instrumentation.
+    #[allow(clippy::type_complexity)]
+    fn fire(
+        &mut self,
+        target: P,
+        coord: D::Coordinate,
+    ) -> Result<(ShotOutcome<I>, BoardEvents<I, D::Coordinate>), ShotError<P, D::Coordinate>> {
         if self.winner().is_some() {
             Err(ShotError::new(
                 CannotShootReason::AlreadyOver,
                 target,
                 coord,
             ))
+        } else if self.paused {
+            Err(ShotError::new(CannotShootReason::Paused, target, coord))
         } else if self.current() == &target {
             Err(ShotError::new(CannotShootReason::SelfShot, target, coord))
         } else if let Some(board) = self.boards.get_mut(&target) {
-            match board.shoot(coord) {
-                Ok(BoardShotOutcome::Defeated(id)) if self.winner().is_some() => {
-                    Ok(ShotOutcome::Victory(id))
+            match board.shoot_with_events(coord) {
+                Ok((res, mut events)) => {
+                    if self.auto_reveal {
+                        if let Some(sunk) = &events.sunk {
+                            events.revealed = board.reveal_around(&sunk.coords);
+                        }
+                    }
+                    match res {
+                        BoardShotOutcome::Defeated(id)
+                            if events.defeated && self.winner().is_some() =>
+                        {
+                            Ok((ShotOutcome::Victory(id), events))
+                        }
+                        res => {
+                            self.advance_turn();
+                            Ok((res.into(), events))
+                        }
+                    }
                 }
-                Ok(res) => {
-                    self.current = (self.current + 1) % self.turn_order.len();
-                    Ok(res.into())
+                Err(err)
+                    if err.reason() == BoardCannotShootReason::AlreadyShot
+                        && self.repeat_shot_policy == RepeatShotPolicy::Waste =>
+                {
+                    self.advance_turn();
+                    let events = BoardEvents {
+                        sunk: None,
+                        defeated: false,
+                        revealed: Vec::new(),
+                    };
+                    Ok((ShotOutcome::Wasted, events))
                 }
                 Err(err) => Err(ShotError::add_context(err, target)),
             }
@@ -259,4 +1326,1372 @@ impl<P: PlayerId, I: ShipId, D: Dimensions> Game<P, I, D> {
             ))
         }
     }
+
+    /// Begin the current player's turn, getting a [`TurnToken`] which holds the game
+    /// until a shot is fired. While the token exists, nothing else can shoot.
+    pub fn begin_turn(&mut self) -> TurnToken<'_, P, I, D> {
+        TurnToken { game: self }
+    }
+}
+
+/// Token for a single shot by the player whose turn it was when the token was created.
+/// Obtained from [`Game::begin_turn`]. The token mutably borrows the game, so a shot
+/// can't be fired through any other path while it exists, and it is consumed by firing.
+///
+/// # Examples
+///
+/// In a game between players `0` and `1`, each with a two-cell ship at (0,0) and (1,0):
+///
+/// ```
+/// # use spacebattleship::prelude::{uniform::GameSetup, Coordinate2D, Line, RectDimensions};
+/// # let mut setup = GameSetup::new();
+/// # for pid in 0..2u8 {
+/// #     let board = setup.add_player(pid, RectDimensions::new(3, 3)).unwrap();
+/// #     board.add_ship(0u8, Line::new(2)).unwrap();
+/// #     let cells = vec![Coordinate2D::new(0, 0), Coordinate2D::new(1, 0)];
+/// #     board.get_ship_mut(&0).unwrap().place(cells).unwrap();
+/// # }
+/// # let mut game = setup.start().ok().unwrap();
+/// let token = game.begin_turn();
+/// assert_eq!(token.player(), &0);
+/// let (outcome, next) = token.shoot(1, Coordinate2D::new(0, 0)).unwrap();
+/// assert!(outcome.is_hit());
+/// assert_eq!(next, 1);
+/// assert_eq!(game.begin_turn().player(), &1);
+/// ```
+///
+/// A token can only fire once, since firing consumes it:
+///
+/// ```compile_fail
+/// # use spacebattleship::prelude::{uniform::GameSetup, Coordinate2D, Line, RectDimensions};
+/// # let mut setup = GameSetup::new();
+/// # for pid in 0..2u8 {
+/// #     let board = setup.add_player(pid, RectDimensions::new(3, 3)).unwrap();
+/// #     board.add_ship(0u8, Line::new(2)).unwrap();
+/// #     let cells = vec![Coordinate2D::new(0, 0), Coordinate2D::new(1, 0)];
+/// #     board.get_ship_mut(&0).unwrap().place(cells).unwrap();
+/// # }
+/// # let mut game = setup.start().ok().unwrap();
+/// let token = game.begin_turn();
+/// token.shoot(1, Coordinate2D::new(0, 0)).unwrap();
+/// token.shoot(1, Coordinate2D::new(1, 0)).unwrap();
+/// ```
+///
+/// Nothing else can shoot while a token is held, including a second token:
+///
+/// ```compile_fail
+/// # use spacebattleship::prelude::{uniform::GameSetup, Coordinate2D, Line, RectDimensions};
+/// # let mut setup = GameSetup::new();
+/// # for pid in 0..2u8 {
+/// #     let board = setup.add_player(pid, RectDimensions::new(3, 3)).unwrap();
+/// #     board.add_ship(0u8, Line::new(2)).unwrap();
+/// #     let cells = vec![Coordinate2D::new(0, 0), Coordinate2D::new(1, 0)];
+/// #     board.get_ship_mut(&0).unwrap().place(cells).unwrap();
+/// # }
+/// # let mut game = setup.start().ok().unwrap();
+/// let token = game.begin_turn();
+/// game.shoot(1, Coordinate2D::new(0, 0)).unwrap();
+/// token.shoot(1, Coordinate2D::new(1, 0)).unwrap();
+/// ```
+///
+/// ```compile_fail
+/// # use spacebattleship::prelude::{uniform::GameSetup, Coordinate2D, Line, RectDimensions};
+/// # let mut setup = GameSetup::new();
+/// # for pid in 0..2u8 {
+/// #     let board = setup.add_player(pid, RectDimensions::new(3, 3)).unwrap();
+/// #     board.add_ship(0u8, Line::new(2)).unwrap();
+/// #     let cells = vec![Coordinate2D::new(0, 0), Coordinate2D::new(1, 0)];
+/// #     board.get_ship_mut(&0).unwrap().place(cells).unwrap();
+/// # }
+/// # let mut game = setup.start().ok().unwrap();
+/// let token = game.begin_turn();
+/// let second = game.begin_turn();
+/// token.shoot(1, Coordinate2D::new(0, 0)).unwrap();
+/// ```
+///
+/// And a token can't outlive the game it was taken from:
+///
+/// ```compile_fail
+/// # use spacebattleship::prelude::{uniform::{GameSetup, TurnToken}, Coordinate2D, Line, RectDimensions};
+/// fn token() -> TurnToken<'static, u8, u8, RectDimensions> {
+/// #     let mut setup = GameSetup::new();
+/// #     for pid in 0..2u8 {
+/// #         let board = setup.add_player(pid, RectDimensions::new(3, 3)).unwrap();
+/// #         board.add_ship(0u8, Line::new(2)).unwrap();
+/// #         let cells = vec![Coordinate2D::new(0, 0), Coordinate2D::new(1, 0)];
+/// #         board.get_ship_mut(&0).unwrap().place(cells).unwrap();
+/// #     }
+/// #     let mut game = setup.start().ok().unwrap();
+///     game.begin_turn()
+/// }
+/// ```
+pub struct TurnToken<'a, P: PlayerId, I: ShipId, D: Dimensions> {
+    game: &'a mut Game<P, I, D>,
+}
+
+impl<'a, P: PlayerId, I: ShipId, D: Dimensions> TurnToken<'a, P, I, D> {
+    /// Get the ID of the player who holds this token.
+    pub fn player(&self) -> &P {
+        self.game.current()
+    }
+
+    /// Get a reference to the game this token is for.
+    pub fn game(&self) -> &Game<P, I, D> {
+        self.game
+    }
+
+    /// Fire a shot as the player holding this token. See [`Game::shoot`]. On success,
+    /// returns the outcome along with the ID of the player whose turn it is next, who
+    /// may be the same player if they have extra shots remaining this turn.
+    pub fn shoot(
+        self,
+        target: P,
+        coord: D::Coordinate,
+    ) -> Result<(ShotOutcome<I>, P), ShotError<P, D::Coordinate>> {
+        let outcome = self.game.shoot(target, coord)?;
+        Ok((outcome, self.game.current().clone()))
+    }
+}
+
+/// Compile-time check that the boards and games are `Send` and `Sync` whenever their
+/// parameters are. Fails to build if a field is added which would break that.
+fn assert_send_sync<P, I, D, S>()
+where
+    P: PlayerId + Send + Sync,
+    I: ShipId + Send + Sync,
+    D: Dimensions + Send + Sync,
+    D::Coordinate: Send + Sync,
+    S: ShipShape<D> + Send + Sync,
+{
+    fn check<T: Send + Sync>() {}
+    check::<Board<I, D>>();
+    check::<BoardSetup<I, D, S>>();
+    check::<Game<P, I, D>>();
+    check::<GameSetup<P, I, D, S>>();
+}
+
+// Instantiating the check with concrete types keeps it from being dead code.
+const _: fn() =
+    assert_send_sync::<u8, u8, crate::board::rectangular::RectDimensions, crate::ships::Line>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        board::{
+            masked::MaskedDimensions,
+            rectangular::{Coordinate, RectDimensions, Wrapping},
+            CannotAddShipReason, CannotPlaceReason, CannotRemoveShipReason,
+        },
+        fixtures::{self, UniformGame, UniformSetup},
+        game::{annotations::Note, simple::Ship},
+        ships::Line,
+    };
+
+    #[test]
+    fn describe_each_outcome() {
+        let namer = |id: &&str| id.to_uppercase();
+        assert_eq!(ShotOutcome::<&str>::Miss.describe(namer), "Miss.");
+        assert_eq!(ShotOutcome::Hit("sub").describe(namer), "Hit SUB!");
+        assert_eq!(ShotOutcome::Sunk("sub").describe(namer), "Sunk SUB!");
+        assert_eq!(
+            ShotOutcome::Defeated("sub").describe(namer),
+            "Sunk SUB! All ships sunk."
+        );
+        assert_eq!(
+            ShotOutcome::Victory("sub").describe(namer),
+            "Sunk SUB! Victory!"
+        );
+        assert_eq!(
+            ShotOutcome::<&str>::Wasted.describe(namer),
+            "Already shot there. Shot wasted."
+        );
+    }
+
+    #[test]
+    fn reversed_turn_order() {
+        let mut setup: UniformSetup = fixtures::uniform_setup();
+        setup.set_turn_order(&[2, 1, 0]).unwrap();
+        assert_eq!(setup.turn_order(), &[2, 1, 0]);
+        let mut game = setup.start().ok().unwrap();
+        let mut seen = Vec::new();
+        for _ in 0..6 {
+            let shooter = *game.current();
+            seen.push(shooter);
+            let target = (shooter + 1) % 3;
+            let miss = Coordinate::new(seen.len(), 9);
+            assert!(game.shoot(target, miss).unwrap().is_miss());
+        }
+        assert_eq!(seen, [2, 1, 0, 2, 1, 0]);
+    }
+
+    #[test]
+    fn turn_order_must_be_permutation() {
+        let mut setup = fixtures::uniform_setup();
+        assert_eq!(
+            setup.set_turn_order(&[0, 1, 3]),
+            Err(TurnOrderError::UnknownPlayer(3))
+        );
+        assert_eq!(
+            setup.set_turn_order(&[0, 1, 1]),
+            Err(TurnOrderError::DuplicatePlayer(1))
+        );
+        assert_eq!(
+            setup.set_turn_order(&[0, 1]),
+            Err(TurnOrderError::MissingPlayer(2))
+        );
+        assert_eq!(setup.turn_order(), &[0, 1, 2]);
+    }
+
+    /// Build a setup with three players who each have the standard fleet, but where only
+    /// player 0 has placed their ships, in the fixture layout. Player 2's destroyer has
+    /// the given length.
+    fn mirror_setup(destroyer_len: usize) -> UniformSetup {
+        let mut setup = UniformSetup::new();
+        for pid in 0..3 {
+            let board = setup.add_player(pid, RectDimensions::new(10, 10)).unwrap();
+            for (row, &ship) in Ship::ALL.iter().enumerate() {
+                let len = match ship {
+                    Ship::Destroyer if pid == 2 => destroyer_len,
+                    _ => ship.len(),
+                };
+                let mut entry = board.add_ship(ship, Line::new(len)).unwrap();
+                if pid == 0 {
+                    let cells = (0..len).map(|x| Coordinate::new(x, row * 2)).collect();
+                    entry.place(cells).unwrap();
+                }
+            }
+        }
+        setup
+    }
+
+    /// Get each of the player's ships and its placement, sorted by ship.
+    fn placements(setup: &UniformSetup, pid: usize) -> Vec<(Ship, Option<Vec<Coordinate>>)> {
+        let board = setup.get_board(&pid).unwrap();
+        let mut ships: Vec<_> = board
+            .iter_ships()
+            .map(|ship| (*ship.id(), ship.placement().cloned()))
+            .collect();
+        ships.sort_by_key(|(ship, _)| Ship::ALL.iter().position(|s| s == ship));
+        ships
+    }
+
+    #[test]
+    fn mirror_onto_other_players() {
+        let mut setup = mirror_setup(2);
+        setup.mirror_placements(&0, &[1, 0, 2, 1]).unwrap();
+        let source = placements(&setup, 0);
+        assert!(source.iter().all(|(_, placement)| placement.is_some()));
+        assert_eq!(placements(&setup, 1), source);
+        assert_eq!(placements(&setup, 2), source);
+        assert!(setup.ready());
+    }
+
+    #[test]
+    fn mirror_rejects_different_shapes() {
+        let mut setup = mirror_setup(3);
+        let before = placements(&setup, 1);
+        assert_eq!(
+            setup.mirror_placements(&0, &[1, 1, 2]),
+            Err(MirrorError::RosterMismatch(2))
+        );
+        assert_eq!(placements(&setup, 1), before);
+        assert!(placements(&setup, 2).iter().all(|(_, p)| p.is_none()));
+    }
+
+    /// Build a setup with two players on 10x10 boards who have no ships yet.
+    fn empty_setup() -> UniformSetup {
+        let mut setup = UniformSetup::new();
+        for pid in 0..2 {
+            setup.add_player(pid, RectDimensions::new(10, 10)).unwrap();
+        }
+        setup
+    }
+
+    /// Get the standard shapes of the given ships.
+    fn fleet(ships: &[Ship]) -> Vec<(Ship, Line)> {
+        ships
+            .iter()
+            .map(|&ship| (ship, Line::new(ship.len())))
+            .collect()
+    }
+
+    /// Get the player's ships, sorted, along with whether each is placed.
+    fn roster(setup: &UniformSetup, pid: usize) -> Vec<(Ship, bool)> {
+        placements(setup, pid)
+            .into_iter()
+            .map(|(ship, placement)| (ship, placement.is_some()))
+            .collect()
+    }
+
+    #[test]
+    fn propose_counter_propose_accept() {
+        let mut setup = empty_setup();
+        assert_eq!(setup.accept_fleet(1), Err(NegotiationError::NoProposal(1)));
+        setup
+            .propose_fleet(0, fleet(&[Ship::Carrier, Ship::Destroyer]))
+            .unwrap();
+        assert!(!setup.fleets_agreed());
+        setup
+            .propose_fleet(1, fleet(&[Ship::Cruiser, Ship::Destroyer]))
+            .unwrap();
+        // Player 0's acceptance of their own proposal was withdrawn by the counter.
+        assert!(!setup.fleets_agreed());
+        assert_eq!(roster(&setup, 0), []);
+        setup.accept_fleet(0).unwrap();
+        assert!(setup.fleets_agreed());
+        assert_eq!(setup.fleet_proposal().unwrap().0, &1);
+        for pid in 0..2 {
+            assert_eq!(
+                roster(&setup, pid),
+                [(Ship::Cruiser, false), (Ship::Destroyer, false)]
+            );
+        }
+        assert_eq!(
+            setup.propose_fleet(0, fleet(&[Ship::Carrier])),
+            Err(NegotiationError::AlreadyAgreed)
+        );
+        assert_eq!(setup.accept_fleet(1), Err(NegotiationError::AlreadyAgreed));
+    }
+
+    #[test]
+    fn proposal_errors() {
+        let mut setup = empty_setup();
+        assert_eq!(
+            setup.propose_fleet(5, fleet(&[Ship::Carrier])),
+            Err(NegotiationError::UnknownPlayer(5))
+        );
+        assert_eq!(
+            setup.propose_fleet(0, fleet(&[Ship::Carrier, Ship::Carrier])),
+            Err(NegotiationError::DuplicateShip(0))
+        );
+        assert!(setup.fleet_proposal().is_none());
+    }
+
+    #[test]
+    fn agreed_roster_is_locked() {
+        let mut setup = empty_setup();
+        setup
+            .get_board_mut(&0)
+            .unwrap()
+            .add_ship(Ship::Battleship, Line::new(4))
+            .unwrap();
+        setup
+            .propose_fleet(0, fleet(&[Ship::Cruiser, Ship::Destroyer]))
+            .unwrap();
+        setup.accept_fleet(1).unwrap();
+        assert!(setup.fleets_agreed());
+        // Ships added before agreement are replaced by the agreed fleet.
+        assert_eq!(
+            roster(&setup, 0),
+            [(Ship::Cruiser, false), (Ship::Destroyer, false)]
+        );
+
+        let board = setup.get_board_mut(&0).unwrap();
+        assert!(board.roster_locked());
+        let err = board.add_ship(Ship::Carrier, Line::new(5)).err().unwrap();
+        assert_eq!(err.reason(), CannotAddShipReason::RosterLocked);
+        assert_eq!(
+            board.remove_ship(&Ship::Cruiser).err(),
+            Some(CannotRemoveShipReason::RosterLocked)
+        );
+
+        // Placement is still allowed, so the game can start.
+        for pid in 0..2 {
+            let board = setup.get_board_mut(&pid).unwrap();
+            for (row, &ship) in [Ship::Cruiser, Ship::Destroyer].iter().enumerate() {
+                let cells = (0..ship.len()).map(|x| Coordinate::new(x, row)).collect();
+                board.get_ship_mut(&ship).unwrap().place(cells).unwrap();
+            }
+        }
+        assert!(setup.start().is_ok());
+    }
+
+    #[test]
+    fn remove_ship_before_agreement() {
+        let mut setup = fixtures::uniform_setup();
+        let board = setup.get_board_mut(&0).unwrap();
+        assert_eq!(board.remove_ship(&Ship::Cruiser).ok(), Some(Line::new(3)));
+        assert_eq!(board.get_coord(&Coordinate::new(0, 4)), None);
+        assert_eq!(
+            board.remove_ship(&Ship::Cruiser).err(),
+            Some(CannotRemoveShipReason::NotFound)
+        );
+        setup.confirm_placements(0).unwrap();
+        assert_eq!(
+            setup
+                .get_board_mut(&0)
+                .unwrap()
+                .remove_ship(&Ship::Carrier)
+                .err(),
+            Some(CannotRemoveShipReason::Locked)
+        );
+    }
+
+    #[test]
+    fn set_current_then_shoot() {
+        let mut game = fixtures::uniform_mid_game();
+        assert_eq!(*game.current(), 0);
+        assert_eq!(game.set_current(&2), Ok(()));
+        assert_eq!(*game.current(), 2);
+        let outcome = game.shoot(1, Coordinate::new(0, 4)).unwrap();
+        assert!(matches!(outcome, ShotOutcome::Hit(Ship::Cruiser)));
+        // Play continues in turn order from the chosen player.
+        assert_eq!(*game.current(), 0);
+        assert!(game
+            .get_board(&1)
+            .unwrap()
+            .get_coord(Coordinate::new(0, 4))
+            .unwrap()
+            .hit());
+    }
+
+    #[test]
+    fn set_current_errors() {
+        let mut game = fixtures::uniform_near_victory();
+        assert_eq!(game.set_current(&7), Err(SetCurrentError::UnknownPlayer(7)));
+        assert_eq!(game.set_current(&2), Err(SetCurrentError::Defeated(2)));
+        assert_eq!(*game.current(), 0);
+    }
+
+    #[test]
+    fn outcome_from_board() {
+        use BoardShotOutcome as B;
+        assert!(matches!(
+            ShotOutcome::from(B::<u8>::Miss),
+            ShotOutcome::Miss
+        ));
+        assert!(matches!(ShotOutcome::from(B::Hit(1)), ShotOutcome::Hit(1)));
+        assert!(matches!(
+            ShotOutcome::from(B::Sunk(2)),
+            ShotOutcome::Sunk(2)
+        ));
+        assert!(matches!(
+            ShotOutcome::from(B::Defeated(3)),
+            ShotOutcome::Defeated(3)
+        ));
+    }
+
+    #[test]
+    fn outcome_downgrade() {
+        use BoardShotOutcome as B;
+        assert!(matches!(ShotOutcome::<u8>::Miss.downgrade(), Some(B::Miss)));
+        assert!(matches!(ShotOutcome::Hit(1).downgrade(), Some(B::Hit(1))));
+        assert!(matches!(ShotOutcome::Sunk(2).downgrade(), Some(B::Sunk(2))));
+        assert!(matches!(
+            ShotOutcome::Defeated(3).downgrade(),
+            Some(B::Defeated(3))
+        ));
+        assert!(matches!(
+            ShotOutcome::Victory(4).downgrade(),
+            Some(B::Defeated(4))
+        ));
+        assert!(ShotOutcome::<u8>::Wasted.downgrade().is_none());
+    }
+
+    #[test]
+    fn pause_and_resume() {
+        let mut game = fixtures::uniform_mid_game();
+        assert_eq!(game.state(), GameState::Playing);
+        assert!(!game.resume());
+        assert!(game.pause());
+        assert!(!game.pause());
+        assert_eq!(game.state(), GameState::Paused);
+
+        let err = game.shoot(1, Coordinate::new(0, 4)).unwrap_err();
+        assert_eq!(
+            err,
+            ShotError::new(CannotShootReason::Paused, 1, Coordinate::new(0, 4))
+        );
+        assert_eq!(*game.current(), 0);
+
+        assert!(game.resume());
+        assert_eq!(game.state(), GameState::Playing);
+        let outcome = game.shoot(1, Coordinate::new(0, 4)).unwrap();
+        assert!(matches!(outcome, ShotOutcome::Hit(Ship::Cruiser)));
+        assert_eq!(*game.current(), 1);
+    }
+
+    #[test]
+    fn over_game_cannot_pause() {
+        let mut game = fixtures::uniform_near_victory();
+        game.shoot(1, Coordinate::new(1, 8)).unwrap();
+        assert_eq!(game.state(), GameState::Over);
+        assert!(!game.pause());
+        assert_eq!(game.state(), GameState::Over);
+    }
+
+    /// Policy with every fairness rule enabled, allowing the given number of edge cells.
+    fn strict_policy(max_edge_cells: usize) -> FairnessPolicy {
+        FairnessPolicy {
+            same_fleet: true,
+            no_adjacent_ships: true,
+            max_edge_cells: Some(max_edge_cells),
+            same_dimensions: true,
+        }
+    }
+
+    #[test]
+    fn fair_setup_passes() {
+        // Each player's carrier is on the top edge and every other ship touches the left
+        // edge, for 9 edge cells.
+        let mut setup = fixtures::uniform_setup();
+        assert_eq!(setup.validate_fairness(&strict_policy(9)), Ok(()));
+        setup.set_fairness_policy(Some(strict_policy(9)));
+        assert!(setup.start().is_ok());
+    }
+
+    #[test]
+    fn unfair_fleet() {
+        let mut setup = fixtures::uniform_setup();
+        setup
+            .get_board_mut(&1)
+            .unwrap()
+            .remove_ship(&Ship::Destroyer)
+            .unwrap();
+        assert_eq!(
+            setup.validate_fairness(&strict_policy(9)),
+            Err(vec![FairnessViolation::FleetMismatch {
+                player: 1,
+                ship: Ship::Destroyer,
+            }])
+        );
+    }
+
+    #[test]
+    fn unfair_adjacent_ships() {
+        let mut setup = fixtures::uniform_setup();
+        let cells = (0..4).map(|x| Coordinate::new(x, 1)).collect();
+        let board = setup.get_board_mut(&2).unwrap();
+        let mut battleship = board.get_ship_mut(&Ship::Battleship).unwrap();
+        battleship.unplace().unwrap();
+        battleship.place(cells).unwrap();
+        let violations = setup.validate_fairness(&strict_policy(9)).unwrap_err();
+        assert_eq!(violations.len(), 1);
+        match &violations[0] {
+            FairnessViolation::AdjacentShips {
+                player: 2,
+                ship,
+                other,
+            } => {
+                let mut pair = [*ship, *other];
+                pair.sort_by_key(|ship| Ship::ALL.iter().position(|s| s == ship));
+                assert_eq!(pair, [Ship::Carrier, Ship::Battleship]);
+            }
+            other => panic!("unexpected violation {:?}", other),
+        }
+    }
+
+    #[test]
+    fn unfair_edge_cells() {
+        let mut setup = fixtures::uniform_setup();
+        let violations: Vec<_> = (0..3)
+            .map(|player| FairnessViolation::TooManyEdgeCells { player, count: 9 })
+            .collect();
+        assert_eq!(setup.validate_fairness(&strict_policy(8)), Err(violations));
+        setup.set_fairness_policy(Some(strict_policy(8)));
+        assert_eq!(setup.validate(), Err(ValidateError::Unfair(0)));
+    }
+
+    #[test]
+    fn wrapping_board_has_no_edge() {
+        let mut setup = UniformSetup::new();
+        let dim = RectDimensions::new_wrapping(10, 10, Wrapping::Horizontal | Wrapping::Vertical);
+        let board = setup.add_player(0, dim).unwrap();
+        board.add_ship(Ship::Carrier, Line::new(5)).unwrap();
+        let cells = (0..5).map(|x| Coordinate::new(x, 0)).collect();
+        board
+            .get_ship_mut(&Ship::Carrier)
+            .unwrap()
+            .place(cells)
+            .unwrap();
+        assert_eq!(setup.validate_fairness(&strict_policy(0)), Ok(()));
+    }
+
+    #[test]
+    fn unfair_dimensions() {
+        let mut setup = fixtures::uniform_setup();
+        let board = setup.add_player(3, RectDimensions::new(11, 10)).unwrap();
+        for (row, &ship) in Ship::ALL.iter().enumerate() {
+            let cells = (0..ship.len())
+                .map(|x| Coordinate::new(x, row * 2))
+                .collect();
+            board
+                .add_ship(ship, Line::new(ship.len()))
+                .unwrap()
+                .place(cells)
+                .unwrap();
+        }
+        assert_eq!(
+            setup.validate_fairness(&strict_policy(9)),
+            Err(vec![FairnessViolation::DimensionMismatch { player: 3 }])
+        );
+    }
+
+    #[test]
+    fn annotations_are_private() {
+        let mut game = fixtures::uniform_mid_game();
+        let note = Note::Text("Probably a carrier".to_owned());
+        game.annotations_mut(&0, &1)
+            .unwrap()
+            .set(Coordinate::new(3, 3), note.clone());
+        assert_eq!(
+            game.annotations(&0, &1)
+                .unwrap()
+                .get(&Coordinate::new(3, 3)),
+            Some(&note)
+        );
+        // Other players, and player 0's notes on other opponents, don't see it.
+        assert!(game.annotations(&1, &0).unwrap().is_empty());
+        assert!(game.annotations(&2, &1).unwrap().is_empty());
+        assert!(game.annotations(&0, &2).unwrap().is_empty());
+        assert!(game.annotations(&0, &0).is_none());
+        assert!(game.annotations(&0, &7).is_none());
+    }
+
+    #[test]
+    fn reset_clears_annotations() {
+        let mut game = fixtures::uniform_mid_game();
+        game.annotations_mut(&2, &0)
+            .unwrap()
+            .set(Coordinate::new(1, 1), Note::Avoid);
+        game.reset();
+        assert!(game.annotations(&2, &0).unwrap().is_empty());
+        assert_eq!(*game.current(), 0);
+    }
+
+    #[test]
+    fn winner_is_sole_survivor() {
+        let mut game = fixtures::uniform_near_victory();
+        assert_eq!(game.winner(), None);
+        // Sink player 0's fleet behind the game's back, so the current player is out and
+        // player 1 is the only one left.
+        let board = game.get_board_mut(&0).unwrap();
+        for (row, &ship) in Ship::ALL.iter().enumerate() {
+            for x in 0..ship.len() {
+                board.shoot(Coordinate::new(x, row * 2)).unwrap();
+            }
+        }
+        assert_eq!(*game.current(), 0);
+        assert_eq!(game.winner(), Some(&1));
+        assert!(game
+            .winner_board()
+            .unwrap()
+            .get_ship(&Ship::Destroyer)
+            .is_some());
+
+        // With nobody left there is no winner.
+        let board = game.get_board_mut(&1).unwrap();
+        board.shoot(Coordinate::new(1, 8)).unwrap();
+        assert_eq!(game.winner(), None);
+    }
+
+    #[test]
+    fn winner_board_counts_surviving_ships() {
+        let mut game = fixtures::uniform_near_victory();
+        assert!(game.winner_board().is_none());
+        // Sink player 0's Destroyer so the winner has lost a ship.
+        let board = game.get_board_mut(&0).unwrap();
+        board.shoot(Coordinate::new(0, 8)).unwrap();
+        board.shoot(Coordinate::new(1, 8)).unwrap();
+        game.shoot(1, Coordinate::new(1, 8)).unwrap();
+
+        assert_eq!(game.winner(), Some(&0));
+        let board = game.winner_board().unwrap();
+        let alive: Vec<_> = board
+            .iter_ships()
+            .filter(|ship| !ship.sunk())
+            .map(|ship| *ship.id())
+            .collect();
+        assert_eq!(alive.len(), Ship::ALL.len() - 1);
+        assert!(!alive.contains(&Ship::Destroyer));
+    }
+
+    #[test]
+    fn reset_matches_fresh_game() {
+        let mut game = fixtures::uniform_near_victory();
+        game.pause();
+        game.reset();
+        let fresh = fixtures::uniform_setup().start().ok().unwrap();
+        let diff = game.diff(&fresh);
+        assert!(diff.is_empty(), "{}", diff);
+        assert_eq!(game.total_ships_sunk(), 0);
+    }
+
+    #[test]
+    fn reset_masked_boards() {
+        /// Build a two-player setup on 4x4 boards with a land cell at (1,1).
+        fn masked_setup() -> GameSetup<usize, Ship, MaskedDimensions<RectDimensions>, Line> {
+            let mut setup = GameSetup::new();
+            for pid in 0..2 {
+                let dim =
+                    MaskedDimensions::new(RectDimensions::new(4, 4), vec![Coordinate::new(1, 1)]);
+                let board = setup.add_player(pid, dim).unwrap();
+                let mut ship = board.add_ship(Ship::Destroyer, Line::new(2)).unwrap();
+                ship.place(vec![Coordinate::new(0, 3), Coordinate::new(1, 3)])
+                    .unwrap();
+            }
+            setup
+        }
+
+        let mut game = masked_setup().start().ok().unwrap();
+        game.shoot(1, Coordinate::new(0, 3)).unwrap();
+        game.shoot(0, Coordinate::new(2, 2)).unwrap();
+        assert_eq!(
+            game.shoot(1, Coordinate::new(1, 1)).unwrap_err(),
+            ShotError::new(CannotShootReason::OutOfBounds, 1, Coordinate::new(1, 1))
+        );
+        game.reset();
+        let fresh = masked_setup().start().ok().unwrap();
+        let diff = game.diff(&fresh);
+        assert!(diff.is_empty(), "{}", diff);
+        let board = game.get_board(&1).unwrap();
+        assert_eq!(board.shot_mask(), [0]);
+        assert_eq!(board.target_legality().count(), 15);
+    }
+
+    #[test]
+    fn four_players_two_eliminated_back_to_back() {
+        let mut setup = UniformSetup::new();
+        for pid in 0..4 {
+            let board = setup.add_player(pid, RectDimensions::new(4, 4)).unwrap();
+            board
+                .add_ship(Ship::Destroyer, Line::new(2))
+                .unwrap()
+                .place(vec![Coordinate::new(0, 0), Coordinate::new(1, 0)])
+                .unwrap();
+        }
+        let mut game = setup.start().ok().unwrap();
+        let mut fire = |target, x, y| {
+            let shooter = *game.current();
+            let outcome = game.shoot(target, Coordinate::new(x, y)).unwrap();
+            (shooter, outcome, *game.current())
+        };
+        assert!(matches!(fire(1, 0, 0), (0, ShotOutcome::Hit(_), 1)));
+        assert!(matches!(fire(2, 0, 0), (1, ShotOutcome::Hit(_), 2)));
+        assert!(matches!(fire(0, 3, 3), (2, ShotOutcome::Miss, 3)));
+        // Player 2 and then player 1 are eliminated by consecutive shots, so the turn
+        // passes from 0 over both of them to 3.
+        assert!(matches!(fire(2, 1, 0), (3, ShotOutcome::Defeated(_), 0)));
+        assert!(matches!(fire(1, 1, 0), (0, ShotOutcome::Defeated(_), 3)));
+        assert_eq!(game.winner(), None);
+
+        // Defeated players can still be inspected, but not shot, and trying doesn't use
+        // up the turn.
+        assert_eq!(game.iter_boards().count(), 4);
+        for &pid in &[1, 2] {
+            let board = game.get_board(&pid).unwrap();
+            assert!(board.defeated());
+            assert_eq!(board.target_legality().count(), 16);
+            assert_eq!(game.would_be_legal_targets(&3, &pid).count(), 0);
+            assert_eq!(
+                game.shoot(pid, Coordinate::new(3, 3)).unwrap_err(),
+                ShotError::new(
+                    CannotShootReason::AlreadyDefeated,
+                    pid,
+                    Coordinate::new(3, 3)
+                )
+            );
+        }
+        assert_eq!(*game.current(), 3);
+
+        let mut fire = |target, x, y| {
+            let shooter = *game.current();
+            let outcome = game.shoot(target, Coordinate::new(x, y)).unwrap();
+            (shooter, outcome, *game.current())
+        };
+        assert!(matches!(fire(0, 2, 3), (3, ShotOutcome::Miss, 0)));
+        assert!(matches!(fire(3, 0, 0), (0, ShotOutcome::Hit(_), 3)));
+        assert!(matches!(fire(0, 3, 2), (3, ShotOutcome::Miss, 0)));
+        assert!(matches!(fire(3, 1, 0), (0, ShotOutcome::Victory(_), 0)));
+        assert_eq!(game.winner(), Some(&0));
+        assert_eq!(game.total_ships_sunk(), 3);
+    }
+
+    #[test]
+    fn confirmed_board_is_locked() {
+        let mut setup = fixtures::uniform_setup();
+        setup.confirm_placements(0).unwrap();
+        assert!(setup.is_confirmed(&0));
+        let board = setup.get_board_mut(&0).unwrap();
+        let mut carrier = board.get_ship_mut(&Ship::Carrier).unwrap();
+        let placement = carrier.placement().unwrap().clone();
+        assert_eq!(carrier.unplace(), Err(CannotPlaceReason::Locked));
+        assert_eq!(
+            carrier.place(placement.clone()).unwrap_err().reason(),
+            CannotPlaceReason::Locked
+        );
+        assert_eq!(
+            board
+                .add_ship(Ship::Carrier, Line::new(5))
+                .err()
+                .unwrap()
+                .reason(),
+            CannotAddShipReason::Locked
+        );
+        assert_eq!(
+            board.remove_ship(&Ship::Carrier).err(),
+            Some(CannotRemoveShipReason::Locked)
+        );
+        assert_eq!(
+            setup.mirror_placements(&1, &[0]),
+            Err(MirrorError::Locked(0))
+        );
+        assert_eq!(
+            setup.unconfirm_placements(0),
+            Err(ConfirmError::UnconfirmNotAllowed(0))
+        );
+
+        setup.set_allow_unconfirm(true);
+        setup.unconfirm_placements(0).unwrap();
+        let board = setup.get_board_mut(&0).unwrap();
+        let mut carrier = board.get_ship_mut(&Ship::Carrier).unwrap();
+        assert_eq!(carrier.unplace(), Ok(Some(placement)));
+        assert_eq!(carrier.unplace(), Ok(None));
+        assert_eq!(setup.confirm_placements(0), Err(ConfirmError::NotReady(0)));
+    }
+
+    #[test]
+    fn ready_requires_confirmation() {
+        let mut setup = fixtures::uniform_setup();
+        assert!(setup.ready());
+        setup.set_require_confirmation(true);
+        assert_eq!(setup.validate(), Err(ValidateError::Unconfirmed(0)));
+        setup.confirm_placements(0).unwrap();
+        setup.confirm_placements(2).unwrap();
+        assert_eq!(setup.validate(), Err(ValidateError::Unconfirmed(1)));
+        let mut setup = setup.start().err().unwrap();
+        setup.confirm_placements(1).unwrap();
+        assert!(setup.ready());
+        assert!(setup.start().is_ok());
+    }
+
+    /// Build a two-player setup on 5x5 boards, each with a destroyer in the top left
+    /// corner and a cruiser running down to the bottom edge at column 2, revealing water
+    /// around sunk ships if asked and ships aren't allowed to touch.
+    fn reveal_setup(no_adjacent_ships: bool) -> UniformSetup {
+        let mut setup = UniformSetup::new();
+        for pid in 0..2 {
+            let board = setup.add_player(pid, RectDimensions::new(5, 5)).unwrap();
+            let ships = [
+                (Ship::Destroyer, vec![(0, 0), (1, 0)]),
+                (Ship::Cruiser, vec![(2, 2), (2, 3), (2, 4)]),
+            ];
+            for (ship, cells) in ships.iter() {
+                let cells = cells.iter().map(|&(x, y)| Coordinate::new(x, y)).collect();
+                board
+                    .add_ship(*ship, Line::new(ship.len()))
+                    .unwrap()
+                    .place(cells)
+                    .unwrap();
+            }
+        }
+        setup.set_fairness_policy(Some(FairnessPolicy {
+            no_adjacent_ships,
+            ..FairnessPolicy::default()
+        }));
+        setup.set_auto_reveal_adjacent_on_sink(true);
+        setup
+    }
+
+    /// Sort coordinates by column, then row.
+    fn sorted(mut coords: Vec<Coordinate>) -> Vec<Coordinate> {
+        coords.sort_by_key(|c| (c.x, c.y));
+        coords
+    }
+
+    #[test]
+    fn reveal_adjacent_on_sink() {
+        let mut game = reveal_setup(true).start().ok().unwrap();
+        assert!(game.auto_reveal_adjacent_on_sink());
+        let miss = |x| Coordinate::new(x, 1);
+        game.shoot(1, Coordinate::new(1, 1)).unwrap();
+        game.shoot(0, miss(3)).unwrap();
+        game.shoot(1, Coordinate::new(0, 0)).unwrap();
+        game.shoot(0, miss(4)).unwrap();
+
+        // The destroyer is in the corner, and (1,1) was already shot, so only two cells
+        // are left to reveal.
+        let (outcome, events) = game.shoot_with_events(1, Coordinate::new(1, 0)).unwrap();
+        assert!(matches!(outcome, ShotOutcome::Sunk(Ship::Destroyer)));
+        assert_eq!(
+            sorted(events.revealed),
+            [Coordinate::new(0, 1), Coordinate::new(2, 0)]
+        );
+        // Revealing cells doesn't use up a shot.
+        assert_eq!(*game.current(), 1);
+        game.shoot(0, Coordinate::new(4, 4)).unwrap();
+        for &(x, y) in &[(0, 1), (2, 0)] {
+            let err = game.shoot(1, Coordinate::new(x, y)).unwrap_err();
+            assert!(err.reason().is_already_shot());
+        }
+
+        // The cruiser runs into the bottom edge.
+        for &(x, y) in &[(2, 2), (2, 3)] {
+            game.shoot(1, Coordinate::new(x, y)).unwrap();
+            game.shoot(0, Coordinate::new(y, 4)).unwrap();
+        }
+        let (outcome, events) = game.shoot_with_events(1, Coordinate::new(2, 4)).unwrap();
+        assert!(matches!(outcome, ShotOutcome::Victory(Ship::Cruiser)));
+        let expected: Vec<_> = [(1, 2), (1, 3), (1, 4), (2, 1), (3, 2), (3, 3), (3, 4)]
+            .iter()
+            .map(|&(x, y)| Coordinate::new(x, y))
+            .collect();
+        assert_eq!(sorted(events.revealed), expected);
+        let board = game.get_board(&1).unwrap();
+        for coord in &expected {
+            assert!(board.get_coord(*coord).unwrap().hit());
+        }
+    }
+
+    #[test]
+    fn reveal_needs_no_adjacent_ships() {
+        let setup = reveal_setup(false);
+        assert!(setup.auto_reveal_adjacent_on_sink());
+        let mut game = setup.start().ok().unwrap();
+        assert!(!game.auto_reveal_adjacent_on_sink());
+        game.shoot(1, Coordinate::new(0, 0)).unwrap();
+        game.shoot(0, Coordinate::new(4, 4)).unwrap();
+        let (outcome, events) = game.shoot_with_events(1, Coordinate::new(1, 0)).unwrap();
+        assert!(matches!(outcome, ShotOutcome::Sunk(Ship::Destroyer)));
+        assert!(events.revealed.is_empty());
+        assert!(!game
+            .get_board(&1)
+            .unwrap()
+            .get_coord(Coordinate::new(0, 1))
+            .unwrap()
+            .hit());
+    }
+
+    #[test]
+    fn turn_tokens_follow_turns() {
+        let mut setup = fixtures::uniform_setup();
+        setup.set_extra_shots(0, 1).unwrap();
+        let mut game = setup.start().ok().unwrap();
+
+        let token = game.begin_turn();
+        assert_eq!(token.player(), &0);
+        assert_eq!(token.game().shots_remaining(), 2);
+        let (outcome, next) = token.shoot(1, Coordinate::new(0, 0)).unwrap();
+        assert!(outcome.is_hit());
+        // Player 0 has an extra shot, so the next token is theirs again.
+        assert_eq!(next, 0);
+
+        let token = game.begin_turn();
+        assert_eq!(token.player(), &next);
+        let (outcome, next) = token.shoot(2, Coordinate::new(9, 9)).unwrap();
+        assert!(outcome.is_miss());
+        assert_eq!(next, 1);
+
+        let token = game.begin_turn();
+        assert_eq!(token.player(), &1);
+        let (_, next) = token.shoot(0, Coordinate::new(0, 0)).unwrap();
+        assert_eq!(next, 2);
+        assert_eq!(game.current(), &2);
+    }
+
+    #[test]
+    fn failed_token_shot_keeps_turn() {
+        let mut game = fixtures::uniform_mid_game();
+        let token = game.begin_turn();
+        let err = token.shoot(0, Coordinate::new(5, 5)).unwrap_err();
+        assert!(err.reason().is_self_shot());
+        let token = game.begin_turn();
+        assert_eq!(token.player(), &0);
+        let err = token.shoot(1, Coordinate::new(0, 0)).unwrap_err();
+        assert!(err.reason().is_already_shot());
+        assert_eq!(game.current(), &0);
+    }
+
+    #[test]
+    fn repeat_shot_rejected() {
+        let mut game = fixtures::uniform_mid_game();
+        assert_eq!(game.repeat_shot_policy(), RepeatShotPolicy::Reject);
+        let mask = game.get_board(&1).unwrap().shot_mask();
+        let err = game.shoot(1, Coordinate::new(0, 0)).unwrap_err();
+        assert!(err.reason().is_already_shot());
+        // The player keeps their turn and must pick another cell.
+        assert_eq!(game.current(), &0);
+        assert_eq!(game.get_board(&1).unwrap().shot_mask(), mask);
+        assert!(game.shoot(1, Coordinate::new(1, 0)).unwrap().is_hit());
+        assert_eq!(game.current(), &1);
+    }
+
+    #[test]
+    fn repeat_shot_wasted() {
+        let mut setup = fixtures::uniform_setup();
+        setup.set_repeat_shot_policy(RepeatShotPolicy::Waste);
+        setup.set_extra_shots(0, 1).unwrap();
+        let mut game = setup.start().ok().unwrap();
+        assert_eq!(game.repeat_shot_policy(), RepeatShotPolicy::Waste);
+
+        assert!(game.shoot(1, Coordinate::new(0, 0)).unwrap().is_hit());
+        assert_eq!(game.current(), &0);
+        let mask = game.get_board(&1).unwrap().shot_mask();
+        // Player 0's final shot of the turn is wasted, which still ends the turn.
+        let (outcome, events) = game.shoot_with_events(1, Coordinate::new(0, 0)).unwrap();
+        assert!(outcome.is_wasted());
+        assert_eq!(events.sunk, None);
+        assert!(!events.defeated);
+        assert_eq!(game.get_board(&1).unwrap().shot_mask(), mask);
+        assert_eq!(game.current(), &1);
+        assert_eq!(game.shots_remaining(), 1);
+
+        // A wasted shot also ends a single-shot turn.
+        assert!(game.shoot(0, Coordinate::new(9, 9)).unwrap().is_miss());
+        assert_eq!(game.current(), &2);
+        assert!(game.shoot(0, Coordinate::new(9, 9)).unwrap().is_wasted());
+        assert_eq!(game.current(), &0);
+        assert_eq!(game.shots_remaining(), 2);
+    }
+
+    #[test]
+    fn events_for_winning_shot() {
+        let mut game = fixtures::uniform_near_victory();
+        let (outcome, events) = game.shoot_with_events(1, Coordinate::new(1, 8)).unwrap();
+        assert!(matches!(outcome, ShotOutcome::Victory(Ship::Destroyer)));
+        assert!(events.defeated);
+        assert!(events.revealed.is_empty());
+        let sunk = events.sunk.unwrap();
+        assert_eq!(sunk.id, Ship::Destroyer);
+        assert_eq!(sunk.coords, [Coordinate::new(0, 8), Coordinate::new(1, 8)]);
+        assert!(sunk.sunk);
+        assert_eq!(game.winner(), Some(&0));
+    }
+
+    /// Subscriber which records the message of every event, for checking the events
+    /// emitted by the `tracing` feature.
+    #[cfg(feature = "tracing")]
+    #[derive(Default)]
+    struct Capture(std::sync::Arc<std::sync::Mutex<Vec<String>>>);
+
+    #[cfg(feature = "tracing")]
+    impl tracing::Subscriber for Capture {
+        fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+            tracing::span::Id::from_u64(1)
+        }
+
+        fn record(&self, _: &tracing::span::Id, _: &tracing::span::Record<'_>) {}
+
+        fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+
+        fn event(&self, event: &tracing::Event<'_>) {
+            /// Extracts the message field of an event.
+            struct Message(String);
+
+            impl tracing::field::Visit for Message {
+                fn record_debug(
+                    &mut self,
+                    field: &tracing::field::Field,
+                    value: &dyn std::fmt::Debug,
+                ) {
+                    if field.name() == "message" {
+                        self.0 = format!("{:?}", value);
+                    }
+                }
+            }
+
+            let mut message = Message(String::new());
+            event.record(&mut message);
+            self.0.lock().unwrap().push(message.0);
+        }
+
+        fn enter(&self, _: &tracing::span::Id) {}
+
+        fn exit(&self, _: &tracing::span::Id) {}
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn one_trace_event_per_shot() {
+        let capture = Capture::default();
+        let events = capture.0.clone();
+        let take = || std::mem::take(&mut *events.lock().unwrap());
+        tracing::subscriber::with_default(capture, || {
+            let mut setup = fixtures::uniform_setup();
+            assert_eq!(take().len(), 15);
+            let board = setup.get_board_mut(&0).unwrap();
+            let mut carrier = board.get_ship_mut(&Ship::Carrier).unwrap();
+            let cells = carrier.unplace().unwrap().unwrap();
+            carrier.place(cells).unwrap();
+            assert_eq!(take(), ["unplaced ship", "placed ship"]);
+
+            let mut game = setup.start().ok().unwrap();
+            game.shoot(1, Coordinate::new(0, 0)).unwrap();
+            assert_eq!(take(), ["board shot", "game shot"]);
+            game.shoot(1, Coordinate::new(0, 0)).unwrap_err();
+            assert_eq!(take(), ["game shot rejected"]);
+            game.get_board_mut(&2)
+                .unwrap()
+                .shoot(Coordinate::new(9, 9))
+                .unwrap();
+            assert_eq!(take(), ["board shot"]);
+        });
+    }
+
+    #[test]
+    fn handicap_two_shots_on_large_board() {
+        let mut setup = GameSetup::new();
+        for &(pid, size) in &[(0, 12), (1, 8)] {
+            let board = setup
+                .add_player(pid, RectDimensions::new(size, size))
+                .unwrap();
+            board.add_ship(Ship::Destroyer, Line::new(2)).unwrap();
+            let cells = vec![Coordinate::new(0, 0), Coordinate::new(1, 0)];
+            board
+                .get_ship_mut(&Ship::Destroyer)
+                .unwrap()
+                .place(cells)
+                .unwrap();
+            board.add_ship(Ship::Submarine, Line::new(3)).unwrap();
+            let cells = (0..3).map(|x| Coordinate::new(x, 2)).collect();
+            board
+                .get_ship_mut(&Ship::Submarine)
+                .unwrap()
+                .place(cells)
+                .unwrap();
+        }
+        // Player 0 plays on the larger board, but gets two shots a turn.
+        setup.set_extra_shots(0, 1).unwrap();
+        assert_eq!(setup.extra_shots(&0), 1);
+        assert_eq!(setup.extra_shots(&1), 0);
+        assert_eq!(
+            setup.set_extra_shots(2, 1),
+            Err(HandicapError::UnknownPlayer(2))
+        );
+        let mut game: Game<usize, Ship, RectDimensions> = setup.start().ok().unwrap();
+        assert_eq!(game.extra_shots(&0), 1);
+
+        // Player 1's board is only 8x8.
+        let err = game.shoot(1, Coordinate::new(10, 10)).unwrap_err();
+        assert!(err.reason().is_out_of_bounds());
+        assert_eq!(game.shots_remaining(), 2);
+
+        let mut shots = 0;
+        let mut turns = vec![];
+        let kid_misses = (0..12).map(|x| Coordinate::new(x, 11));
+        let mut kid_misses = kid_misses.chain((0..12).map(|x| Coordinate::new(x, 10)));
+        let targets = [(0, 0), (5, 5), (1, 0), (0, 2), (1, 2), (6, 6), (2, 2)];
+        let mut targets = targets.iter().map(|&(x, y)| Coordinate::new(x, y));
+        while game.winner().is_none() {
+            let player = *game.current();
+            turns.push((player, game.shots_remaining()));
+            if player == 0 {
+                game.shoot(1, targets.next().unwrap()).unwrap();
+                shots += 1;
+            } else {
+                game.shoot(0, kid_misses.next().unwrap()).unwrap();
+            }
+        }
+        assert_eq!(game.winner(), Some(&0));
+        assert_eq!(shots, 7);
+        assert_eq!(
+            turns,
+            [
+                (0, 2),
+                (0, 1),
+                (1, 1),
+                (0, 2),
+                (0, 1),
+                (1, 1),
+                (0, 2),
+                (0, 1),
+                (1, 1),
+                (0, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn duplicate_player_error_compares_whole() {
+        let mut setup = fixtures::uniform_setup();
+        let err = setup
+            .add_player(1, RectDimensions::new(8, 8))
+            .err()
+            .unwrap();
+        assert_eq!(err, AddPlayerError::new(1, RectDimensions::new(8, 8)));
+        assert_ne!(err, AddPlayerError::new(1, RectDimensions::new(10, 10)));
+        assert_eq!(
+            format!("{:?}", err),
+            format!(
+                "AddPlayerError {{ id: 1, dim: {:?} }}",
+                RectDimensions::new(8, 8)
+            )
+        );
+    }
+
+    /// Play the given shots on the three-player fixture, skipping any that are rejected.
+    fn play_shots(shots: &[(usize, usize, usize)]) -> UniformGame {
+        let mut game = fixtures::uniform_setup().start().ok().unwrap();
+        for &(target, x, y) in shots {
+            let _ = game.shoot(target, Coordinate::new(x, y));
+        }
+        game
+    }
+
+    proptest::proptest! {
+        #![proptest_config(proptest::test_runner::Config::with_cases(16))]
+
+        #[test]
+        fn legal_targets_match_brute_force(
+            shots in proptest::collection::vec((0..3usize, 0..10usize, 0..10usize), 0..60),
+        ) {
+            let game = play_shots(&shots);
+            for shooter in 0..3 {
+                for target in 0..3 {
+                    let legal: Vec<_> = game.legal_targets(&shooter, &target).collect();
+                    let would_be: Vec<_> = game.would_be_legal_targets(&shooter, &target).collect();
+                    let mut expected = Vec::new();
+                    for y in 0..10 {
+                        for x in 0..10 {
+                            // Brute force: replay the position, hand the shooter the turn,
+                            // and see whether the shot is accepted.
+                            let coord = Coordinate::new(x, y);
+                            let mut copy = play_shots(&shots);
+                            if copy.set_current(&shooter).is_ok()
+                                && copy.shoot(target, coord).is_ok()
+                            {
+                                expected.push(coord);
+                            }
+                        }
+                    }
+                    proptest::prop_assert_eq!(&would_be, &expected);
+                    if *game.current() == shooter {
+                        proptest::prop_assert_eq!(&legal, &expected);
+                    } else {
+                        proptest::prop_assert!(legal.is_empty());
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn setup_boards_iterate_in_turn_order() {
+        let mut setup = fixtures::uniform_setup();
+        let ids = |setup: &UniformSetup| -> Vec<usize> {
+            setup.iter_boards().map(|(pid, _)| *pid).collect()
+        };
+        assert_eq!(ids(&setup), [0, 1, 2]);
+
+        setup.set_turn_order(&[2, 0, 1]).unwrap();
+        assert_eq!(ids(&setup), [2, 0, 1]);
+        let mut visited = Vec::new();
+        for (pid, board) in setup.iter_boards_mut() {
+            visited.push(*pid);
+            board
+                .get_ship_mut(&Ship::Destroyer)
+                .unwrap()
+                .unplace()
+                .unwrap();
+        }
+        assert_eq!(visited, [2, 0, 1]);
+        for (_, board) in setup.iter_boards() {
+            assert!(board
+                .get_ship(&Ship::Destroyer)
+                .unwrap()
+                .placement()
+                .is_none());
+        }
+    }
+
+    #[test]
+    fn game_sent_to_thread() {
+        let mut setup = GameSetup::<u8, u8, RectDimensions, Line>::new();
+        for pid in 0..2 {
+            setup
+                .add_player(pid, RectDimensions::new(3, 3))
+                .unwrap()
+                .add_ship(7, Line::new(2))
+                .unwrap()
+                .place(vec![Coordinate::new(0, 0), Coordinate::new(1, 0)])
+                .unwrap();
+        }
+        let game: Game<u8, u8, RectDimensions> = setup.start().ok().unwrap();
+        let game = std::thread::spawn(move || {
+            let mut game = game;
+            game.shoot(1, Coordinate::new(0, 0)).unwrap();
+            game.shoot(0, Coordinate::new(2, 2)).unwrap();
+            game.shoot(1, Coordinate::new(1, 0)).unwrap();
+            game
+        })
+        .join()
+        .unwrap();
+        assert_eq!(game.winner(), Some(&0));
+        assert!(game.get_board(&1).unwrap().get_ship(&7).unwrap().sunk());
+    }
+
+    #[test]
+    fn idempotent_player_re_adds() {
+        let mut setup = fixtures::uniform_setup();
+        let dim = RectDimensions::new(10, 10);
+
+        // Identical: the existing board is returned with its ships.
+        let board = setup.add_player_idempotent(1, dim).unwrap();
+        assert_eq!(board.iter_ships().count(), Ship::ALL.len());
+
+        // Conflicting: rejected with the new dimensions.
+        let other = RectDimensions::new(8, 8);
+        assert_eq!(
+            setup.add_player_idempotent(1, other).err(),
+            Some(AddPlayerError::new(1, other))
+        );
+        assert_eq!(setup.get_board(&1).unwrap().dimensions(), &dim);
+
+        // Strict: the plain method still rejects even an identical re-add.
+        assert_eq!(
+            setup.add_player(1, dim).err(),
+            Some(AddPlayerError::new(1, dim))
+        );
+
+        // A new player joins once, at the end of the turn order.
+        setup.add_player_idempotent(3, other).unwrap();
+        setup.add_player_idempotent(3, other).unwrap();
+        let order: Vec<_> = setup.iter_boards().map(|(pid, _)| *pid).collect();
+        assert_eq!(order, [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn totals_count_ships_across_players() {
+        let mut game = fixtures::uniform_mid_game();
+        assert_eq!(game.total_ships(), 3 * Ship::ALL.len());
+        assert_eq!(game.total_ships_sunk(), 0);
+        let mut sunk = Vec::new();
+        for &(target, x, y) in &[
+            (2, 1, 8),
+            (0, 9, 7),
+            (1, 0, 8),
+            (1, 1, 8),
+            (2, 0, 4),
+            (0, 9, 5),
+            (2, 1, 4),
+            (2, 2, 4),
+        ] {
+            game.shoot(target, Coordinate::new(x, y)).unwrap();
+            sunk.push(game.total_ships_sunk());
+        }
+        // Player 2's Destroyer, then player 1's, then player 2's Cruiser.
+        assert_eq!(sunk, [1, 1, 1, 2, 2, 2, 2, 3]);
+        assert_eq!(game.total_ships(), 3 * Ship::ALL.len());
+    }
+
+    #[test]
+    fn occupied_cells_add_up_to_tonnage() {
+        let game = fixtures::uniform_mid_game();
+        let tonnage: usize = Ship::ALL.iter().map(|ship| ship.len()).sum();
+        let occupied: Vec<_> = game.iter_all_occupied().collect();
+        assert_eq!(occupied.len(), 3 * tonnage);
+
+        let mut per_player = [0; 3];
+        for (pid, ship, coord) in occupied {
+            per_player[*pid] += 1;
+            let cell = game.get_board(pid).unwrap().get_coord(*coord).unwrap();
+            assert_eq!(cell.ship().map(|ship| *ship.id()), Some(*ship));
+        }
+        assert_eq!(per_player, [tonnage; 3]);
+    }
 }
@@ -0,0 +1,251 @@
+// Copyright 2020 Zachary Stewart
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Comparison of two games, for finding where copies of a game disagree.
+
+use std::fmt::{self, Debug};
+
+use crate::{
+    board::{BoardDiff, Dimensions},
+    game::uniform::{Game, GameState, PlayerId},
+    ships::ShipId,
+};
+
+/// Differences between two games, from [`Game::diff`]. Each pair of values is this
+/// game's value followed by the other game's. Displays as a compact report with one
+/// difference per line.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct GameDiff<P, I, C> {
+    /// The turn orders, if they differ.
+    pub turn_order: Option<(Vec<P>, Vec<P>)>,
+    /// The players whose turn it is, if they differ.
+    pub current: Option<(P, P)>,
+    /// The shots remaining this turn, if they differ.
+    pub shots_remaining: Option<(usize, usize)>,
+    /// The states of play, if they differ.
+    pub state: Option<(GameState, GameState)>,
+    /// Players in only one of the games, along with whether they are in this game.
+    pub players: Vec<(P, bool)>,
+    /// Differences between the boards of players in both games, in this game's turn
+    /// order. Players whose boards match are left out.
+    pub boards: Vec<(P, BoardDiff<I, C>)>,
+}
+
+impl<P, I, C> GameDiff<P, I, C> {
+    /// Returns true if no differences were found.
+    pub fn is_empty(&self) -> bool {
+        self.turn_order.is_none()
+            && self.current.is_none()
+            && self.shots_remaining.is_none()
+            && self.state.is_none()
+            && self.players.is_empty()
+            && self.boards.is_empty()
+    }
+}
+
+impl<P: Debug, I: Debug, C: Debug> fmt::Display for GameDiff<P, I, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "games match");
+        }
+        let mut lines = Vec::new();
+        if let Some((here, there)) = &self.turn_order {
+            lines.push(format!("turn order: {:?} here, {:?} there", here, there));
+        }
+        if let Some((here, there)) = &self.current {
+            lines.push(format!("current: {:?} here, {:?} there", here, there));
+        }
+        if let Some((here, there)) = self.shots_remaining {
+            lines.push(format!("shots remaining: {} here, {} there", here, there));
+        }
+        if let Some((here, there)) = self.state {
+            lines.push(format!("state: {:?} here, {:?} there", here, there));
+        }
+        for (pid, here) in &self.players {
+            let (here, there) = if *here {
+                ("present", "missing")
+            } else {
+                ("missing", "present")
+            };
+            lines.push(format!("player {:?}: {} here, {} there", pid, here, there));
+        }
+        for (pid, diff) in &self.boards {
+            lines.push(format!("player {:?} board:", pid));
+            for line in diff.to_string().lines() {
+                lines.push(format!("  {}", line));
+            }
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// Get the pair of values if they differ.
+fn changed<T: PartialEq>(here: T, there: T) -> Option<(T, T)> {
+    if here != there {
+        Some((here, there))
+    } else {
+        None
+    }
+}
+
+impl<P: PlayerId, I: ShipId, D: Dimensions> Game<P, I, D> {
+    /// Compare this game with another copy which should be in sync with it, such as a
+    /// replay or a peer's copy of a networked game.
+    pub fn diff(&self, other: &Game<P, I, D>) -> GameDiff<P, I, D::Coordinate> {
+        let mut players: Vec<_> = self
+            .turn_order
+            .iter()
+            .filter(|pid| !other.boards.contains_key(*pid))
+            .map(|pid| (pid.clone(), true))
+            .collect();
+        players.extend(
+            other
+                .turn_order
+                .iter()
+                .filter(|pid| !self.boards.contains_key(*pid))
+                .map(|pid| (pid.clone(), false)),
+        );
+        let boards = self
+            .turn_order
+            .iter()
+            .filter_map(|pid| {
+                let diff = self.boards[pid].diff(other.boards.get(pid)?);
+                if diff.is_empty() {
+                    None
+                } else {
+                    Some((pid.clone(), diff))
+                }
+            })
+            .collect();
+        GameDiff {
+            turn_order: changed(&self.turn_order, &other.turn_order)
+                .map(|(here, there)| (here.clone(), there.clone())),
+            current: changed(self.current(), other.current())
+                .map(|(here, there)| (here.clone(), there.clone())),
+            shots_remaining: changed(self.shots_remaining(), other.shots_remaining()),
+            state: changed(self.state(), other.state()),
+            players,
+            boards,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        board::rectangular::{Coordinate, RectDimensions},
+        fixtures::{self, UniformGame, UniformSetup},
+        game::simple::Ship,
+        ships::Line,
+    };
+
+    /// Set up a game between the given players, each with a Destroyer in the top left
+    /// corner of a 4x4 board.
+    fn duel_setup(players: &[usize]) -> UniformSetup {
+        let mut setup = UniformSetup::new();
+        for &pid in players {
+            let board = setup.add_player(pid, RectDimensions::new(4, 4)).unwrap();
+            board.add_ship(Ship::Destroyer, Line::new(2)).unwrap();
+            let cells = vec![Coordinate::new(0, 0), Coordinate::new(1, 0)];
+            board
+                .get_ship_mut(&Ship::Destroyer)
+                .unwrap()
+                .place(cells)
+                .unwrap();
+        }
+        setup
+    }
+
+    /// Start a game from [`duel_setup`].
+    fn duel(players: &[usize]) -> UniformGame {
+        duel_setup(players).start().ok().unwrap()
+    }
+
+    #[test]
+    fn matching_games() {
+        let diff = fixtures::uniform_mid_game().diff(&fixtures::uniform_mid_game());
+        assert!(diff.is_empty());
+        assert_eq!(diff.to_string(), "games match");
+    }
+
+    #[test]
+    fn turn_differences() {
+        let mut here = duel(&[0, 1]);
+        let there = duel(&[0, 1]);
+        here.shoot(1, Coordinate::new(3, 3)).unwrap();
+        let diff = here.diff(&there);
+        assert_eq!(diff.current, Some((1, 0)));
+        assert_eq!(diff.turn_order, None);
+        assert_eq!(diff.shots_remaining, None);
+        assert_eq!(diff.state, None);
+        assert!(diff.players.is_empty());
+        assert_eq!(diff.boards.len(), 1);
+        assert_eq!(diff.boards[0].0, 1);
+        assert_eq!(
+            diff.boards[0].1,
+            here.get_board(&1)
+                .unwrap()
+                .diff(there.get_board(&1).unwrap())
+        );
+    }
+
+    #[test]
+    fn rule_and_state_differences() {
+        let mut setup = duel_setup(&[0, 1]);
+        setup.set_extra_shots(0, 2).unwrap();
+        setup.set_turn_order(&[1, 0]).unwrap();
+        let mut here = setup.start().ok().unwrap();
+        here.set_current(&0).unwrap();
+        here.pause();
+        let diff = here.diff(&duel(&[0, 1]));
+        assert_eq!(diff.turn_order, Some((vec![1, 0], vec![0, 1])));
+        assert_eq!(diff.current, None);
+        assert_eq!(diff.shots_remaining, Some((3, 1)));
+        assert_eq!(diff.state, Some((GameState::Paused, GameState::Playing)));
+        assert!(diff.boards.is_empty());
+    }
+
+    #[test]
+    fn player_differences() {
+        let diff = duel(&[0, 1, 2]).diff(&duel(&[0, 1, 3]));
+        assert_eq!(diff.players, [(2, true), (3, false)]);
+        assert_eq!(diff.turn_order, Some((vec![0, 1, 2], vec![0, 1, 3])));
+        // Only boards of players in both games are compared.
+        assert!(diff.boards.is_empty());
+    }
+
+    #[test]
+    fn report_snapshot() {
+        let mut setup = duel_setup(&[0, 1, 2]);
+        setup.set_extra_shots(0, 2).unwrap();
+        let mut here = setup.start().ok().unwrap();
+        here.shoot(1, Coordinate::new(0, 0)).unwrap();
+        here.pause();
+        let mut there = duel(&[0, 1, 3]);
+        there.shoot(1, Coordinate::new(3, 3)).unwrap();
+        assert_eq!(
+            here.diff(&there).to_string(),
+            "turn order: [0, 1, 2] here, [0, 1, 3] there
+current: 0 here, 1 there
+shots remaining: 2 here, 1 there
+state: Paused here, Playing there
+player 2: present here, missing there
+player 3: missing here, present there
+player 1 board:
+  cell Coordinate2D { x: 0, y: 0 }: shot here, unshot there
+  cell Coordinate2D { x: 3, y: 3 }: unshot here, shot there"
+        );
+    }
+}
@@ -12,16 +12,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::fmt::{self, Debug};
+use std::fmt::Debug;
 
 use thiserror::Error;
 
 use crate::board::{CannotShootReason as BoardCannotShootReason, ShotError as BoardShotError};
 
 /// Error returned when trying to add a ship that already existed.
-#[derive(Error)]
+#[derive(Debug, Error, Clone, Eq, PartialEq)]
 #[error("player with id {id:?} already exists")]
-pub struct AddPlayerError<P: Debug, D> {
+pub struct AddPlayerError<P: Debug, D: Debug> {
     /// ID of the player that was attempted to be added.
     id: P,
     /// The dimensions of the player grid that was not added because the player ID was
@@ -29,7 +29,7 @@ pub struct AddPlayerError<P: Debug, D> {
     dim: D,
 }
 
-impl<P: Debug, D> AddPlayerError<P, D> {
+impl<P: Debug, D: Debug> AddPlayerError<P, D> {
     /// Create an [`AddPlayerError`] for the player with the given ID and dimensions.
     pub(super) fn new(id: P, dim: D) -> Self {
         Self { id, dim }
@@ -49,23 +49,183 @@ impl<P: Debug, D> AddPlayerError<P, D> {
     pub fn into_inner(self) -> (P, D) {
         (self.id, self.dim)
     }
+
+    /// Get the dimensions paired with a new ID, to try adding the player again.
+    pub fn retry_with_id(self, new_id: P) -> (P, D) {
+        (new_id, self.dim)
+    }
 }
 
-impl<P: Debug, D> From<AddPlayerError<P, D>> for (P, D) {
+impl<P: Debug, D: Debug> From<AddPlayerError<P, D>> for (P, D) {
     /// Allows retrieving the inner id and shape from the error with into.
     fn from(err: AddPlayerError<P, D>) -> Self {
         err.into_inner()
     }
 }
 
-impl<I: Debug, S> Debug for AddPlayerError<I, S> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        fmt::Display::fmt(self, f)
+/// Error returned when a requested turn order is not a permutation of the players in the
+/// game.
+#[derive(Debug, Error, Clone, Eq, PartialEq)]
+pub enum TurnOrderError<P: Debug> {
+    /// The turn order included a player that was never added to the game.
+    #[error("player {0:?} is not part of the game")]
+    UnknownPlayer(P),
+    /// The turn order included the same player more than once.
+    #[error("player {0:?} appears in the turn order more than once")]
+    DuplicatePlayer(P),
+    /// The turn order did not include one of the players in the game.
+    #[error("player {0:?} is missing from the turn order")]
+    MissingPlayer(P),
+}
+
+/// Error returned when confirming or unconfirming a player's placements fails.
+#[derive(Debug, Error, Clone, Eq, PartialEq)]
+pub enum ConfirmError<P: Debug> {
+    /// The player is not part of the game.
+    #[error("player {0:?} is not part of the game")]
+    UnknownPlayer(P),
+    /// The player has not placed all of their ships.
+    #[error("player {0:?} has not placed all of their ships")]
+    NotReady(P),
+    /// Players are not allowed to unconfirm placements in this game.
+    #[error("player {0:?} is not allowed to unconfirm their placements")]
+    UnconfirmNotAllowed(P),
+}
+
+/// Error returned when mirroring one player's placements onto other players fails. No
+/// boards are changed if mirroring fails.
+#[derive(Debug, Error, Clone, Eq, PartialEq)]
+pub enum MirrorError<P: Debug> {
+    /// The player is not part of the game.
+    #[error("player {0:?} is not part of the game")]
+    UnknownPlayer(P),
+    /// The target player's board has different dimensions than the source board.
+    #[error("player {0:?} has different board dimensions than the source player")]
+    DimensionMismatch(P),
+    /// The target player's board has a different set of ships than the source board, or a
+    /// ship with the same ID but a different shape.
+    #[error("player {0:?} has different ships than the source player")]
+    RosterMismatch(P),
+    /// The target player's board is locked.
+    #[error("player {0:?} has already confirmed their placements")]
+    Locked(P),
+    /// One of the source placements is not valid for the target player's ship, because
+    /// the ship has a different shape.
+    #[error("player {0:?} has a ship which cannot take the source placement")]
+    InvalidPlacement(P),
+}
+
+/// Error returned when proposing or accepting fleets fails.
+#[derive(Debug, Error, Clone, Eq, PartialEq)]
+pub enum NegotiationError<P: Debug> {
+    /// The player is not part of the game.
+    #[error("player {0:?} is not part of the game")]
+    UnknownPlayer(P),
+    /// The players already agreed on a fleet, which can't be renegotiated.
+    #[error("fleets have already been agreed")]
+    AlreadyAgreed,
+    /// The player tried to accept a fleet, but none has been proposed.
+    #[error("player {0:?} cannot accept because no fleet has been proposed")]
+    NoProposal(P),
+    /// The player proposed a fleet which has more than one ship with the same ID.
+    #[error("player {0:?} proposed a fleet with a duplicate ship id")]
+    DuplicateShip(P),
+}
+
+/// Reason a [`GameSetup`][super::GameSetup] is not ready to start.
+#[derive(Debug, Error, Clone, Eq, PartialEq)]
+pub enum ValidateError<P: Debug> {
+    /// Fewer than two players have been added.
+    #[error("at least two players are required")]
+    TooFewPlayers,
+    /// The player has no ships, so they would be defeated before the game began.
+    #[error("player {0:?} has no ships added")]
+    NoShips(P),
+    /// The player has not placed all of their ships.
+    #[error("player {0:?} has not placed all of their ships")]
+    UnplacedShips(P),
+    /// Confirmation is required and the player has not confirmed their placements.
+    #[error("player {0:?} has not confirmed their placements")]
+    Unconfirmed(P),
+    /// A fairness policy is attached and the player's setup violates it. Use
+    /// [`validate_fairness`][super::GameSetup::validate_fairness] to list the violations.
+    #[error("player {0:?} has a setup which violates the fairness policy")]
+    Unfair(P),
+}
+
+/// A rule of a [`FairnessPolicy`][super::FairnessPolicy] broken by a player's setup.
+#[derive(Debug, Error, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum FairnessViolation<P: Debug, I: Debug> {
+    /// The player's fleet differs from the first player's fleet in this ship: either
+    /// only one of them has it, or it covers a different number of cells.
+    #[error("player {player:?} has a different fleet: ship {ship:?} does not match")]
+    FleetMismatch { player: P, ship: I },
+    /// Two of the player's ships occupy neighboring cells.
+    #[error("player {player:?} has ships {ship:?} and {other:?} next to each other")]
+    AdjacentShips { player: P, ship: I, other: I },
+    /// The player has more ship cells on the edge of their board than allowed.
+    #[error("player {player:?} has {count} ship cells on the edge of their board")]
+    TooManyEdgeCells { player: P, count: usize },
+    /// The player's board has different dimensions from the first player's board.
+    #[error("player {player:?} has different board dimensions")]
+    DimensionMismatch { player: P },
+}
+
+impl<P: Debug, I: Debug> FairnessViolation<P, I> {
+    /// Get the player whose setup breaks the rule.
+    pub fn player(&self) -> &P {
+        match self {
+            FairnessViolation::FleetMismatch { player, .. }
+            | FairnessViolation::AdjacentShips { player, .. }
+            | FairnessViolation::TooManyEdgeCells { player, .. }
+            | FairnessViolation::DimensionMismatch { player } => player,
+        }
+    }
+
+    /// Returns true if this is [`FairnessViolation::FleetMismatch`].
+    pub fn is_fleet_mismatch(&self) -> bool {
+        matches!(self, FairnessViolation::FleetMismatch { .. })
+    }
+
+    /// Returns true if this is [`FairnessViolation::AdjacentShips`].
+    pub fn is_adjacent_ships(&self) -> bool {
+        matches!(self, FairnessViolation::AdjacentShips { .. })
+    }
+
+    /// Returns true if this is [`FairnessViolation::TooManyEdgeCells`].
+    pub fn is_too_many_edge_cells(&self) -> bool {
+        matches!(self, FairnessViolation::TooManyEdgeCells { .. })
     }
+
+    /// Returns true if this is [`FairnessViolation::DimensionMismatch`].
+    pub fn is_dimension_mismatch(&self) -> bool {
+        matches!(self, FairnessViolation::DimensionMismatch { .. })
+    }
+}
+
+/// Error returned when setting a player's handicap fails.
+#[derive(Debug, Error, Clone, Eq, PartialEq)]
+pub enum HandicapError<P: Debug> {
+    /// The player is not part of the game.
+    #[error("player {0:?} is not part of the game")]
+    UnknownPlayer(P),
+}
+
+/// Error returned when selecting the player whose turn it is fails.
+#[derive(Debug, Error, Clone, Eq, PartialEq)]
+pub enum SetCurrentError<P: Debug> {
+    /// The player is not part of the game.
+    #[error("player {0:?} is not part of the game")]
+    UnknownPlayer(P),
+    /// The player has already been defeated.
+    #[error("player {0:?} has already been defeated")]
+    Defeated(P),
 }
 
 /// Reason why a particular tile could not be shot.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum CannotShootReason {
     /// The game is already over.
     AlreadyOver,
@@ -84,6 +244,46 @@ pub enum CannotShootReason {
 
     /// The tile specified was already shot.
     AlreadyShot,
+
+    /// The game is paused.
+    Paused,
+}
+
+impl CannotShootReason {
+    /// Returns true if this is [`CannotShootReason::AlreadyOver`].
+    pub fn is_already_over(&self) -> bool {
+        matches!(self, CannotShootReason::AlreadyOver)
+    }
+
+    /// Returns true if this is [`CannotShootReason::SelfShot`].
+    pub fn is_self_shot(&self) -> bool {
+        matches!(self, CannotShootReason::SelfShot)
+    }
+
+    /// Returns true if this is [`CannotShootReason::UnknownPlayer`].
+    pub fn is_unknown_player(&self) -> bool {
+        matches!(self, CannotShootReason::UnknownPlayer)
+    }
+
+    /// Returns true if this is [`CannotShootReason::AlreadyDefeated`].
+    pub fn is_already_defeated(&self) -> bool {
+        matches!(self, CannotShootReason::AlreadyDefeated)
+    }
+
+    /// Returns true if this is [`CannotShootReason::OutOfBounds`].
+    pub fn is_out_of_bounds(&self) -> bool {
+        matches!(self, CannotShootReason::OutOfBounds)
+    }
+
+    /// Returns true if this is [`CannotShootReason::AlreadyShot`].
+    pub fn is_already_shot(&self) -> bool {
+        matches!(self, CannotShootReason::AlreadyShot)
+    }
+
+    /// Returns true if this is [`CannotShootReason::Paused`].
+    pub fn is_paused(&self) -> bool {
+        matches!(self, CannotShootReason::Paused)
+    }
 }
 
 impl From<BoardCannotShootReason> for CannotShootReason {
@@ -97,7 +297,7 @@ impl From<BoardCannotShootReason> for CannotShootReason {
 }
 
 /// Error returned when trying to shoot a cell.
-#[derive(Debug, Error)]
+#[derive(Debug, Error, Clone, Eq, PartialEq)]
 #[error("could not shoot player {player:?} at cell {coord:?}: {reason:?}")]
 pub struct ShotError<P: Debug, C: Debug> {
     /// Reason why the cell could not be shot.
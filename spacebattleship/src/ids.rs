@@ -0,0 +1,268 @@
+// Copyright 2020 Zachary Stewart
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers for cheaply-cloneable [`ShipId`][crate::ships::ShipId] and
+//! [`PlayerId`][crate::game::uniform::PlayerId] types.
+use std::{
+    borrow::Borrow,
+    collections::HashSet,
+    fmt::{self, Debug, Display},
+    hash::{Hash, Hasher},
+    ops::Deref,
+    sync::Arc,
+};
+
+use crate::game::uniform::GameSetup;
+
+/// Shared, immutable ID value. Cloning an [`Interned`] only bumps a reference count, so
+/// it is cheap to store in every grid cell that a ship occupies.
+///
+/// Equality first checks whether both values share the same allocation, and only
+/// compares the underlying values if they don't, so two separately-allocated instances
+/// of equal values still compare equal. Hashing always uses the underlying value, to
+/// stay consistent with equality.
+pub struct Interned<T: ?Sized>(Arc<T>);
+
+impl<T: ?Sized> Interned<T> {
+    /// Wrap an existing [`Arc`] without copying the value.
+    pub fn from_arc(value: Arc<T>) -> Self {
+        Interned(value)
+    }
+
+    /// Returns true if both values share the same allocation.
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Arc::ptr_eq(&this.0, &other.0)
+    }
+
+    /// Extract the inner [`Arc`].
+    pub fn into_arc(self) -> Arc<T> {
+        self.0
+    }
+}
+
+// Derive for Clone would require T: Clone, but only the Arc needs to be cloned.
+impl<T: ?Sized> Clone for Interned<T> {
+    fn clone(&self) -> Self {
+        Interned(self.0.clone())
+    }
+}
+
+impl<T: ?Sized + PartialEq> PartialEq for Interned<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Self::ptr_eq(self, other) || *self.0 == *other.0
+    }
+}
+
+impl<T: ?Sized + Eq> Eq for Interned<T> {}
+
+impl<T: ?Sized + Hash> Hash for Interned<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (*self.0).hash(state)
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for Interned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(&*self.0, f)
+    }
+}
+
+impl<T: ?Sized + Display> Display for Interned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&*self.0, f)
+    }
+}
+
+impl<T: ?Sized> Deref for Interned<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: ?Sized> AsRef<T> for Interned<T> {
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: ?Sized> Borrow<T> for Interned<T> {
+    /// Allows looking up boards and ships keyed by [`Interned`] using the plain value.
+    fn borrow(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> From<T> for Interned<T> {
+    fn from(value: T) -> Self {
+        Interned(Arc::new(value))
+    }
+}
+
+impl From<&str> for Interned<str> {
+    fn from(value: &str) -> Self {
+        Interned(value.into())
+    }
+}
+
+impl From<String> for Interned<str> {
+    fn from(value: String) -> Self {
+        Interned(value.into())
+    }
+}
+
+/// Pool of [`Interned`] values. Interning an equal value twice returns the same
+/// allocation, so equality checks between IDs from the same pool take the pointer fast
+/// path.
+pub struct Interner<T: ?Sized + Eq + Hash> {
+    values: HashSet<Interned<T>>,
+}
+
+impl<T: ?Sized + Eq + Hash> Interner<T> {
+    /// Construct an empty [`Interner`].
+    pub fn new() -> Self {
+        Self {
+            values: HashSet::new(),
+        }
+    }
+
+    /// Get the interned instance of the given value, adding it to the pool if it isn't
+    /// there yet.
+    pub fn intern<V>(&mut self, value: V) -> Interned<T>
+    where
+        V: Borrow<T> + Into<Interned<T>>,
+    {
+        if let Some(existing) = self.values.get(value.borrow()) {
+            existing.clone()
+        } else {
+            let interned = value.into();
+            self.values.insert(interned.clone());
+            interned
+        }
+    }
+
+    /// Get the interned instance of the given value if it has already been interned.
+    pub fn get(&self, value: &T) -> Option<&Interned<T>> {
+        self.values.get(value)
+    }
+
+    /// Get the number of distinct values in the pool.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns true if no values have been interned.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl<T: ?Sized + Eq + Hash> Default for Interner<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Interned string ID, suitable for both players and ships.
+pub type InternedStr = Interned<str>;
+
+/// [`GameSetup`] keyed by interned strings for both player and ship IDs.
+pub type InternedGameSetup<D, S> = GameSetup<InternedStr, InternedStr, D, S>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{hash_map::DefaultHasher, HashMap};
+
+    use crate::{
+        board::rectangular::{Coordinate, RectDimensions},
+        ships::Line,
+    };
+
+    /// Hash the value with the standard hasher.
+    fn hash_of<T: Hash + ?Sized>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn equality_matches_values() {
+        let words = ["alpha", "beta", "", "alpha "];
+        for a in &words {
+            for b in &words {
+                let (x, y) = (InternedStr::from(*a), InternedStr::from(*b));
+                assert_eq!(x == y, a == b, "{:?} {:?}", a, b);
+                assert!(!InternedStr::ptr_eq(&x, &y));
+            }
+        }
+        let a = InternedStr::from("alpha");
+        let b = a.clone();
+        assert!(InternedStr::ptr_eq(&a, &b));
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn hash_matches_values() {
+        let a = InternedStr::from("alpha");
+        let b = InternedStr::from(String::from("alpha"));
+        assert_eq!(hash_of(&a), hash_of(&b));
+        assert_eq!(hash_of(&a), hash_of("alpha"));
+        assert_eq!(hash_of(&Interned::from(7u32)), hash_of(&7u32));
+
+        let mut map = HashMap::new();
+        map.insert(a, 1);
+        assert_eq!(map.get("alpha"), Some(&1));
+        assert_eq!(map.get(&b), Some(&1));
+        assert_eq!(map.get("beta"), None);
+    }
+
+    #[test]
+    fn interner_shares_allocations() {
+        let mut interner: Interner<str> = Interner::new();
+        assert!(interner.is_empty());
+        let a = interner.intern("alpha");
+        let b = interner.intern(String::from("alpha"));
+        let c = interner.intern("beta");
+        assert!(InternedStr::ptr_eq(&a, &b));
+        assert!(!InternedStr::ptr_eq(&a, &c));
+        assert_eq!(interner.len(), 2);
+        assert!(InternedStr::ptr_eq(interner.get("beta").unwrap(), &c));
+        assert!(interner.get("gamma").is_none());
+    }
+
+    #[test]
+    fn interned_game_setup() {
+        let mut interner: Interner<str> = Interner::new();
+        let mut setup: InternedGameSetup<RectDimensions, Line> = GameSetup::new();
+        for &player in &["alice", "bob"] {
+            let board = setup
+                .add_player(interner.intern(player), RectDimensions::new(4, 4))
+                .unwrap();
+            let ship = interner.intern("sub");
+            board.add_ship(ship.clone(), Line::new(2)).unwrap();
+            let cells = vec![Coordinate::new(0, 0), Coordinate::new(1, 0)];
+            board.get_ship_mut("sub").unwrap().place(cells).unwrap();
+        }
+        let mut game = setup.start().ok().unwrap();
+        // Separately allocated IDs find the same players and ships.
+        let outcome = game
+            .shoot(InternedStr::from("bob"), Coordinate::new(0, 0))
+            .unwrap();
+        assert!(outcome.is_hit());
+        let board = game.get_board("bob").unwrap();
+        assert!(board.get_ship("sub").unwrap().is_damaged());
+    }
+}
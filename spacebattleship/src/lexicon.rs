@@ -0,0 +1,70 @@
+// Copyright 2020 Zachary Stewart
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Replaceable user-facing strings, allowing frontends to present the game in other
+//! languages.
+
+/// Source of the user-facing strings for a game with ship IDs of type `I`.
+///
+/// Only [`ship_name`][Lexicon::ship_name] is required. The remaining methods default to
+/// English, so a lexicon for another language should override all of them.
+pub trait Lexicon<I> {
+    /// Get the name of the given ship.
+    fn ship_name(&self, ship: &I) -> String;
+
+    /// Parse a ship name or alias in this language. Returns `None` if the name isn't
+    /// recognized. The default recognizes no names.
+    fn parse_ship(&self, _name: &str) -> Option<I> {
+        None
+    }
+
+    /// Describe a shot that missed.
+    fn miss(&self) -> String {
+        "Miss.".to_owned()
+    }
+
+    /// Describe a shot that hit the given ship without sinking it.
+    fn hit(&self, ship: &I) -> String {
+        format!("Hit {}!", self.ship_name(ship))
+    }
+
+    /// Describe a shot that sunk the given ship.
+    fn sunk(&self, ship: &I) -> String {
+        format!("Sunk {}!", self.ship_name(ship))
+    }
+
+    /// Describe a shot that sunk the given ship, which was its player's last.
+    fn defeated(&self, ship: &I) -> String {
+        format!("Sunk {}! All ships sunk.", self.ship_name(ship))
+    }
+
+    /// Describe a shot that sunk the given ship and won the game.
+    fn victory(&self, ship: &I) -> String {
+        format!("Sunk {}! Victory!", self.ship_name(ship))
+    }
+
+    /// Describe a shot that was wasted on an already-shot cell.
+    fn wasted(&self) -> String {
+        "Already shot there. Shot wasted.".to_owned()
+    }
+}
+
+/// English [`Lexicon`] which names ships using a closure.
+pub struct DefaultLexicon<F>(pub F);
+
+impl<I, F: Fn(&I) -> String> Lexicon<I> for DefaultLexicon<F> {
+    fn ship_name(&self, ship: &I) -> String {
+        (self.0)(ship)
+    }
+}
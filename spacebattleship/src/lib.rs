@@ -12,6 +12,27 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "rng_gen")]
+pub mod ai;
 pub mod board;
+#[cfg(any(test, feature = "test-fixtures"))]
+pub mod fixtures;
 pub mod game;
+pub mod ids;
+pub mod lexicon;
+pub mod prelude;
 pub mod ships;
+
+pub use crate::{
+    board::{
+        common::{Coordinate2D, ParseCoordinateError},
+        rectangular::Wrapping,
+        AddShipError, BoardCreationError, CannotAddShipReason, CannotCreateBoardReason,
+        CannotPlaceReason, CannotRemoveShipReason, CannotShootReason, PlaceError, ShotError,
+        ShotMaskError,
+    },
+    game::uniform::{
+        AddPlayerError, ConfirmError, FairnessPolicy, FairnessViolation, HandicapError,
+        MirrorError, NegotiationError, SetCurrentError, TurnOrderError, ValidateError,
+    },
+};
@@ -0,0 +1,78 @@
+// Copyright 2020 Zachary Stewart
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Re-exports of the types needed for common games, for glob importing with
+//! `use spacebattleship::prelude::*`.
+//!
+//! The [`simple`] and [`uniform`] game modules are re-exported as modules rather than
+//! having their contents imported, since both have their own `Game`, `GameSetup`, and
+//! `ShotOutcome`.
+//!
+//! # Examples
+//!
+//! A simple game, where the second player's Destroyer is sunk:
+//!
+//! ```
+//! use spacebattleship::prelude::*;
+//! use simple::{Orientation, Player, Ship, ShotOutcome};
+//!
+//! let mut setup = simple::GameSetup::new();
+//! for &player in &[Player::P1, Player::P2] {
+//!     for (y, &ship) in Ship::ALL.iter().enumerate() {
+//!         let start = Coordinate2D::new(0, 2 * y);
+//!         setup.place_ship(player, ship, start, Orientation::Right).unwrap();
+//!     }
+//! }
+//! let destroyer = Coordinate2D::new(0, 8);
+//! let placement = setup.get_placement(Player::P2, Ship::Destroyer).unwrap();
+//! assert_eq!(placement.start(), &destroyer);
+//! let mut game = setup.start().ok().unwrap();
+//! assert_eq!(game.shoot(Player::P2, destroyer), Ok(ShotOutcome::Hit(Ship::Destroyer)));
+//! game.shoot(Player::P1, Coordinate2D::new(9, 9)).unwrap();
+//! assert_eq!(
+//!     game.shoot(Player::P2, Coordinate2D::new(1, 8)),
+//!     Ok(ShotOutcome::Sunk(Ship::Destroyer)),
+//! );
+//! ```
+//!
+//! A uniform game with named players on a board which wraps horizontally:
+//!
+//! ```
+//! use spacebattleship::prelude::*;
+//!
+//! let dim = RectDimensions::new_wrapping(5, 5, Wrapping::Horizontal);
+//! let mut setup = uniform::GameSetup::new();
+//! for &player in &["ann", "bo"] {
+//!     let board = setup.add_player(player, dim).unwrap();
+//!     board.add_ship("sub", Line::new(3)).unwrap();
+//!     // Crosses the seam between the last and first columns.
+//!     let cells = vec![(3, 0), (4, 0), (0, 0)];
+//!     let cells = cells.into_iter().map(|(x, y)| Coordinate2D::new(x, y)).collect();
+//!     board.get_ship_mut(&"sub").unwrap().place(cells).unwrap();
+//! }
+//! let mut game = setup.start().ok().unwrap();
+//! assert_eq!(game.current(), &"ann");
+//! assert!(game.shoot("bo", Coordinate2D::new(0, 0)).unwrap().is_hit());
+//! assert_eq!(game.current(), &"bo");
+//! ```
+
+pub use crate::{
+    board::{
+        common::Coordinate2D, rectangular::RectDimensions, rectangular::Wrapping, Board,
+        BoardSetup, ColinearCheck, Dimensions, LinearStep, ShotOutcome as BoardShotOutcome,
+    },
+    game::{simple, uniform, uniform::PlayerId},
+    lexicon::Lexicon,
+    ships::{Line, ShipId, ShipShape, StraightLine},
+};
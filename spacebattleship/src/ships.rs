@@ -13,11 +13,11 @@
 // limitations under the License.
 
 //! Types used for defining ships and their shapes.
-use std::{fmt::Debug, hash::Hash};
+use std::{collections::HashMap, fmt::Debug, hash::Hash};
 
 use crate::board::Dimensions;
 
-pub use self::linear::Line;
+pub use self::linear::{Line, StraightLine};
 
 mod linear;
 
@@ -40,10 +40,10 @@ pub trait ShipShape<D: Dimensions + ?Sized> {
         &'a self,
         coord: D::Coordinate,
         dim: &'a D,
-    ) -> ProjectIter<D, Self::ProjectIterState> {
+    ) -> ProjectIter<'a, D, Self::ProjectIterState> {
         ProjectIter {
             shape: self,
-            dim: dim,
+            dim,
             state: Self::ProjectIterState::start(self, dim, coord),
         }
     }
@@ -60,6 +60,26 @@ pub trait ShipShape<D: Dimensions + ?Sized> {
 /// did not generate.
 pub type ShapeProjection<C> = Vec<C>;
 
+/// Returns true if two projections cover the same set of cells, regardless of the order
+/// of the cells. For example, a line projected up from one end and a line projected down
+/// from the other end cover the same cells, but are not equal as projections.
+pub fn same_cells<C: Eq + Hash>(a: &[C], b: &[C]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut counts = HashMap::with_capacity(a.len());
+    for cell in a {
+        *counts.entry(cell).or_insert(0usize) += 1;
+    }
+    b.iter().all(|cell| match counts.get_mut(cell) {
+        Some(count) if *count > 0 => {
+            *count -= 1;
+            true
+        }
+        _ => false,
+    })
+}
+
 /// State type for the ship projection iterator.
 pub trait ProjectIterState<D: Dimensions + ?Sized> {
     type ShipShape: ShipShape<D> + ?Sized;
@@ -93,3 +113,38 @@ where
         self.state.next(self.shape, self.dim)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::rectangular::{Coordinate, RectDimensions};
+
+    #[test]
+    fn same_cells_ignores_order() {
+        let dim = RectDimensions::new(5, 5);
+        let line = Line::new(3);
+        let column = |proj: &ShapeProjection<Coordinate>| proj.iter().all(|c| c.x == 2);
+        let down = line
+            .project(Coordinate::new(2, 1), &dim)
+            .find(|proj| column(proj) && proj[1].y > proj[0].y)
+            .unwrap();
+        let up = line
+            .project(Coordinate::new(2, 3), &dim)
+            .find(|proj| column(proj) && proj[1].y < proj[0].y)
+            .unwrap();
+        assert_ne!(down, up);
+        assert!(same_cells(&down, &up));
+        assert!(same_cells(&up, &down));
+
+        let shorter = &down[..2];
+        assert!(!same_cells(shorter, &up));
+        let shifted = Line::new(3)
+            .project(Coordinate::new(2, 2), &dim)
+            .find(|proj| column(proj) && proj[1].y > proj[0].y)
+            .unwrap();
+        assert!(!same_cells(&down, &shifted));
+        // Repeated cells are counted, not just checked for membership.
+        let (a, b) = (Coordinate::new(0, 0), Coordinate::new(1, 0));
+        assert!(!same_cells(&[a, a, b], &[a, b, b]));
+    }
+}
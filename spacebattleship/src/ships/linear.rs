@@ -14,7 +14,7 @@
 use std::collections::HashSet;
 
 use crate::{
-    board::{ColinearCheck, Dimensions},
+    board::{ColinearCheck, Dimensions, LinearStep},
     ships::{ProjectIterState, ShapeProjection, ShipShape},
 };
 
@@ -30,9 +30,31 @@ impl Line {
     }
 
     /// Get the length of this ship.
+    #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize {
         self.0
     }
+
+    /// Get the single projection of this ship which starts at `start` and continues
+    /// through its neighbor `next`, without building the projections in any other
+    /// direction. Takes time proportional to the length of the ship. Returns `None` if
+    /// `start` is out of bounds, `next` isn't a neighbor of it, or the ship would leave
+    /// the board or wrap around onto itself. Ships of length 1 ignore `next`.
+    pub fn project_in_direction<D: LinearStep + ?Sized>(
+        &self,
+        start: D::Coordinate,
+        next: D::Coordinate,
+        dim: &D,
+    ) -> Option<ShapeProjection<D::Coordinate>> {
+        dim.try_linearize(&start)?;
+        if self.len() == 1 {
+            Some(vec![start])
+        } else if dim.is_neighbor(&start, &next) {
+            try_step_route(dim, self.len(), start, next)
+        } else {
+            None
+        }
+    }
 }
 
 impl<D: Dimensions + ColinearCheck + ?Sized> ShipShape<D> for Line {
@@ -53,7 +75,7 @@ impl<D: Dimensions + ColinearCheck + ?Sized> ShipShape<D> for Line {
                 return false;
             }
         }
-        return true;
+        true
     }
 }
 
@@ -112,13 +134,18 @@ impl<D: Dimensions + ColinearCheck + ?Sized> ProjectIterState<D>
     }
 }
 
-/// Attempt to build a route in the given direction from the start.
+/// Attempt to build a route in the given direction from the start. Always terminates,
+/// even if the [`ColinearCheck`] impl is inconsistent: the route never revisits a cell,
+/// including the start, and can never be longer than the number of cells in `dim`.
 fn try_build_route<D: Dimensions + ColinearCheck + ?Sized>(
     dim: &D,
     len: usize,
     start: D::Coordinate,
     dir: D::Coordinate,
 ) -> Option<ShapeProjection<D::Coordinate>> {
+    if len > dim.total_size() || dir == start {
+        return None;
+    }
     let mut route = Vec::with_capacity(len);
     let mut visited = HashSet::with_capacity(len);
     route.push(start.clone());
@@ -132,7 +159,10 @@ fn try_build_route<D: Dimensions + ColinearCheck + ?Sized>(
         // Check the neighbors of the last cell, to find one that's in the same direction
         // and not yet visited.
         for neighbor in dim.neighbors(last) {
-            if dim.is_colinear(&start, &dir, &neighbor) && visited.insert(neighbor.clone()) {
+            if neighbor != start
+                && dim.is_colinear(&start, &dir, &neighbor)
+                && visited.insert(neighbor.clone())
+            {
                 route.push(neighbor.clone());
                 last = neighbor;
                 // Once we find a neighbor at this position along the route, continue.
@@ -142,5 +172,244 @@ fn try_build_route<D: Dimensions + ColinearCheck + ?Sized>(
         // If no neighbor matches along the line, there's nowhere else to go from here.
         return None;
     }
-    return Some(route);
+    Some(route)
+}
+
+/// A linear ship shape, with a given length. Unlike [`Line`], routes are built by
+/// following a consistent direction with [`LinearStep`], so it can be used on dimensions
+/// which don't implement [`ColinearCheck`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct StraightLine(usize);
+
+impl StraightLine {
+    /// Construct a straight ship with the specified length. Panics if len is 0.
+    pub fn new(len: usize) -> Self {
+        assert!(len > 0);
+        StraightLine(len)
+    }
+
+    /// Get the length of this ship.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.0
+    }
+}
+
+impl<D: LinearStep + ?Sized> ShipShape<D> for StraightLine {
+    type ProjectIterState = StraightLineProjectIterState<D::Coordinate>;
+
+    fn is_valid_placement(&self, proj: &ShapeProjection<D::Coordinate>, dim: &D) -> bool {
+        if proj.len() != self.len() || dim.try_linearize(&proj[0]).is_none() {
+            return false;
+        }
+        if proj.len() == 1 {
+            return true;
+        }
+        dim.is_neighbor(&proj[0], &proj[1])
+            && try_step_route(dim, self.len(), proj[0].clone(), proj[1].clone()).as_ref()
+                == Some(proj)
+    }
+}
+
+/// State of the projection iterator for StraightLine shape.
+pub struct StraightLineProjectIterState<C> {
+    start: C,
+    directions: Vec<C>,
+    next_dir: usize,
+}
+
+impl<D: LinearStep + ?Sized> ProjectIterState<D> for StraightLineProjectIterState<D::Coordinate> {
+    type ShipShape = StraightLine;
+
+    fn start(shape: &Self::ShipShape, dim: &D, coord: D::Coordinate) -> Self {
+        let directions = if shape.len() == 1 {
+            Vec::new()
+        } else {
+            dim.neighbors(coord.clone()).collect()
+        };
+        Self {
+            start: coord,
+            directions,
+            next_dir: 0,
+        }
+    }
+
+    fn next(&mut self, shape: &Self::ShipShape, dim: &D) -> Option<ShapeProjection<D::Coordinate>> {
+        if shape.len() == 1 {
+            if self.next_dir == 0 {
+                self.next_dir = 1;
+                dim.try_linearize(&self.start)
+                    .map(|_| vec![self.start.clone()])
+            } else {
+                None
+            }
+        } else {
+            while self.next_dir < self.directions.len() {
+                let dir = self.directions[self.next_dir].clone();
+                self.next_dir += 1;
+                if let Some(route) = try_step_route(dim, shape.0, self.start.clone(), dir) {
+                    return Some(route);
+                }
+            }
+            None
+        }
+    }
+}
+
+/// Attempt to build a route of the given length by stepping from the start through the
+/// given neighbor. Fails if the route leaves the board or wraps around onto itself.
+fn try_step_route<D: LinearStep + ?Sized>(
+    dim: &D,
+    len: usize,
+    start: D::Coordinate,
+    dir: D::Coordinate,
+) -> Option<ShapeProjection<D::Coordinate>> {
+    if len > dim.total_size() || dir == start {
+        return None;
+    }
+    let mut route = Vec::with_capacity(len);
+    let mut visited = HashSet::with_capacity(len);
+    visited.insert(start.clone());
+    visited.insert(dir.clone());
+    route.push(start);
+    route.push(dir);
+    while route.len() < len {
+        let next = dim.step(&route[route.len() - 2], &route[route.len() - 1])?;
+        if !visited.insert(next.clone()) {
+            return None;
+        }
+        route.push(next);
+    }
+    Some(route)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::{BoardSetup, Coordinate, NeighborIterState};
+
+    /// Position on a [`Track`].
+    #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+    struct Pos(usize);
+
+    impl Coordinate for Pos {}
+
+    /// A single row of cells which supports [`LinearStep`] but not [`ColinearCheck`].
+    #[derive(Debug)]
+    struct Track(usize);
+
+    impl Dimensions for Track {
+        type Coordinate = Pos;
+        type NeighborIterState = TrackNeighbors;
+
+        fn total_size(&self) -> usize {
+            self.0
+        }
+
+        fn try_linearize(&self, coord: &Pos) -> Option<usize> {
+            if coord.0 < self.0 {
+                Some(coord.0)
+            } else {
+                None
+            }
+        }
+
+        fn try_un_linearize(&self, index: usize) -> Option<Pos> {
+            if index < self.0 {
+                Some(Pos(index))
+            } else {
+                None
+            }
+        }
+    }
+
+    impl LinearStep for Track {
+        fn step(&self, prev: &Pos, cur: &Pos) -> Option<Pos> {
+            let next = (2 * cur.0).checked_sub(prev.0)?;
+            if self.is_neighbor(prev, cur) && next < self.0 {
+                Some(Pos(next))
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Neighbor iter for [`Track`]: the cells before and after.
+    struct TrackNeighbors(std::vec::IntoIter<Pos>);
+
+    impl NeighborIterState for TrackNeighbors {
+        type Dimensions = Track;
+
+        fn start(dim: &Track, coord: Pos) -> Self {
+            let before = coord.0.checked_sub(1);
+            let after = Some(coord.0 + 1).filter(|&i| i < dim.0);
+            TrackNeighbors(
+                before
+                    .into_iter()
+                    .chain(after)
+                    .map(Pos)
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+            )
+        }
+
+        fn next(&mut self, _dim: &Track) -> Option<Pos> {
+            self.0.next()
+        }
+    }
+
+    #[test]
+    fn straight_line_without_colinear_check() {
+        let dim = Track(6);
+        let ship = StraightLine::new(3);
+        let from_middle: Vec<_> = ship.project(Pos(2), &dim).collect();
+        assert_eq!(
+            from_middle,
+            [vec![Pos(2), Pos(1), Pos(0)], vec![Pos(2), Pos(3), Pos(4)]]
+        );
+        let from_end: Vec<_> = ship.project(Pos(5), &dim).collect();
+        assert_eq!(from_end, [vec![Pos(5), Pos(4), Pos(3)]]);
+        assert!(!ship.is_valid_placement(&vec![Pos(1), Pos(2), Pos(1)], &dim));
+        assert!(!ship.is_valid_placement(&vec![Pos(1), Pos(3), Pos(5)], &dim));
+
+        let mut setup = BoardSetup::new(dim);
+        setup
+            .add_ship("cruiser", ship)
+            .unwrap()
+            .place(vec![Pos(3), Pos(4), Pos(5)])
+            .unwrap();
+        let mut board = setup.start().ok().unwrap();
+        for i in 3..6 {
+            board.shoot(Pos(i)).unwrap();
+        }
+        assert!(board.defeated());
+    }
+
+    #[test]
+    fn line_fills_small_wrapping_row() {
+        use crate::board::rectangular::{Coordinate as Coord, RectDimensions, Wrapping};
+
+        let dim = RectDimensions::new_wrapping(3, 1, Wrapping::Horizontal);
+        let row = |xs: &[usize]| xs.iter().map(|&x| Coord::new(x, 0)).collect::<Vec<_>>();
+        let projections: Vec<_> = Line::new(3).project(Coord::new(1, 0), &dim).collect();
+        assert_eq!(projections.len(), 2);
+        assert!(projections.contains(&row(&[1, 2, 0])));
+        assert!(projections.contains(&row(&[1, 0, 2])));
+        for proj in &projections {
+            assert!(Line::new(3).is_valid_placement(proj, &dim));
+        }
+        assert_eq!(Line::new(4).project(Coord::new(1, 0), &dim).count(), 0);
+
+        let mut setup = BoardSetup::new(dim);
+        setup
+            .add_ship("cruiser", Line::new(3))
+            .unwrap()
+            .place(row(&[2, 0, 1]))
+            .unwrap();
+        let mut board = setup.start().ok().unwrap();
+        for x in 0..3 {
+            board.shoot(Coord::new(x, 0)).unwrap();
+        }
+        assert!(board.defeated());
+    }
 }
@@ -0,0 +1,406 @@
+// Copyright 2020 Zachary Stewart
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Plays random legal games while round-tripping boards through shot masks, resets,
+//! replays, and recorded changes, checking after every step that the game and every copy
+//! of each board agree with a reference simulation which only applies the shots in
+//! order.
+
+use std::collections::HashSet;
+
+use proptest::prelude::*;
+use spacebattleship::{
+    board::{
+        rectangular::{Coordinate, RectDimensions},
+        Board, BoardSetup, CannotShootReason,
+    },
+    game::{simple::Ship, uniform},
+    ships::Line,
+};
+
+type Game = uniform::Game<usize, Ship, RectDimensions>;
+
+/// Cells occupied by each ship of a single player.
+type Layout = Vec<(Ship, Vec<Coordinate>)>;
+
+/// Width and height of every board.
+const SIZE: usize = 10;
+
+/// Step applied to the game and the reference simulation.
+#[derive(Debug, Clone)]
+enum Op {
+    /// The current player shoots. The first value picks the target among the other
+    /// players who are still in the game, and the second picks one of the target's
+    /// un-shot cells in row-major order.
+    Shoot(usize, usize),
+    /// Save a player's board as a shot mask, reset it, then restore the mask.
+    MaskRoundTrip(usize),
+    /// Rebuild a player's board from its layout and apply its shots as damage.
+    Replay(usize),
+    /// Reset the game and replay every shot so far in order.
+    ResetReplay,
+}
+
+/// Reference simulation, which only records the shots in the order they were fired.
+struct Model {
+    layouts: Vec<Layout>,
+    /// Every shot so far as `(target, coord)`, in order.
+    log: Vec<(usize, Coordinate)>,
+    /// Cells of each player's board which have been shot.
+    shots: Vec<HashSet<Coordinate>>,
+    current: usize,
+}
+
+impl Model {
+    fn new(layouts: Vec<Layout>) -> Self {
+        Self {
+            shots: vec![HashSet::new(); layouts.len()],
+            layouts,
+            log: Vec::new(),
+            current: 0,
+        }
+    }
+
+    /// Number of hits on each of the player's ships, in layout order.
+    fn ship_hits(&self, pid: usize) -> Vec<usize> {
+        let shots = &self.shots[pid];
+        self.layouts[pid]
+            .iter()
+            .map(|(_, cells)| cells.iter().filter(|cell| shots.contains(cell)).count())
+            .collect()
+    }
+
+    fn defeated(&self, pid: usize) -> bool {
+        self.ship_hits(pid)
+            .iter()
+            .zip(&self.layouts[pid])
+            .all(|(&hits, (_, cells))| hits == cells.len())
+    }
+
+    fn alive(&self) -> Vec<usize> {
+        (0..self.layouts.len())
+            .filter(|&pid| !self.defeated(pid))
+            .collect()
+    }
+
+    fn winner(&self) -> Option<usize> {
+        match self.alive()[..] {
+            [pid] => Some(pid),
+            _ => None,
+        }
+    }
+
+    /// Interpret a [`Op::Shoot`] as a legal shot for the current player. Returns `None`
+    /// if the game is over.
+    fn pick_shot(&self, target: usize, cell: usize) -> Option<(usize, Coordinate)> {
+        if self.winner().is_some() {
+            return None;
+        }
+        let targets: Vec<_> = self
+            .alive()
+            .into_iter()
+            .filter(|&pid| pid != self.current)
+            .collect();
+        let target = targets[target % targets.len()];
+        let shots = &self.shots[target];
+        let unshot: Vec<_> = cells().filter(|cell| !shots.contains(cell)).collect();
+        Some((target, unshot[cell % unshot.len()]))
+    }
+
+    /// Record a shot and pass the turn to the next player still in the game.
+    fn shoot(&mut self, target: usize, coord: Coordinate) {
+        self.log.push((target, coord));
+        self.shots[target].insert(coord);
+        let players = self.layouts.len();
+        self.current = (1..=players)
+            .map(|step| (self.current + step) % players)
+            .find(|&pid| !self.defeated(pid))
+            .unwrap();
+    }
+}
+
+/// Every cell of a board, in row-major order.
+fn cells() -> impl Iterator<Item = Coordinate> {
+    (0..SIZE).flat_map(|y| (0..SIZE).map(move |x| Coordinate::new(x, y)))
+}
+
+/// Cells of the board which have been shot.
+fn board_shots(board: &Board<Ship, RectDimensions>) -> HashSet<Coordinate> {
+    cells()
+        .filter(|&cell| board.get_coord(cell).unwrap().hit())
+        .collect()
+}
+
+/// Shot mask with the bits of the given cells set, in the format of
+/// [`Board::shot_mask`].
+fn mask_of(shots: &HashSet<Coordinate>) -> Vec<u64> {
+    let mut mask = vec![0; (SIZE * SIZE).div_ceil(64)];
+    for coord in shots {
+        let i = coord.y * SIZE + coord.x;
+        mask[i / 64] |= 1 << (i % 64);
+    }
+    mask
+}
+
+/// Check a board against the reference simulation's view of the given player.
+fn check_board(
+    what: &str,
+    board: &Board<Ship, RectDimensions>,
+    model: &Model,
+    pid: usize,
+) -> Result<(), String> {
+    if board.shot_mask() != mask_of(&model.shots[pid]) {
+        return Err(format!("{} of player {} has the wrong shots", what, pid));
+    }
+    let hits: Vec<_> = model.layouts[pid]
+        .iter()
+        .map(|(ship, _)| board.get_ship(ship).unwrap().hit_coords().count())
+        .collect();
+    if hits != model.ship_hits(pid) {
+        return Err(format!(
+            "{} of player {} has ship hits {:?}, expected {:?}",
+            what,
+            pid,
+            hits,
+            model.ship_hits(pid)
+        ));
+    }
+    if board.defeated() != model.defeated(pid) {
+        return Err(format!("{} of player {} disagrees on defeat", what, pid));
+    }
+    Ok(())
+}
+
+/// Boards and game checked against the reference simulation.
+struct Harness {
+    model: Model,
+    game: Game,
+    /// Standalone copy of each player's board, recording its changes.
+    boards: Vec<Board<Ship, RectDimensions>>,
+    /// Copy of each player's board kept up to date only by applying the changes drained
+    /// from `boards`.
+    mirrors: Vec<Board<Ship, RectDimensions>>,
+}
+
+impl Harness {
+    fn new(layouts: Vec<Layout>) -> Self {
+        let mut setup = uniform::GameSetup::<usize, Ship, RectDimensions, Line>::new();
+        for (pid, layout) in layouts.iter().enumerate() {
+            let board = setup
+                .add_player(pid, RectDimensions::new(SIZE, SIZE))
+                .unwrap();
+            for (ship, cells) in layout {
+                board.add_ship(*ship, Line::new(ship.len())).unwrap();
+                board
+                    .get_ship_mut(ship)
+                    .unwrap()
+                    .place(cells.clone())
+                    .unwrap();
+            }
+        }
+        let game = setup.start().ok().unwrap();
+        let boards = layouts
+            .iter()
+            .map(|layout| {
+                let mut board = board(layout);
+                board.set_record_changes(true);
+                board
+            })
+            .collect();
+        let mirrors = layouts.iter().map(board).collect();
+        Self {
+            model: Model::new(layouts),
+            game,
+            boards,
+            mirrors,
+        }
+    }
+
+    fn apply(&mut self, op: &Op) -> Result<(), String> {
+        match *op {
+            Op::Shoot(target, cell) => {
+                let (target, coord) = match self.model.pick_shot(target, cell) {
+                    Some(shot) => shot,
+                    None => return Ok(()),
+                };
+                self.game
+                    .shoot(target, coord)
+                    .map_err(|err| format!("game rejected {:?}: {}", coord, err))?;
+                self.boards[target]
+                    .shoot(coord)
+                    .map_err(|err| format!("board rejected {:?}: {}", coord, err))?;
+                self.model.shoot(target, coord);
+            }
+            Op::MaskRoundTrip(pid) => {
+                let players = self.boards.len();
+                let board = &mut self.boards[pid % players];
+                let mask = board.shot_mask();
+                board.reset_damage();
+                if !board_shots(board).is_empty() {
+                    return Err("reset left shots on the board".to_owned());
+                }
+                board
+                    .apply_shot_mask(&mask)
+                    .map_err(|err| err.to_string())?;
+                if board.shot_mask() != mask {
+                    return Err("restored mask differs".to_owned());
+                }
+            }
+            Op::Replay(pid) => {
+                let pid = pid % self.boards.len();
+                let shots: Vec<_> = self
+                    .model
+                    .log
+                    .iter()
+                    .filter(|(target, _)| *target == pid)
+                    .map(|(_, coord)| *coord)
+                    .collect();
+                match board(&self.model.layouts[pid]).with_damage(shots) {
+                    Ok(replayed) => check_board("replayed board", &replayed, &self.model, pid)?,
+                    Err(err) if err.reason() == CannotShootReason::AlreadyDefeated => {
+                        if !self.model.defeated(pid) {
+                            return Err(format!("replay of player {} was refused", pid));
+                        }
+                    }
+                    Err(err) => return Err(format!("replay failed: {}", err)),
+                }
+            }
+            Op::ResetReplay => {
+                self.game.reset();
+                for &(target, coord) in &self.model.log {
+                    self.game
+                        .shoot(target, coord)
+                        .map_err(|err| format!("replay rejected {:?}: {}", coord, err))?;
+                }
+            }
+        }
+        for (board, mirror) in self.boards.iter_mut().zip(&mut self.mirrors) {
+            for change in board.drain_changes() {
+                mirror
+                    .apply_change(&change)
+                    .map_err(|err| err.to_string())?;
+            }
+        }
+        self.check()
+    }
+
+    /// Check the game and every board against the reference simulation.
+    fn check(&self) -> Result<(), String> {
+        for pid in 0..self.model.layouts.len() {
+            check_board(
+                "game board",
+                self.game.get_board(&pid).unwrap(),
+                &self.model,
+                pid,
+            )?;
+            check_board("board", &self.boards[pid], &self.model, pid)?;
+            check_board("mirror", &self.mirrors[pid], &self.model, pid)?;
+        }
+        if self.game.winner().copied() != self.model.winner() {
+            return Err(format!(
+                "game winner is {:?}, expected {:?}",
+                self.game.winner(),
+                self.model.winner()
+            ));
+        }
+        if self.model.winner().is_none() && *self.game.current() != self.model.current {
+            return Err(format!(
+                "it is player {}'s turn, expected {}",
+                self.game.current(),
+                self.model.current
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Build a started board with the given layout.
+fn board(layout: &Layout) -> Board<Ship, RectDimensions> {
+    let mut setup = BoardSetup::new(RectDimensions::new(SIZE, SIZE));
+    for (ship, cells) in layout {
+        setup
+            .add_ship(*ship, Line::new(ship.len()))
+            .unwrap()
+            .place(cells.clone())
+            .unwrap();
+    }
+    setup.start().ok().unwrap()
+}
+
+/// Layout with each ship on a different row, facing right from the given column.
+fn layout(rows: &[usize], columns: &[usize]) -> Layout {
+    Ship::ALL
+        .iter()
+        .zip(rows.iter().zip(columns))
+        .map(|(&ship, (&y, &x))| {
+            let x = x % (SIZE - ship.len() + 1);
+            let cells = (x..x + ship.len()).map(|x| Coordinate::new(x, y)).collect();
+            (ship, cells)
+        })
+        .collect()
+}
+
+fn layout_strategy() -> impl Strategy<Value = Layout> {
+    (
+        Just((0..SIZE).collect::<Vec<_>>()).prop_shuffle(),
+        prop::collection::vec(0..SIZE, Ship::ALL.len()),
+    )
+        .prop_map(|(rows, columns)| layout(&rows, &columns))
+}
+
+fn op_strategy() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        8 => (any::<usize>(), any::<usize>()).prop_map(|(t, c)| Op::Shoot(t, c)),
+        1 => any::<usize>().prop_map(Op::MaskRoundTrip),
+        1 => any::<usize>().prop_map(Op::Replay),
+        1 => Just(Op::ResetReplay),
+    ]
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(200))]
+
+    #[test]
+    fn shots_are_consistent(
+        layouts in prop::collection::vec(layout_strategy(), 2..=3),
+        ops in prop::collection::vec(op_strategy(), 0..200),
+    ) {
+        let mut harness = Harness::new(layouts);
+        harness.check().map_err(TestCaseError::fail)?;
+        for op in &ops {
+            harness.apply(op).map_err(|msg| TestCaseError::fail(format!("{:?}: {}", op, msg)))?;
+        }
+    }
+}
+
+/// The check must notice a board which diverges from the reference simulation.
+#[test]
+fn divergence_is_caught() {
+    let layouts = vec![
+        layout(&[0, 2, 4, 6, 8], &[0; 5]),
+        layout(&[1, 3, 5, 7, 9], &[0; 5]),
+    ];
+    let mut harness = Harness::new(layouts);
+    for op in &[Op::Shoot(0, 0), Op::Shoot(0, 0), Op::MaskRoundTrip(0)] {
+        harness.apply(op).unwrap();
+    }
+
+    // Shoot one copy of a board without telling the model.
+    harness.boards[1].shoot(Coordinate::new(9, 9)).unwrap();
+    assert!(harness.check().is_err());
+
+    // Once the change reaches the mirror, the mirror is wrong too.
+    let err = harness.apply(&Op::MaskRoundTrip(1)).unwrap_err();
+    assert!(err.contains("player 1"), "{}", err);
+}
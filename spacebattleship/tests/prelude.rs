@@ -0,0 +1,102 @@
+// Copyright 2020 Zachary Stewart
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Pins the surface of the prelude and the crate-root re-exports. Every item is named
+//! explicitly, so removing or renaming one fails to build here rather than downstream.
+
+use spacebattleship::prelude::{
+    simple, uniform, Board, BoardSetup, BoardShotOutcome, ColinearCheck, Coordinate2D, Dimensions,
+    Lexicon, Line, LinearStep, PlayerId, RectDimensions, ShipId, ShipShape, StraightLine, Wrapping,
+};
+
+/// Compiles only if `D` is usable as linear dimensions.
+fn linear_dimensions<D: Dimensions + ColinearCheck + LinearStep>() {}
+
+/// Compiles only if `S` is a ship shape for `D`.
+fn ship_shape<D: Dimensions, S: ShipShape<D>>() {}
+
+/// Compiles only if `P` and `I` are usable as player and ship IDs.
+fn ids<P: PlayerId, I: ShipId>() {}
+
+#[test]
+fn prelude_items_resolve() {
+    linear_dimensions::<RectDimensions>();
+    ship_shape::<RectDimensions, Line>();
+    ship_shape::<RectDimensions, StraightLine>();
+    ids::<simple::Player, simple::Ship>();
+    ids::<String, String>();
+
+    let dim = RectDimensions::new_wrapping(4, 3, Wrapping::Horizontal);
+    assert_eq!(dim.total_size(), 12);
+    let coord: Coordinate2D = Coordinate2D::new(3, 2);
+    assert!(dim.try_linearize(&coord).is_some());
+
+    let lexicon: &dyn Lexicon<simple::Ship> = &simple::English;
+    assert_eq!(lexicon.ship_name(&simple::Ship::Carrier), "carrier");
+
+    let mut setup: BoardSetup<u8, RectDimensions, Line> = BoardSetup::new(dim);
+    setup
+        .add_ship(0, Line::new(2))
+        .unwrap()
+        .place(vec![Coordinate2D::new(3, 0), Coordinate2D::new(0, 0)])
+        .unwrap();
+    let mut board: Board<u8, RectDimensions> = setup.start().ok().unwrap();
+    let outcome: BoardShotOutcome<u8> = board.shoot(Coordinate2D::new(0, 0)).unwrap();
+    assert!(outcome.is_hit());
+
+    let game: uniform::GameSetup<u8, u8, RectDimensions, Line> = uniform::GameSetup::new();
+    assert_eq!(game.iter_boards().count(), 0);
+    let game: simple::GameSetup = simple::GameSetup::new();
+    assert!(!game.ready());
+}
+
+#[test]
+fn root_items_resolve() {
+    use spacebattleship::{
+        AddPlayerError, AddShipError, BoardCreationError, CannotAddShipReason,
+        CannotCreateBoardReason, CannotPlaceReason, CannotRemoveShipReason, CannotShootReason,
+        ConfirmError, Coordinate2D, FairnessPolicy, FairnessViolation, HandicapError, MirrorError,
+        NegotiationError, ParseCoordinateError, PlaceError, SetCurrentError, ShotError,
+        ShotMaskError, TurnOrderError, ValidateError, Wrapping,
+    };
+
+    /// Compiles only if `E` is an error type.
+    fn error<E: std::error::Error>() {}
+
+    /// Compiles only if `T` names a type.
+    fn named<T>() {}
+
+    error::<AddPlayerError<u8, RectDimensions>>();
+    error::<AddShipError<u8, Line>>();
+    error::<BoardCreationError<RectDimensions>>();
+    error::<ConfirmError<u8>>();
+    error::<HandicapError<u8>>();
+    error::<MirrorError<u8>>();
+    error::<NegotiationError<u8>>();
+    error::<ParseCoordinateError>();
+    error::<PlaceError<Line>>();
+    error::<SetCurrentError<u8>>();
+    error::<ShotError<Coordinate2D>>();
+    error::<ShotMaskError>();
+    error::<TurnOrderError<u8>>();
+    error::<ValidateError<u8>>();
+    named::<CannotAddShipReason>();
+    named::<CannotCreateBoardReason>();
+    named::<CannotPlaceReason>();
+    named::<CannotRemoveShipReason>();
+    named::<CannotShootReason>();
+    named::<FairnessPolicy>();
+    named::<FairnessViolation<u8, u8>>();
+    named::<Wrapping>();
+}